@@ -0,0 +1,563 @@
+//! Static arbitrage module.
+//!
+//! This module scans the resting quotes of option chains for static
+//! arbitrages that can be executed at the best bid and ask:
+//!
+//! - **Bounds**: a contract offered below its discounted intrinsic value or
+//!   bid above its upper bound (`S * exp(-qT)` for calls, `K * exp(-rT)` for
+//!   puts)
+//! - **Vertical**: call prices increasing in strike, put prices decreasing in
+//!   strike, or a vertical spread worth more than its discounted width
+//! - **Butterfly**: prices that are not convex in strike
+//! - **Calendar**: a near expiration bid above the far expiration ask at the
+//!   same strike, where carry makes this a static arbitrage
+//!
+//! Opportunities that share a contract compete for the same liquidity; each
+//! one reports the quantity executable on its own.
+
+use crate::orderbook::{GreeksInputs, OptionChainOrderBook, Quote};
+use optionstratlib::{ExpirationDate, OptionStyle};
+use orderbook_rs::Side;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Minimum edge, in price units, reported as an arbitrage.
+const MIN_EDGE: f64 = 1e-9;
+
+/// Type of static arbitrage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ArbitrageKind {
+    /// Offered below discounted intrinsic value.
+    BelowIntrinsic,
+    /// Bid above the underlying (calls) or the discounted strike (puts).
+    AboveUpperBound,
+    /// Vertical spread mispriced against monotonicity or its width.
+    Vertical,
+    /// Butterfly with a credit: prices not convex in strike.
+    Butterfly,
+    /// Near expiration bid above the far expiration ask.
+    Calendar,
+}
+
+/// One leg of an arbitrage, executed at the best price of its book.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArbitrageLeg {
+    /// Contract symbol.
+    pub symbol: String,
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// The strike price.
+    pub strike: u64,
+    /// Call or Put.
+    pub option_style: OptionStyle,
+    /// Buy at the ask or sell at the bid.
+    pub side: Side,
+    /// Execution price: the best ask for buys, the best bid for sells.
+    pub price: u128,
+    /// Quantity available at the execution price.
+    pub available: u64,
+    /// Contracts of this leg per unit of the structure.
+    pub ratio: u64,
+}
+
+/// An executable static arbitrage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StaticArbitrage {
+    /// Type of arbitrage.
+    pub kind: ArbitrageKind,
+    /// Legs of the structure.
+    pub legs: Vec<ArbitrageLeg>,
+    /// Present value locked in per unit of the structure, in price units.
+    pub edge: f64,
+    /// Units of the structure executable at the best prices.
+    pub quantity: u64,
+    /// Edge times executable quantity.
+    pub profit: f64,
+}
+
+impl StaticArbitrage {
+    /// Creates an arbitrage, sizing it from the quantity available on each
+    /// leg.
+    #[must_use]
+    pub fn new(kind: ArbitrageKind, legs: Vec<ArbitrageLeg>, edge: f64) -> Self {
+        let quantity = legs
+            .iter()
+            .map(|leg| leg.available / leg.ratio.max(1))
+            .min()
+            .unwrap_or_default();
+        Self {
+            kind,
+            legs,
+            edge,
+            quantity,
+            profit: edge * quantity as f64,
+        }
+    }
+}
+
+/// Static arbitrages found across the chains of an underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArbitrageReport {
+    /// The underlying asset symbol.
+    pub underlying: String,
+    /// Timestamp of the scan in milliseconds.
+    pub timestamp_ms: u64,
+    /// Opportunities sorted by profit, largest first.
+    pub opportunities: Vec<StaticArbitrage>,
+}
+
+impl ArbitrageReport {
+    /// Builds a report from opportunities in any order.
+    #[must_use]
+    pub fn new(underlying: impl Into<String>, mut opportunities: Vec<StaticArbitrage>) -> Self {
+        opportunities.sort_by(|a, b| b.profit.total_cmp(&a.profit));
+        Self {
+            underlying: underlying.into(),
+            timestamp_ms: orderbook_rs::current_time_millis(),
+            opportunities,
+        }
+    }
+
+    /// Returns the number of opportunities.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.opportunities.len()
+    }
+
+    /// Returns true if no arbitrage was found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.opportunities.is_empty()
+    }
+
+    /// Returns the opportunities of one kind.
+    pub fn of_kind(&self, kind: ArbitrageKind) -> impl Iterator<Item = &StaticArbitrage> {
+        self.opportunities.iter().filter(move |a| a.kind == kind)
+    }
+
+    /// Returns the sum of the profit of every opportunity.
+    #[must_use]
+    pub fn total_profit(&self) -> f64 {
+        self.opportunities.iter().map(|a| a.profit).sum()
+    }
+}
+
+/// Best quote of one contract.
+struct Contract {
+    symbol: String,
+    quote: Quote,
+}
+
+/// Call and put quotes of one strike.
+struct Listing {
+    strike: u64,
+    multiplier: Decimal,
+    call: Contract,
+    put: Contract,
+}
+
+impl Listing {
+    const fn contract(&self, option_style: OptionStyle) -> &Contract {
+        match option_style {
+            OptionStyle::Call => &self.call,
+            OptionStyle::Put => &self.put,
+        }
+    }
+}
+
+/// Quotes and carry of a chain, read once per scan.
+struct ChainQuotes {
+    expiration: ExpirationDate,
+    listings: Vec<Listing>,
+    /// Rates and time to expiry, adjusted for the pricing model.
+    carry: Option<GreeksInputs>,
+    underlying_price: Option<u64>,
+}
+
+impl ChainQuotes {
+    fn read(chain: &OptionChainOrderBook) -> Self {
+        let listings = chain
+            .strikes()
+            .iter()
+            .map(|entry| {
+                let book = entry.value();
+                let contract = |option_style| {
+                    let option = book.get(option_style);
+                    Contract {
+                        symbol: option.symbol().to_string(),
+                        quote: option.best_quote(),
+                    }
+                };
+                Listing {
+                    strike: book.strike(),
+                    multiplier: book.terms().multiplier,
+                    call: contract(OptionStyle::Call),
+                    put: contract(OptionStyle::Put),
+                }
+            })
+            .collect();
+        let carry = (|| {
+            let inputs = GreeksInputs {
+                risk_free_rate: chain.risk_free_rate().ok()?,
+                dividend_yield: chain.dividend_yield().ok()?,
+                years_to_expiry: chain.expiration().get_years().ok()?.to_f64(),
+                ..GreeksInputs::default()
+            };
+            Some(chain.pricing_model().adjust(inputs))
+        })();
+
+        Self {
+            expiration: *chain.expiration(),
+            listings,
+            carry,
+            underlying_price: chain.underlying_price(),
+        }
+    }
+
+    /// Returns the discount factor to expiry, one if unknown.
+    fn discount_factor(&self) -> f64 {
+        self.carry
+            .map_or(1.0, |c| (-c.risk_free_rate * c.years_to_expiry).exp())
+    }
+
+    /// Returns the leg trading a contract at its best price, if quoted.
+    fn leg(
+        &self,
+        listing: &Listing,
+        option_style: OptionStyle,
+        side: Side,
+        ratio: u64,
+    ) -> Option<ArbitrageLeg> {
+        let contract = listing.contract(option_style);
+        let (price, available) = match side {
+            Side::Buy => (contract.quote.ask_price()?, contract.quote.ask_size()),
+            Side::Sell => (contract.quote.bid_price()?, contract.quote.bid_size()),
+        };
+        Some(ArbitrageLeg {
+            symbol: contract.symbol.clone(),
+            expiration: self.expiration,
+            strike: listing.strike,
+            option_style,
+            side,
+            price,
+            available,
+            ratio,
+        })
+    }
+}
+
+/// Returns the signed cash flow of a leg per unit of the structure.
+fn cash_flow(leg: &ArbitrageLeg) -> f64 {
+    let amount = leg.price as f64 * leg.ratio as f64;
+    match leg.side {
+        Side::Buy => -amount,
+        Side::Sell => amount,
+    }
+}
+
+/// Records an arbitrage if the legs exist and their net credit exceeds
+/// `bound`, the present value of the worst payoff of the structure.
+fn record(
+    out: &mut Vec<StaticArbitrage>,
+    kind: ArbitrageKind,
+    legs: Option<Vec<ArbitrageLeg>>,
+    bound: f64,
+) {
+    let Some(legs) = legs else {
+        return;
+    };
+    let edge = legs.iter().map(cash_flow).sum::<f64>() - bound;
+    if edge > MIN_EDGE {
+        out.push(StaticArbitrage::new(kind, legs, edge));
+    }
+}
+
+/// Scans a chain for bounds, vertical and butterfly arbitrages.
+pub(crate) fn scan_chain(chain: &OptionChainOrderBook) -> Vec<StaticArbitrage> {
+    let quotes = ChainQuotes::read(chain);
+    let mut out = Vec::new();
+    scan_bounds(&quotes, &mut out);
+    scan_verticals(&quotes, &mut out);
+    scan_butterflies(&quotes, &mut out);
+    out
+}
+
+/// Scans two chains of the same underlying for calendar arbitrages.
+///
+/// Selling the near contract and buying the far one at the same strike is
+/// a static arbitrage for calls when there is no dividend yield and rates
+/// are non-negative, and for puts when rates are zero; other contracts are
+/// not checked.
+pub(crate) fn scan_calendar(
+    near: &OptionChainOrderBook,
+    far: &OptionChainOrderBook,
+) -> Vec<StaticArbitrage> {
+    let (near, far) = (ChainQuotes::read(near), ChainQuotes::read(far));
+    let (Some(near_carry), Some(far_carry)) = (near.carry, far.carry) else {
+        return Vec::new();
+    };
+    let carries = [near_carry, far_carry];
+    let calls = carries
+        .iter()
+        .all(|c| c.dividend_yield == 0.0 && c.risk_free_rate >= 0.0);
+    let puts = carries.iter().all(|c| c.risk_free_rate == 0.0);
+    let styles = [(OptionStyle::Call, calls), (OptionStyle::Put, puts)];
+
+    let mut out = Vec::new();
+    for near_listing in &near.listings {
+        let Some(far_listing) = far
+            .listings
+            .iter()
+            .find(|l| l.strike == near_listing.strike && l.multiplier == near_listing.multiplier)
+        else {
+            continue;
+        };
+        for option_style in styles.iter().filter(|s| s.1).map(|s| s.0) {
+            let legs = near
+                .leg(near_listing, option_style, Side::Sell, 1)
+                .zip(far.leg(far_listing, option_style, Side::Buy, 1))
+                .map(|(sell, buy)| vec![sell, buy]);
+            record(&mut out, ArbitrageKind::Calendar, legs, 0.0);
+        }
+    }
+    out
+}
+
+/// Checks every contract against its no-arbitrage price bounds.
+fn scan_bounds(quotes: &ChainQuotes, out: &mut Vec<StaticArbitrage>) {
+    let (Some(carry), Some(spot)) = (quotes.carry, quotes.underlying_price) else {
+        return;
+    };
+    let discount = quotes.discount_factor();
+    let spot = spot as f64 * (-carry.dividend_yield * carry.years_to_expiry).exp();
+
+    for listing in &quotes.listings {
+        let strike = listing.strike as f64 * discount;
+        for option_style in [OptionStyle::Call, OptionStyle::Put] {
+            let (intrinsic, upper) = match option_style {
+                OptionStyle::Call => ((spot - strike).max(0.0), spot),
+                OptionStyle::Put => ((strike - spot).max(0.0), strike),
+            };
+            let buy = quotes.leg(listing, option_style, Side::Buy, 1);
+            record(
+                out,
+                ArbitrageKind::BelowIntrinsic,
+                buy.map(|leg| vec![leg]),
+                -intrinsic,
+            );
+            let sell = quotes.leg(listing, option_style, Side::Sell, 1);
+            record(
+                out,
+                ArbitrageKind::AboveUpperBound,
+                sell.map(|leg| vec![leg]),
+                upper,
+            );
+        }
+    }
+}
+
+/// Checks every pair of strikes for monotonicity and slope violations.
+fn scan_verticals(quotes: &ChainQuotes, out: &mut Vec<StaticArbitrage>) {
+    let discount = quotes.discount_factor();
+    for (i, low) in quotes.listings.iter().enumerate() {
+        for high in quotes.listings[i + 1..]
+            .iter()
+            .filter(|l| l.multiplier == low.multiplier)
+        {
+            let width = (high.strike - low.strike) as f64 * discount;
+            for option_style in [OptionStyle::Call, OptionStyle::Put] {
+                // The contract that must be worth more, and the other one.
+                let (rich, cheap) = match option_style {
+                    OptionStyle::Call => (low, high),
+                    OptionStyle::Put => (high, low),
+                };
+                // Buying the richer contract below the cheaper one's bid.
+                let legs = quotes
+                    .leg(rich, option_style, Side::Buy, 1)
+                    .zip(quotes.leg(cheap, option_style, Side::Sell, 1))
+                    .map(|(buy, sell)| vec![buy, sell]);
+                record(out, ArbitrageKind::Vertical, legs, 0.0);
+                // Selling the spread for more than its discounted width.
+                let legs = quotes
+                    .leg(rich, option_style, Side::Sell, 1)
+                    .zip(quotes.leg(cheap, option_style, Side::Buy, 1))
+                    .map(|(sell, buy)| vec![sell, buy]);
+                record(out, ArbitrageKind::Vertical, legs, width);
+            }
+        }
+    }
+}
+
+/// Checks every three consecutive strikes for convexity.
+fn scan_butterflies(quotes: &ChainQuotes, out: &mut Vec<StaticArbitrage>) {
+    for window in quotes.listings.windows(3) {
+        let [low, body, high] = [&window[0], &window[1], &window[2]];
+        if low.multiplier != body.multiplier || body.multiplier != high.multiplier {
+            continue;
+        }
+        let (left, right) = (body.strike - low.strike, high.strike - body.strike);
+        let unit = gcd(left, right);
+        let (low_ratio, body_ratio, high_ratio) =
+            (right / unit, (left + right) / unit, left / unit);
+
+        for option_style in [OptionStyle::Call, OptionStyle::Put] {
+            let legs = (|| {
+                Some(vec![
+                    quotes.leg(low, option_style, Side::Buy, low_ratio)?,
+                    quotes.leg(body, option_style, Side::Sell, body_ratio)?,
+                    quotes.leg(high, option_style, Side::Buy, high_ratio)?,
+                ])
+            })();
+            record(out, ArbitrageKind::Butterfly, legs, 0.0);
+        }
+    }
+}
+
+/// Greatest common divisor.
+const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::ReferencePriceKind;
+    use optionstratlib::prelude::pos_or_panic;
+    use orderbook_rs::OrderId;
+
+    fn chain(days: f64) -> OptionChainOrderBook {
+        OptionChainOrderBook::new("BTC", ExpirationDate::Days(pos_or_panic!(days)))
+    }
+
+    fn quote(
+        chain: &OptionChainOrderBook,
+        strike: u64,
+        option_style: OptionStyle,
+        bid: u128,
+        ask: u128,
+        size: u64,
+    ) {
+        let book = chain.get_or_create_strike(strike).get_arc(option_style);
+        book.add_limit_order(OrderId::new(), Side::Buy, bid, size)
+            .unwrap();
+        book.add_limit_order(OrderId::new(), Side::Sell, ask, size)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(10, 5), 5);
+        assert_eq!(gcd(5, 10), 5);
+        assert_eq!(gcd(7, 3), 1);
+    }
+
+    #[test]
+    fn test_consistent_chain_has_no_arbitrage() {
+        let chain = chain(30.0);
+        chain.reference().update(ReferencePriceKind::Index, 100);
+        quote(&chain, 90, OptionStyle::Call, 10, 12, 5);
+        quote(&chain, 100, OptionStyle::Call, 3, 4, 5);
+        quote(&chain, 110, OptionStyle::Call, 1, 2, 5);
+        quote(&chain, 90, OptionStyle::Put, 1, 2, 5);
+        quote(&chain, 100, OptionStyle::Put, 3, 4, 5);
+        quote(&chain, 110, OptionStyle::Put, 10, 12, 5);
+        assert!(scan_chain(&chain).is_empty());
+    }
+
+    #[test]
+    fn test_bounds_violations() {
+        let chain = chain(30.0);
+        quote(&chain, 80, OptionStyle::Call, 15, 18, 3);
+        quote(&chain, 80, OptionStyle::Put, 90, 95, 2);
+        // Bounds need an underlying price.
+        assert!(scan_chain(&chain).is_empty());
+
+        chain.reference().update(ReferencePriceKind::Index, 100);
+        let found = scan_chain(&chain);
+        let below: Vec<_> = found
+            .iter()
+            .filter(|a| a.kind == ArbitrageKind::BelowIntrinsic)
+            .collect();
+        assert_eq!(below.len(), 1);
+        assert_eq!(below[0].legs[0].side, Side::Buy);
+        assert_eq!(below[0].legs[0].price, 18);
+        assert!((below[0].edge - 2.0).abs() < 1e-9);
+        assert_eq!(below[0].quantity, 3);
+
+        let above: Vec<_> = found
+            .iter()
+            .filter(|a| a.kind == ArbitrageKind::AboveUpperBound)
+            .collect();
+        assert_eq!(above.len(), 1);
+        assert_eq!(above[0].legs[0].option_style, OptionStyle::Put);
+        assert!((above[0].edge - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_vertical_and_butterfly_violations() {
+        let chain = chain(30.0);
+        quote(&chain, 90, OptionStyle::Call, 10, 11, 4);
+        quote(&chain, 100, OptionStyle::Call, 7, 8, 6);
+        // Call priced above the lower strike and a concave butterfly.
+        quote(&chain, 110, OptionStyle::Call, 12, 13, 2);
+
+        let found = scan_chain(&chain);
+        let vertical: Vec<_> = found
+            .iter()
+            .filter(|a| a.kind == ArbitrageKind::Vertical)
+            .collect();
+        // Buy 90 at 11 / sell 110 at 12, and buy 100 at 8 / sell 110 at 12.
+        assert_eq!(vertical.len(), 2);
+        assert!(vertical.iter().all(|a| a.quantity == 2));
+        assert!(vertical.iter().any(|a| (a.edge - 4.0).abs() < 1e-9));
+
+        let butterfly: Vec<_> = found
+            .iter()
+            .filter(|a| a.kind == ArbitrageKind::Butterfly)
+            .collect();
+        assert!(butterfly.is_empty());
+
+        let chain = self::chain(30.0);
+        quote(&chain, 90, OptionStyle::Put, 1, 2, 10);
+        quote(&chain, 100, OptionStyle::Put, 7, 8, 10);
+        quote(&chain, 110, OptionStyle::Put, 9, 10, 10);
+        let found = scan_chain(&chain);
+        let butterfly: Vec<_> = found
+            .iter()
+            .filter(|a| a.kind == ArbitrageKind::Butterfly)
+            .collect();
+        assert_eq!(butterfly.len(), 1);
+        // Buy 90 at 2, sell two 100 at 7, buy 110 at 10: credit of 2.
+        assert!((butterfly[0].edge - 2.0).abs() < 1e-9);
+        assert_eq!(butterfly[0].legs[1].ratio, 2);
+        assert_eq!(butterfly[0].quantity, 5);
+    }
+
+    #[test]
+    fn test_calendar_violations() {
+        let near = chain(30.0);
+        let far = chain(60.0);
+        quote(&near, 100, OptionStyle::Call, 6, 7, 3);
+        quote(&far, 100, OptionStyle::Call, 4, 5, 8);
+        quote(&near, 100, OptionStyle::Put, 6, 7, 3);
+        quote(&far, 100, OptionStyle::Put, 4, 5, 8);
+
+        let found = scan_calendar(&near, &far);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|a| (a.edge - 1.0).abs() < 1e-9));
+        assert!(found.iter().all(|a| a.quantity == 3));
+
+        // With a dividend yield only puts are checked; with rates neither.
+        near.market()
+            .set_dividend_curve(crate::pricing::YieldCurve::flat(0.02));
+        let found = scan_calendar(&near, &far);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].legs[0].option_style, OptionStyle::Put);
+        near.market()
+            .set_rate_curve(crate::pricing::YieldCurve::flat(0.05));
+        assert!(scan_calendar(&near, &far).is_empty());
+    }
+}
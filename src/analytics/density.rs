@@ -0,0 +1,366 @@
+//! Risk-neutral density module.
+//!
+//! This module reads the risk-neutral distribution of the underlying at
+//! expiry off call prices across strikes (Breeden-Litzenberger):
+//!
+//! ```text
+//! P(S_T <= K) = 1 + exp(rT) * dC/dK
+//! f(K)        = exp(rT) * d2C/dK2
+//! ```
+//!
+//! Call prices are the out-of-the-money mids, with puts converted to calls
+//! through put-call parity. Before differentiating, the price curve is
+//! projected onto the closest convex curve with slopes in `[-DF, 0]`, so the
+//! density is never negative and the distribution stays in `[0, 1]`.
+
+use crate::error::{Error, Result};
+use crate::orderbook::OptionChainOrderBook;
+use optionstratlib::ExpirationDate;
+use serde::{Deserialize, Serialize};
+
+/// Risk-neutral distribution at one strike.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DensityPoint {
+    /// The strike price.
+    pub strike: u64,
+    /// Smoothed call price, in price units.
+    pub call_price: f64,
+    /// Probability density per strike unit.
+    pub density: f64,
+    /// Probability of finishing at or below the strike.
+    pub cumulative: f64,
+}
+
+/// Risk-neutral distribution of the underlying at an expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskNeutralDensity {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Time to expiry in years.
+    pub years_to_expiry: f64,
+    /// Risk-free rate used for discounting.
+    pub risk_free_rate: f64,
+    /// Forward used to convert puts to calls.
+    pub forward: f64,
+    /// Distribution at the inner strikes, sorted by strike. The lowest and
+    /// highest quoted strikes only bound the finite differences.
+    pub points: Vec<DensityPoint>,
+}
+
+impl RiskNeutralDensity {
+    /// Computes the distribution of a chain from its best quotes.
+    ///
+    /// The forward is the chain's implied forward, falling back to the
+    /// carried underlying price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// `Error::PricingError` if the expiration is not in the future, or
+    /// `Error::NoDataAvailable` if fewer than three strikes have a mid.
+    pub fn from_chain(chain: &OptionChainOrderBook) -> Result<Self> {
+        let inputs = chain.greeks_inputs(0.0)?;
+        let expiration = *chain.expiration();
+        let years = inputs.years_to_expiry;
+        if years <= 0.0 {
+            return Err(Error::pricing(format!(
+                "expiration {expiration} is not in the future"
+            )));
+        }
+        let forward = match chain.implied_forward() {
+            Ok(implied) => implied.forward,
+            Err(_) => chain.forward_price()?,
+        };
+        let discount_factor = (-inputs.risk_free_rate * years).exp();
+
+        let (strikes, prices): (Vec<u64>, Vec<f64>) = chain
+            .strikes()
+            .iter()
+            .filter_map(|entry| {
+                let strike = *entry.key();
+                let call = entry.value().call_quote().mid_price();
+                let put = entry
+                    .value()
+                    .put_quote()
+                    .mid_price()
+                    .map(|p| p + discount_factor * (forward - strike as f64));
+                let price = if (strike as f64) < forward {
+                    put.or(call)
+                } else {
+                    call.or(put)
+                };
+                price.map(|p| (strike, p))
+            })
+            .unzip();
+        if strikes.len() < 3 {
+            return Err(Error::no_data(format!(
+                "fewer than three strikes with mids for {expiration}"
+            )));
+        }
+
+        let widths: Vec<f64> = strikes.windows(2).map(|w| (w[1] - w[0]) as f64).collect();
+        let slopes = convex_slopes(&prices, &widths, discount_factor);
+        let smoothed = integrate(&prices, &widths, &slopes);
+        let growth = 1.0 / discount_factor;
+        let points = (1..strikes.len() - 1)
+            .map(|i| DensityPoint {
+                strike: strikes[i],
+                call_price: smoothed[i],
+                density: growth * (slopes[i] - slopes[i - 1]) / ((widths[i - 1] + widths[i]) / 2.0),
+                cumulative: 1.0 + growth * (slopes[i - 1] + slopes[i]) / 2.0,
+            })
+            .collect();
+
+        Ok(Self {
+            expiration,
+            years_to_expiry: years,
+            risk_free_rate: inputs.risk_free_rate,
+            forward,
+            points,
+        })
+    }
+
+    /// Returns the probability of finishing at or below a level, or `None`
+    /// outside the inner strikes.
+    #[must_use]
+    pub fn probability_below(&self, level: f64) -> Option<f64> {
+        let first = self.points.first()?;
+        let last = self.points.last()?;
+        if level < first.strike as f64 || level > last.strike as f64 {
+            return None;
+        }
+        let upper = self.points.iter().position(|p| p.strike as f64 >= level)?;
+        if upper == 0 {
+            return Some(first.cumulative);
+        }
+        let (a, b) = (&self.points[upper - 1], &self.points[upper]);
+        let weight = (level - a.strike as f64) / (b.strike - a.strike) as f64;
+        Some(a.cumulative + weight * (b.cumulative - a.cumulative))
+    }
+
+    /// Returns the probability of finishing above a level, or `None`
+    /// outside the inner strikes.
+    #[must_use]
+    pub fn probability_above(&self, level: f64) -> Option<f64> {
+        self.probability_below(level).map(|p| 1.0 - p)
+    }
+
+    /// Returns the probability of finishing between two levels, or `None`
+    /// if either is outside the inner strikes.
+    #[must_use]
+    pub fn probability_between(&self, lower: f64, upper: f64) -> Option<f64> {
+        Some((self.probability_below(upper)? - self.probability_below(lower)?).max(0.0))
+    }
+}
+
+/// Expected move of the underlying implied by the ATM straddle.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExpectedMove {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Time to expiry in years.
+    pub years_to_expiry: f64,
+    /// Forward price, in strike units.
+    pub forward: f64,
+    /// Straddle strike: the strike with call and put mids closest to the
+    /// forward.
+    pub strike: u64,
+    /// Straddle mid: call mid plus put mid, the expected move in price
+    /// units.
+    pub straddle: f64,
+    /// Expected move as a fraction of the forward.
+    pub move_fraction: f64,
+    /// Forward minus the expected move.
+    pub lower: f64,
+    /// Forward plus the expected move.
+    pub upper: f64,
+}
+
+impl ExpectedMove {
+    /// Computes the expected move of a chain from its best quotes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or `Error::NoDataAvailable` if no strike has call and put mids.
+    pub fn from_chain(chain: &OptionChainOrderBook) -> Result<Self> {
+        let inputs = chain.greeks_inputs(0.0)?;
+        let forward = match chain.implied_forward() {
+            Ok(implied) => implied.forward,
+            Err(_) => chain.forward_price()?,
+        };
+        let (strike, straddle) = chain
+            .strikes()
+            .iter()
+            .filter_map(|entry| {
+                let call = entry.value().call_quote().mid_price()?;
+                let put = entry.value().put_quote().mid_price()?;
+                Some((*entry.key(), call + put))
+            })
+            .min_by(|a, b| {
+                (a.0 as f64 - forward)
+                    .abs()
+                    .total_cmp(&(b.0 as f64 - forward).abs())
+            })
+            .ok_or_else(|| {
+                Error::no_data(format!("no call and put mids for {}", chain.expiration()))
+            })?;
+
+        Ok(Self {
+            expiration: *chain.expiration(),
+            years_to_expiry: inputs.years_to_expiry,
+            forward,
+            strike,
+            straddle,
+            move_fraction: straddle / forward,
+            lower: forward - straddle,
+            upper: forward + straddle,
+        })
+    }
+}
+
+/// Returns the slopes of the closest convex price curve: the raw slopes
+/// made non-decreasing by pooling adjacent violators, weighted by strike
+/// width, and clamped to `[-discount_factor, 0]`.
+fn convex_slopes(prices: &[f64], widths: &[f64], discount_factor: f64) -> Vec<f64> {
+    // Blocks of (weighted slope sum, total width, slope count).
+    let mut blocks: Vec<(f64, f64, usize)> = Vec::with_capacity(widths.len());
+    for (i, width) in widths.iter().enumerate() {
+        blocks.push((prices[i + 1] - prices[i], *width, 1));
+        while blocks.len() > 1 {
+            let (sum, width, count) = blocks[blocks.len() - 1];
+            let (prev_sum, prev_width, prev_count) = blocks[blocks.len() - 2];
+            if prev_sum / prev_width <= sum / width {
+                break;
+            }
+            blocks.pop();
+            let last = blocks.len() - 1;
+            blocks[last] = (prev_sum + sum, prev_width + width, prev_count + count);
+        }
+    }
+    blocks
+        .iter()
+        .flat_map(|(sum, width, count)| {
+            std::iter::repeat_n((sum / width).clamp(-discount_factor, 0.0), *count)
+        })
+        .collect()
+}
+
+/// Rebuilds prices from slopes, shifted to the least-squares level of the
+/// raw prices.
+fn integrate(prices: &[f64], widths: &[f64], slopes: &[f64]) -> Vec<f64> {
+    let mut curve = Vec::with_capacity(prices.len());
+    curve.push(0.0);
+    for (width, slope) in widths.iter().zip(slopes) {
+        curve.push(curve[curve.len() - 1] + width * slope);
+    }
+    let offset = prices
+        .iter()
+        .zip(&curve)
+        .map(|(price, level)| price - level)
+        .sum::<f64>()
+        / prices.len() as f64;
+    curve
+        .iter()
+        .map(|level| (level + offset).max(0.0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::ReferencePriceKind;
+    use crate::pricing::theoretical_price;
+    use optionstratlib::OptionStyle;
+    use optionstratlib::prelude::pos_or_panic;
+    use orderbook_rs::{OrderId, Side};
+
+    /// Chain quoted one tick wide around Black-Scholes prices.
+    fn lognormal_chain(volatility: f64) -> OptionChainOrderBook {
+        let chain = OptionChainOrderBook::new("BTC", ExpirationDate::Days(pos_or_panic!(91.25)));
+        chain.reference().update(ReferencePriceKind::Index, 10_000);
+        let inputs = chain.greeks_inputs(volatility).unwrap();
+        for strike in (6_000..=14_000).step_by(100) {
+            for option_style in [OptionStyle::Call, OptionStyle::Put] {
+                let fair = theoretical_price(strike, option_style, &inputs).unwrap();
+                let bid = fair.floor() as u128;
+                let book = chain.get_or_create_strike(strike).get_arc(option_style);
+                if bid > 0 {
+                    book.add_limit_order(OrderId::new(), Side::Buy, bid, 1)
+                        .unwrap();
+                }
+                book.add_limit_order(OrderId::new(), Side::Sell, bid + 1, 1)
+                    .unwrap();
+            }
+        }
+        chain
+    }
+
+    #[test]
+    fn test_convex_projection() {
+        // The middle slope violates convexity and the last exceeds zero.
+        let prices = [20.0, 10.0, 8.0, 4.5, 5.0];
+        let widths = [10.0, 10.0, 10.0, 10.0];
+        let slopes = convex_slopes(&prices, &widths, 0.9);
+
+        assert_eq!(slopes.len(), 4);
+        assert!(slopes.windows(2).all(|w| w[0] <= w[1]));
+        assert!(slopes.iter().all(|s| (-0.9..=0.0).contains(s)));
+        assert_eq!(slopes[0], -0.9);
+        // -0.2 and -0.35 pool to -0.275.
+        assert!((slopes[1] + 0.275).abs() < 1e-12 && slopes[1] == slopes[2]);
+        assert_eq!(slopes[3], 0.0);
+
+        let curve = integrate(&prices, &widths, &slopes);
+        assert!(curve.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn test_density_of_lognormal_chain() {
+        let volatility = 0.2;
+        let chain = lognormal_chain(volatility);
+        let density = RiskNeutralDensity::from_chain(&chain).unwrap();
+
+        assert_eq!(density.points.len(), 79);
+        assert!((density.forward - 10_000.0).abs() < 1.0);
+        assert!(density.points.iter().all(|p| p.density >= 0.0));
+        assert!(
+            density
+                .points
+                .windows(2)
+                .all(|w| w[0].cumulative <= w[1].cumulative)
+        );
+
+        // P(S_T <= K) = 1 + dC/dK on exact prices (zero rate).
+        let inputs = chain.greeks_inputs(volatility).unwrap();
+        let call = |strike| theoretical_price(strike, OptionStyle::Call, &inputs).unwrap();
+        for level in [9_000, 10_000, 11_500] {
+            let expected = 1.0 + (call(level + 1) - call(level - 1)) / 2.0;
+            let below = density.probability_below(level as f64).unwrap();
+            assert!(
+                (below - expected).abs() < 0.02,
+                "{level}: {below} vs {expected}"
+            );
+        }
+        let above = density.probability_above(10_000.0).unwrap();
+        assert!((above + density.probability_below(10_000.0).unwrap() - 1.0).abs() < 1e-12);
+        let inside = density.probability_between(8_000.0, 12_000.0).unwrap();
+        assert!(inside > 0.9 && inside < 1.0);
+        assert!(density.probability_below(5_000.0).is_none());
+    }
+
+    #[test]
+    fn test_expected_move_from_straddle() {
+        let chain = lognormal_chain(0.2);
+        let expected = ExpectedMove::from_chain(&chain).unwrap();
+        assert_eq!(expected.strike, 10_000);
+        // ATM straddle ~ 0.8 * sigma * sqrt(T) * F.
+        assert!((expected.move_fraction - 0.8 * 0.2 * 0.5).abs() < 0.002);
+        assert!((expected.upper - expected.lower - 2.0 * expected.straddle).abs() < 1e-9);
+
+        let empty = OptionChainOrderBook::new("BTC", ExpirationDate::Days(pos_or_panic!(30.0)));
+        empty.reference().update(ReferencePriceKind::Index, 10_000);
+        assert!(ExpectedMove::from_chain(&empty).is_err());
+        assert!(RiskNeutralDensity::from_chain(&empty).is_err());
+    }
+}
@@ -0,0 +1,399 @@
+//! Gamma exposure module.
+//!
+//! This module computes the dealer gamma exposure (GEX) of a chain from the
+//! open interest and stored Greeks of its contracts, assuming dealers are
+//! long the calls and short the puts held by customers:
+//!
+//! ```text
+//! GEX = +/- gamma * OI * multiplier * S^2 / 100
+//! ```
+//!
+//! GEX is the change of dealer delta, in underlying value, for a 1% move.
+//! The zero-gamma level is where the net GEX changes sign as the underlying
+//! moves; max pain is the settlement strike with the lowest total payout to
+//! option holders.
+
+use crate::error::Result;
+use crate::orderbook::{GreeksInputs, OptionChainOrderBook};
+use crate::pricing::black_scholes_gamma;
+use optionstratlib::{ExpirationDate, OptionStyle};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// Largest relative move of the underlying scanned for the zero-gamma
+/// level.
+const ZERO_GAMMA_RANGE: f64 = 0.5;
+
+/// Number of underlying levels on each side of the current price scanned
+/// for the zero-gamma level.
+const ZERO_GAMMA_STEPS: u32 = 100;
+
+/// Gamma exposure and open interest of one strike.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct StrikeGammaExposure {
+    /// The strike price.
+    pub strike: u64,
+    /// Open interest of the call.
+    pub call_open_interest: u64,
+    /// Open interest of the put.
+    pub put_open_interest: u64,
+    /// Dealer GEX of the call, positive.
+    pub call_gex: f64,
+    /// Dealer GEX of the put, negative.
+    pub put_gex: f64,
+    /// Call plus put GEX.
+    pub net_gex: f64,
+}
+
+/// Open contracts of one option, used to scan the zero-gamma level.
+#[derive(Debug, Clone, Copy)]
+struct OpenContract {
+    strike: f64,
+    /// Signed open interest times multiplier: positive for calls.
+    weight: f64,
+    /// Underlying price of the chain.
+    underlying_price: f64,
+    inputs: GreeksInputs,
+}
+
+/// Gamma exposure, walls and max pain of one expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GammaExposureReport {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Underlying price the exposure is measured at.
+    pub underlying_price: u64,
+    /// Strikes with open interest, sorted by strike.
+    pub strikes: Vec<StrikeGammaExposure>,
+    /// Total call GEX.
+    pub call_gex: f64,
+    /// Total put GEX.
+    pub put_gex: f64,
+    /// Net GEX of the expiration.
+    pub total_gex: f64,
+    /// Underlying level where the net GEX changes sign, nearest to the
+    /// current price.
+    pub zero_gamma: Option<f64>,
+    /// Strike with the largest call GEX.
+    pub call_wall: Option<u64>,
+    /// Strike with the largest put GEX in absolute value.
+    pub put_wall: Option<u64>,
+    /// Strike minimizing the total payout to option holders at expiry.
+    pub max_pain: Option<u64>,
+    /// Number of contracts with open interest but no stored Greeks.
+    pub missing_count: usize,
+}
+
+impl GammaExposureReport {
+    /// Computes the gamma exposure of a chain.
+    ///
+    /// Each contract uses the gamma and underlying price stored with its
+    /// Greeks. The zero-gamma scan recomputes gamma from the stored inputs;
+    /// contracts whose Greeks were stored without inputs are left out of
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn from_chain(chain: &OptionChainOrderBook) -> Result<Self> {
+        Self::with_contracts(chain).map(|(report, _)| report)
+    }
+
+    /// Computes the report and the open contracts of the zero-gamma scan.
+    fn with_contracts(chain: &OptionChainOrderBook) -> Result<(Self, Vec<OpenContract>)> {
+        let underlying_price = chain.greeks_inputs(0.0)?.underlying_price;
+        let mut strikes = Vec::new();
+        let mut contracts = Vec::new();
+        let mut payouts = Vec::new();
+        let mut missing_count = 0;
+
+        for entry in chain.strikes().iter() {
+            let book = entry.value();
+            let multiplier = book.terms().multiplier.to_f64().unwrap_or(1.0);
+            let mut exposure = StrikeGammaExposure {
+                strike: *entry.key(),
+                ..StrikeGammaExposure::default()
+            };
+            for option_style in [OptionStyle::Call, OptionStyle::Put] {
+                let open_interest = book.get(option_style).activity().open_interest;
+                let (sign, slot) = match option_style {
+                    OptionStyle::Call => {
+                        exposure.call_open_interest = open_interest;
+                        (1.0, &mut exposure.call_gex)
+                    }
+                    OptionStyle::Put => {
+                        exposure.put_open_interest = open_interest;
+                        (-1.0, &mut exposure.put_gex)
+                    }
+                };
+                if open_interest == 0 {
+                    continue;
+                }
+                let weight = sign * open_interest as f64 * multiplier;
+                payouts.push((exposure.strike, option_style, weight.abs()));
+                let Some(snapshot) = book.greeks_snapshot(option_style) else {
+                    missing_count += 1;
+                    continue;
+                };
+                let price = snapshot
+                    .inputs
+                    .map_or(underlying_price, |i| i.underlying_price)
+                    as f64;
+                let gamma = snapshot.greeks.gamma.to_f64().unwrap_or_default();
+                *slot = weight * gamma * price * price / 100.0;
+                if let Some(inputs) = snapshot.inputs {
+                    contracts.push(OpenContract {
+                        strike: exposure.strike as f64,
+                        weight,
+                        underlying_price: underlying_price as f64,
+                        inputs,
+                    });
+                }
+            }
+            if exposure.call_open_interest > 0 || exposure.put_open_interest > 0 {
+                exposure.net_gex = exposure.call_gex + exposure.put_gex;
+                strikes.push(exposure);
+            }
+        }
+
+        let call_gex: f64 = strikes.iter().map(|s| s.call_gex).sum();
+        let put_gex: f64 = strikes.iter().map(|s| s.put_gex).sum();
+        let call_wall = strikes
+            .iter()
+            .filter(|s| s.call_gex > 0.0)
+            .max_by(|a, b| a.call_gex.total_cmp(&b.call_gex))
+            .map(|s| s.strike);
+        let put_wall = strikes
+            .iter()
+            .filter(|s| s.put_gex < 0.0)
+            .min_by(|a, b| a.put_gex.total_cmp(&b.put_gex))
+            .map(|s| s.strike);
+        let report = Self {
+            expiration: *chain.expiration(),
+            underlying_price,
+            strikes,
+            call_gex,
+            put_gex,
+            total_gex: call_gex + put_gex,
+            zero_gamma: zero_gamma_shift(&contracts).map(|f| f * underlying_price as f64),
+            call_wall,
+            put_wall,
+            max_pain: max_pain(&chain.strike_prices(), &payouts),
+            missing_count,
+        };
+        Ok((report, contracts))
+    }
+
+    /// Returns the exposure of a strike, if it has open interest.
+    #[must_use]
+    pub fn get(&self, strike: u64) -> Option<&StrikeGammaExposure> {
+        self.strikes.iter().find(|s| s.strike == strike)
+    }
+}
+
+/// Gamma exposure across the expirations of an underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GammaExposureSummary {
+    /// The underlying asset symbol.
+    pub underlying: String,
+    /// Timestamp of the report in milliseconds.
+    pub timestamp_ms: u64,
+    /// Reference price of the underlying, if available.
+    pub underlying_price: Option<u64>,
+    /// Net GEX across all expirations.
+    pub total_gex: f64,
+    /// Reference level where the combined net GEX changes sign, scanning
+    /// every expiration's underlying by the same relative move.
+    pub zero_gamma: Option<f64>,
+    /// Reports sorted by expiration.
+    pub expirations: Vec<GammaExposureReport>,
+    /// Expirations without an underlying price.
+    pub skipped: Vec<ExpirationDate>,
+}
+
+impl GammaExposureSummary {
+    /// Computes the gamma exposure of the chains of an underlying.
+    #[must_use]
+    pub fn from_chains(
+        underlying: impl Into<String>,
+        underlying_price: Option<u64>,
+        chains: &[&OptionChainOrderBook],
+    ) -> Self {
+        let mut expirations = Vec::new();
+        let mut contracts = Vec::new();
+        let mut skipped = Vec::new();
+        for chain in chains {
+            match GammaExposureReport::with_contracts(chain) {
+                Ok((report, open)) => {
+                    expirations.push(report);
+                    contracts.extend(open);
+                }
+                Err(_) => skipped.push(*chain.expiration()),
+            }
+        }
+        let shift = zero_gamma_shift(&contracts);
+        Self {
+            underlying: underlying.into(),
+            timestamp_ms: orderbook_rs::current_time_millis(),
+            underlying_price,
+            total_gex: expirations.iter().map(|r| r.total_gex).sum(),
+            zero_gamma: shift.zip(underlying_price).map(|(f, s)| f * s as f64),
+            expirations,
+            skipped,
+        }
+    }
+}
+
+/// Returns the net GEX of the contracts with every underlying moved by a
+/// factor.
+fn shifted_gex(contracts: &[OpenContract], factor: f64) -> f64 {
+    contracts
+        .iter()
+        .map(|c| {
+            let price = c.underlying_price * factor;
+            let gamma = black_scholes_gamma(c.strike, price, &c.inputs).unwrap_or_default();
+            c.weight * gamma * price * price / 100.0
+        })
+        .sum()
+}
+
+/// Returns the factor of the current underlying price where the net GEX
+/// changes sign, nearest to the current price, interpolated linearly
+/// between scanned levels.
+fn zero_gamma_shift(contracts: &[OpenContract]) -> Option<f64> {
+    if contracts.is_empty() {
+        return None;
+    }
+    let step = ZERO_GAMMA_RANGE / f64::from(ZERO_GAMMA_STEPS);
+    let level = |i: u32, up: bool| {
+        let offset = f64::from(i) * step;
+        let factor = if up { 1.0 + offset } else { 1.0 - offset };
+        (factor, shifted_gex(contracts, factor))
+    };
+    let crossing = |(f0, g0): (f64, f64), (f1, g1): (f64, f64)| {
+        (g0 * g1 < 0.0 || (g1 == 0.0 && g0 != 0.0)).then(|| f0 + (f1 - f0) * g0 / (g0 - g1))
+    };
+
+    let current = level(0, true);
+    if current.1 == 0.0 {
+        return Some(1.0);
+    }
+    let (mut below, mut above) = (current, current);
+    for i in 1..=ZERO_GAMMA_STEPS {
+        let (next_below, next_above) = (level(i, false), level(i, true));
+        let down = crossing(below, next_below);
+        let up = crossing(above, next_above);
+        match (down, up) {
+            (Some(d), Some(u)) => {
+                return Some(if 1.0 - d <= u - 1.0 { d } else { u });
+            }
+            (Some(f), None) | (None, Some(f)) => return Some(f),
+            (None, None) => {}
+        }
+        (below, above) = (next_below, next_above);
+    }
+    None
+}
+
+/// Returns the listed strike minimizing the intrinsic payout of the open
+/// contracts `(strike, style, open interest times multiplier)`.
+fn max_pain(strikes: &[u64], payouts: &[(u64, OptionStyle, f64)]) -> Option<u64> {
+    if payouts.is_empty() {
+        return None;
+    }
+    let payout = |settlement: u64| -> f64 {
+        payouts
+            .iter()
+            .map(|&(strike, option_style, weight)| {
+                let intrinsic = match option_style {
+                    OptionStyle::Call => settlement.saturating_sub(strike),
+                    OptionStyle::Put => strike.saturating_sub(settlement),
+                };
+                weight * intrinsic as f64
+            })
+            .sum()
+    };
+    strikes
+        .iter()
+        .map(|&s| (s, payout(s)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(s, _)| s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::ReferencePriceKind;
+    use crate::pricing::compute_greeks;
+    use optionstratlib::prelude::pos_or_panic;
+
+    #[test]
+    fn test_max_pain() {
+        let strikes = [90, 100, 110];
+        // Heavy call OI at 100 and put OI at 110 pin the settlement at 100.
+        let payouts = [
+            (100, OptionStyle::Call, 50.0),
+            (110, OptionStyle::Put, 10.0),
+            (90, OptionStyle::Put, 5.0),
+        ];
+        assert_eq!(max_pain(&strikes, &payouts), Some(100));
+        assert_eq!(max_pain(&strikes, &[]), None);
+    }
+
+    #[test]
+    fn test_zero_gamma_between_put_and_call_strikes() {
+        let inputs = GreeksInputs {
+            underlying_price: 100,
+            volatility: 0.2,
+            risk_free_rate: 0.0,
+            dividend_yield: 0.0,
+            years_to_expiry: 0.1,
+        };
+        let contract = |strike: f64, weight: f64| OpenContract {
+            strike,
+            weight,
+            underlying_price: 100.0,
+            inputs,
+        };
+        // Short put gamma below, long call gamma above: by symmetry the
+        // sign change is near the midpoint.
+        let contracts = [contract(90.0, -100.0), contract(110.0, 100.0)];
+        let shift = zero_gamma_shift(&contracts).unwrap();
+        assert!((shift - 1.0).abs() < 0.01, "{shift}");
+        assert!(shifted_gex(&contracts, 0.9) < 0.0);
+        assert!(shifted_gex(&contracts, 1.1) > 0.0);
+
+        assert!(zero_gamma_shift(&[contract(100.0, 100.0)]).is_none());
+        assert!(zero_gamma_shift(&[]).is_none());
+    }
+
+    #[test]
+    fn test_chain_gamma_exposure() {
+        let chain = OptionChainOrderBook::new("SPX", ExpirationDate::Days(pos_or_panic!(30.0)));
+        assert!(GammaExposureReport::from_chain(&chain).is_err());
+        chain.reference().update(ReferencePriceKind::Index, 100);
+
+        let inputs = chain.greeks_inputs(0.2).unwrap();
+        for (strike, call_oi, put_oi) in [(90, 0, 900), (100, 100, 100), (110, 600, 0)] {
+            let book = chain.get_or_create_strike(strike);
+            for (option_style, oi) in [(OptionStyle::Call, call_oi), (OptionStyle::Put, put_oi)] {
+                book.get(option_style).set_open_interest(oi);
+                let greeks = compute_greeks(strike, option_style, &inputs).unwrap();
+                book.update_greeks(option_style, greeks, inputs);
+            }
+        }
+        chain.get_or_create_strike(120).call().set_open_interest(5);
+
+        let report = GammaExposureReport::from_chain(&chain).unwrap();
+        assert_eq!(report.strikes.len(), 4);
+        assert_eq!(report.missing_count, 1);
+        let atm = report.get(100).unwrap();
+        assert!(atm.call_gex > 0.0 && (atm.call_gex + atm.put_gex).abs() < 1e-6 * atm.call_gex);
+        assert!((report.total_gex - (report.call_gex + report.put_gex)).abs() < 1e-9);
+        assert_eq!(report.call_wall, Some(110));
+        assert_eq!(report.put_wall, Some(90));
+        assert_eq!(report.max_pain, Some(100));
+        let zero = report.zero_gamma.unwrap();
+        assert!(zero > 90.0 && zero < 110.0, "{zero}");
+    }
+}
@@ -0,0 +1,27 @@
+//! Analytics module.
+//!
+//! This module derives market views from the live quotes of the order book
+//! hierarchy.
+//!
+//! ## Components
+//!
+//! - [`VolatilityMetrics`]: ATM volatility, risk reversal and butterfly of an expiration
+//! - [`TermStructure`]: Volatility metrics across the expirations of an underlying
+//! - [`ArbitrageReport`]: Executable static arbitrages in resting quotes
+//! - [`RiskNeutralDensity`]: Breeden-Litzenberger distribution of the underlying at expiry
+//! - [`ExpectedMove`]: Move implied by the ATM straddle
+//! - [`GammaExposureReport`]: Dealer gamma exposure, zero-gamma level, walls and max pain
+//! - [`VolatilityIndex`]: Model-free constant-maturity implied volatility index (VIX methodology)
+
+mod arbitrage;
+mod density;
+mod gamma;
+mod vix;
+mod vol_metrics;
+
+pub use arbitrage::{ArbitrageKind, ArbitrageLeg, ArbitrageReport, StaticArbitrage};
+pub(crate) use arbitrage::{scan_calendar, scan_chain};
+pub use density::{DensityPoint, ExpectedMove, RiskNeutralDensity};
+pub use gamma::{GammaExposureReport, GammaExposureSummary, StrikeGammaExposure};
+pub use vix::{ExpiryVariance, StrikeContribution, VolatilityIndex};
+pub use vol_metrics::{STANDARD_WING_DELTA, TermStructure, VolatilityMetrics};
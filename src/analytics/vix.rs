@@ -0,0 +1,361 @@
+//! Volatility index module.
+//!
+//! This module computes a model-free implied variance index with the CBOE
+//! VIX methodology. For each expiration:
+//!
+//! ```text
+//! sigma^2 = 2/T * sum(dK / K^2 * exp(RT) * Q(K)) - 1/T * (F / K0 - 1)^2
+//! ```
+//!
+//! where `F` is the forward from put-call parity at the strike with the
+//! smallest call/put mid difference, `K0` the first strike at or below `F`,
+//! and `Q(K)` the out-of-the-money mid at each strike (the call/put average
+//! at `K0`). Strikes are selected away from `K0` until two consecutive zero
+//! bids. The variances of two expirations are interpolated in total variance
+//! to a constant maturity, and the index is `100 * sqrt(sigma^2)`.
+
+use crate::error::{Error, Result};
+use crate::orderbook::{OptionChainOrderBook, Quote};
+use optionstratlib::{ExpirationDate, OptionStyle};
+use serde::{Deserialize, Serialize};
+
+/// Contribution of one strike to the variance of an expiration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StrikeContribution {
+    /// The strike price.
+    pub strike: u64,
+    /// Out-of-the-money option used, `None` for the call/put average at `K0`.
+    pub option_style: Option<OptionStyle>,
+    /// Mid price `Q(K)`.
+    pub price: f64,
+    /// `dK / K^2 * exp(RT) * Q(K)`.
+    pub contribution: f64,
+}
+
+/// Model-free implied variance of one expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpiryVariance {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Time to expiry in years.
+    pub years_to_expiry: f64,
+    /// Risk-free rate used for discounting.
+    pub risk_free_rate: f64,
+    /// Forward price from put-call parity.
+    pub forward: f64,
+    /// First strike at or below the forward.
+    pub atm_strike: u64,
+    /// Annualized implied variance.
+    pub variance: f64,
+    /// Selected strikes, sorted by strike.
+    pub contributions: Vec<StrikeContribution>,
+}
+
+impl ExpiryVariance {
+    /// Computes the variance of a chain from its best quotes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if no strike has call and put mids,
+    /// fewer than two strikes are selected, or the variance is not
+    /// positive; or `Error::PricingError` if the expiration is not in the
+    /// future.
+    pub fn from_chain(chain: &OptionChainOrderBook) -> Result<Self> {
+        let expiration = *chain.expiration();
+        let years = expiration.get_years()?.to_f64();
+        if years <= 0.0 {
+            return Err(Error::pricing(format!(
+                "expiration {expiration} is not in the future"
+            )));
+        }
+        let rate = chain.risk_free_rate()?;
+        let growth = (rate * years).exp();
+
+        let quotes: Vec<(u64, Quote, Quote)> = chain
+            .strikes()
+            .iter()
+            .map(|e| (*e.key(), e.value().call_quote(), e.value().put_quote()))
+            .collect();
+
+        let (parity_strike, difference) = quotes
+            .iter()
+            .filter_map(|(k, call, put)| Some((*k, call.mid_price()? - put.mid_price()?)))
+            .min_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .ok_or_else(|| Error::no_data(format!("no call and put mids for {expiration}")))?;
+        let forward = parity_strike as f64 + growth * difference;
+        let atm_index = quotes
+            .iter()
+            .rposition(|(k, _, _)| *k as f64 <= forward)
+            .unwrap_or(0);
+        let atm_strike = quotes[atm_index].0;
+
+        let mut selected: Vec<(u64, Option<OptionStyle>, f64)> = Vec::new();
+        selected.extend(otm_prices(
+            quotes[..atm_index]
+                .iter()
+                .rev()
+                .map(|(k, _, put)| (*k, put)),
+            OptionStyle::Put,
+        ));
+        let (_, call, put) = &quotes[atm_index];
+        let atm_mids: Vec<f64> = [call.mid_price(), put.mid_price()]
+            .into_iter()
+            .flatten()
+            .collect();
+        if !atm_mids.is_empty() {
+            let price = atm_mids.iter().sum::<f64>() / atm_mids.len() as f64;
+            selected.push((atm_strike, None, price));
+        }
+        selected.extend(otm_prices(
+            quotes[atm_index + 1..]
+                .iter()
+                .map(|(k, call, _)| (*k, call)),
+            OptionStyle::Call,
+        ));
+        selected.sort_by_key(|s| s.0);
+        if selected.len() < 2 {
+            return Err(Error::no_data(format!(
+                "fewer than two strikes with bids for {expiration}"
+            )));
+        }
+
+        let contributions: Vec<StrikeContribution> = selected
+            .iter()
+            .enumerate()
+            .map(|(i, &(strike, option_style, price))| {
+                let below = selected[i.saturating_sub(1)].0;
+                let above = selected[(i + 1).min(selected.len() - 1)].0;
+                let steps = if i == 0 || i == selected.len() - 1 {
+                    1.0
+                } else {
+                    2.0
+                };
+                let delta_k = (above - below) as f64 / steps;
+                StrikeContribution {
+                    strike,
+                    option_style,
+                    price,
+                    contribution: delta_k / (strike as f64).powi(2) * growth * price,
+                }
+            })
+            .collect();
+
+        let sum: f64 = contributions.iter().map(|c| c.contribution).sum();
+        let variance = 2.0 / years * sum - (forward / atm_strike as f64 - 1.0).powi(2) / years;
+        if variance <= 0.0 {
+            return Err(Error::no_data(format!(
+                "non-positive implied variance for {expiration}"
+            )));
+        }
+        Ok(Self {
+            expiration,
+            years_to_expiry: years,
+            risk_free_rate: rate,
+            forward,
+            atm_strike,
+            variance,
+            contributions,
+        })
+    }
+}
+
+/// Walks out-of-the-money quotes away from the money, keeping positive bids
+/// with a mid and stopping after two consecutive zero bids.
+fn otm_prices<'a>(
+    quotes: impl Iterator<Item = (u64, &'a Quote)>,
+    option_style: OptionStyle,
+) -> Vec<(u64, Option<OptionStyle>, f64)> {
+    let mut prices = Vec::new();
+    let mut zero_bids = 0;
+    for (strike, quote) in quotes {
+        if quote.bid_price().unwrap_or(0) == 0 {
+            zero_bids += 1;
+            if zero_bids == 2 {
+                break;
+            }
+            continue;
+        }
+        zero_bids = 0;
+        if let Some(mid) = quote.mid_price() {
+            prices.push((strike, Some(option_style), mid));
+        }
+    }
+    prices
+}
+
+/// Constant-maturity volatility index of an underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolatilityIndex {
+    /// The underlying asset symbol.
+    pub underlying: String,
+    /// Timestamp of the computation in milliseconds.
+    pub timestamp_ms: u64,
+    /// Constant maturity in years.
+    pub target_years: f64,
+    /// Annualized variance at the constant maturity.
+    pub variance: f64,
+    /// Index value: `100 * sqrt(variance)`.
+    pub value: f64,
+    /// Near-term expiration.
+    pub near: ExpiryVariance,
+    /// Next-term expiration, `None` if a single expiration was available.
+    pub next: Option<ExpiryVariance>,
+}
+
+impl VolatilityIndex {
+    /// Interpolates expiration variances to a constant maturity.
+    ///
+    /// The two expirations bracketing the target are used; outside the
+    /// listed expirations the two nearest are extrapolated. A single
+    /// expiration is used as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if `variances` is empty or the
+    /// interpolated variance is not positive, or `Error::ValidationError`
+    /// if the target is not positive.
+    pub fn from_variances(
+        underlying: impl Into<String>,
+        mut variances: Vec<ExpiryVariance>,
+        target_years: f64,
+    ) -> Result<Self> {
+        if target_years <= 0.0 {
+            return Err(Error::validation("constant maturity must be positive"));
+        }
+        let underlying = underlying.into();
+        if variances.is_empty() {
+            return Err(Error::no_data(format!(
+                "no expiration variance for {underlying}"
+            )));
+        }
+        variances.sort_by(|a, b| a.years_to_expiry.total_cmp(&b.years_to_expiry));
+
+        let (near, next) = if variances.len() == 1 {
+            (variances.swap_remove(0), None)
+        } else {
+            let next_index = variances
+                .iter()
+                .position(|v| v.years_to_expiry >= target_years)
+                .unwrap_or(variances.len() - 1)
+                .max(1);
+            let next = variances.swap_remove(next_index);
+            (variances.swap_remove(next_index - 1), Some(next))
+        };
+        let variance = match &next {
+            None => near.variance,
+            Some(next) => {
+                let (t1, t2) = (near.years_to_expiry, next.years_to_expiry);
+                let weight = (t2 - target_years) / (t2 - t1);
+                (t1 * near.variance * weight + t2 * next.variance * (1.0 - weight)) / target_years
+            }
+        };
+        if variance <= 0.0 {
+            return Err(Error::no_data(format!(
+                "non-positive constant-maturity variance for {underlying}"
+            )));
+        }
+
+        Ok(Self {
+            underlying,
+            timestamp_ms: orderbook_rs::current_time_millis(),
+            target_years,
+            variance,
+            value: 100.0 * variance.sqrt(),
+            near,
+            next,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use optionstratlib::prelude::pos_or_panic;
+    use orderbook_rs::{OrderId, Side};
+
+    fn variance(days: f64, variance: f64) -> ExpiryVariance {
+        ExpiryVariance {
+            expiration: ExpirationDate::Days(pos_or_panic!(days)),
+            years_to_expiry: days / 365.0,
+            risk_free_rate: 0.0,
+            forward: 100.0,
+            atm_strike: 100,
+            variance,
+            contributions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_constant_maturity_interpolation() {
+        let index = VolatilityIndex::from_variances(
+            "BTC",
+            vec![variance(45.0, 0.09), variance(15.0, 0.04)],
+            30.0 / 365.0,
+        )
+        .unwrap();
+        assert!((index.variance - 0.0775).abs() < 1e-12);
+        assert!((index.value - 100.0 * 0.0775f64.sqrt()).abs() < 1e-9);
+        assert_eq!(index.near.years_to_expiry, 15.0 / 365.0);
+        assert_eq!(index.next.unwrap().years_to_expiry, 45.0 / 365.0);
+
+        let single =
+            VolatilityIndex::from_variances("BTC", vec![variance(7.0, 0.25)], 30.0 / 365.0)
+                .unwrap();
+        assert!((single.value - 50.0).abs() < 1e-9);
+        assert!(single.next.is_none());
+
+        // The target is bracketed by the middle pair.
+        let three = VolatilityIndex::from_variances(
+            "BTC",
+            vec![
+                variance(7.0, 0.25),
+                variance(60.0, 0.04),
+                variance(21.0, 0.04),
+            ],
+            30.0 / 365.0,
+        )
+        .unwrap();
+        assert_eq!(three.near.years_to_expiry, 21.0 / 365.0);
+        assert!((three.variance - 0.04).abs() < 1e-12);
+
+        assert!(VolatilityIndex::from_variances("BTC", Vec::new(), 0.1).is_err());
+        assert!(VolatilityIndex::from_variances("BTC", vec![variance(7.0, 0.25)], 0.0).is_err());
+    }
+
+    #[test]
+    fn test_strike_selection_stops_at_zero_bids() {
+        let chain = OptionChainOrderBook::new("BTC", ExpirationDate::Days(pos_or_panic!(30.0)));
+        let quote = |strike: u64, option_style: OptionStyle, bid: u128, ask: u128| {
+            let book = chain.get_or_create_strike(strike).get_arc(option_style);
+            if bid > 0 {
+                book.add_limit_order(OrderId::new(), Side::Buy, bid, 1)
+                    .unwrap();
+            }
+            book.add_limit_order(OrderId::new(), Side::Sell, ask, 1)
+                .unwrap();
+        };
+        assert!(ExpiryVariance::from_chain(&chain).is_err());
+
+        quote(100, OptionStyle::Call, 40, 42);
+        quote(100, OptionStyle::Put, 40, 42);
+        quote(90, OptionStyle::Put, 20, 22);
+        quote(80, OptionStyle::Put, 0, 2);
+        quote(70, OptionStyle::Put, 5, 7);
+        quote(60, OptionStyle::Put, 0, 1);
+        quote(50, OptionStyle::Put, 0, 1);
+        quote(40, OptionStyle::Put, 3, 4);
+        quote(110, OptionStyle::Call, 20, 22);
+
+        let result = ExpiryVariance::from_chain(&chain).unwrap();
+        assert_eq!(result.atm_strike, 100);
+        assert!((result.forward - 100.0).abs() < 1e-9);
+        let strikes: Vec<u64> = result.contributions.iter().map(|c| c.strike).collect();
+        // 80 is skipped, 70 is kept, and the walk stops at 60/50.
+        assert_eq!(strikes, vec![70, 90, 100, 110]);
+        assert_eq!(result.contributions[2].option_style, None);
+        assert!((result.contributions[2].price - 41.0).abs() < 1e-9);
+        // Wing strikes use the distance to their only neighbor.
+        let first = result.contributions[0];
+        assert!((first.contribution - 20.0 / 4900.0 * 6.0).abs() < 1e-12);
+    }
+}
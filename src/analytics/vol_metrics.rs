@@ -0,0 +1,230 @@
+//! Volatility metrics module.
+//!
+//! This module computes the standard quoting metrics of a volatility smile:
+//!
+//! ```text
+//! risk reversal = vol(call delta) - vol(put delta)
+//! butterfly     = (vol(call delta) + vol(put delta)) / 2 - vol(ATM)
+//! ```
+//!
+//! ATM is the at-the-forward volatility and wing strikes are found by
+//! forward Black delta on the smile.
+
+use crate::error::{Error, Result};
+use crate::pricing::{VolatilitySmile, solve_delta};
+use optionstratlib::{ExpirationDate, OptionStyle};
+use serde::{Deserialize, Serialize};
+
+/// Wing delta of the standard risk reversal and butterfly (25-delta).
+pub const STANDARD_WING_DELTA: f64 = 0.25;
+
+/// ATM volatility, risk reversal and butterfly of one expiration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolatilityMetrics {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Time to expiry in years.
+    pub years_to_expiry: f64,
+    /// Forward price of the smile, in strike units.
+    pub forward: f64,
+    /// Absolute delta of the wings, e.g. 0.25.
+    pub wing_delta: f64,
+    /// At-the-forward volatility.
+    pub atm_volatility: f64,
+    /// Strike of the call wing.
+    pub call_strike: f64,
+    /// Volatility of the call wing.
+    pub call_volatility: f64,
+    /// Strike of the put wing.
+    pub put_strike: f64,
+    /// Volatility of the put wing.
+    pub put_volatility: f64,
+    /// Call wing volatility minus put wing volatility.
+    pub risk_reversal: f64,
+    /// Average wing volatility minus ATM volatility.
+    pub butterfly: f64,
+}
+
+impl VolatilityMetrics {
+    /// Computes the metrics of a smile for a wing delta.
+    ///
+    /// # Arguments
+    ///
+    /// * `smile` - Volatility smile of the expiration
+    /// * `wing_delta` - Absolute delta of the wings, in `(0, 0.5)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if the smile has no mid volatility,
+    /// or `Error::PricingError` if the wing delta is out of range or cannot
+    /// be reached.
+    pub fn from_smile(smile: &VolatilitySmile, wing_delta: f64) -> Result<Self> {
+        if !(wing_delta > 0.0 && wing_delta < 0.5) {
+            return Err(Error::pricing(format!(
+                "wing delta {wing_delta} must be in (0, 0.5)"
+            )));
+        }
+        let years = smile.years_to_expiry;
+        if years <= 0.0 {
+            return Err(Error::pricing(
+                "volatility metrics require a positive time to expiry",
+            ));
+        }
+        let atm_volatility = smile
+            .atm_volatility()
+            .ok_or_else(|| Error::no_data(format!("no mid volatility for {}", smile.expiration)))?;
+        let variance = |k: f64| {
+            let vol = smile
+                .volatility_at_log_moneyness(k)
+                .unwrap_or(atm_volatility);
+            vol * vol * years
+        };
+        let wing = |delta: f64, option_style: OptionStyle| -> Result<(f64, f64)> {
+            let k = solve_delta(&variance, delta, option_style)?;
+            Ok((smile.forward * k.exp(), (variance(k) / years).sqrt()))
+        };
+        let (call_strike, call_volatility) = wing(wing_delta, OptionStyle::Call)?;
+        let (put_strike, put_volatility) = wing(-wing_delta, OptionStyle::Put)?;
+
+        Ok(Self {
+            expiration: smile.expiration,
+            years_to_expiry: years,
+            forward: smile.forward,
+            wing_delta,
+            atm_volatility,
+            call_strike,
+            call_volatility,
+            put_strike,
+            put_volatility,
+            risk_reversal: call_volatility - put_volatility,
+            butterfly: (call_volatility + put_volatility) / 2.0 - atm_volatility,
+        })
+    }
+}
+
+/// Volatility metrics across the expirations of an underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TermStructure {
+    /// The underlying asset symbol.
+    pub underlying: String,
+    /// Timestamp of the report in milliseconds.
+    pub timestamp_ms: u64,
+    /// Metrics sorted by time to expiry.
+    pub expirations: Vec<VolatilityMetrics>,
+    /// Expirations whose metrics could not be computed.
+    pub skipped: Vec<ExpirationDate>,
+}
+
+impl TermStructure {
+    /// Builds a term structure from per-expiration metrics in any order.
+    #[must_use]
+    pub fn new(
+        underlying: impl Into<String>,
+        mut expirations: Vec<VolatilityMetrics>,
+        skipped: Vec<ExpirationDate>,
+    ) -> Self {
+        expirations.sort_by(|a, b| a.years_to_expiry.total_cmp(&b.years_to_expiry));
+        Self {
+            underlying: underlying.into(),
+            timestamp_ms: orderbook_rs::current_time_millis(),
+            expirations,
+            skipped,
+        }
+    }
+
+    /// Returns the number of expirations with metrics.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.expirations.len()
+    }
+
+    /// Returns true if no expiration has metrics.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.expirations.is_empty()
+    }
+
+    /// Returns the metrics of an expiration.
+    #[must_use]
+    pub fn get(&self, expiration: &ExpirationDate) -> Option<&VolatilityMetrics> {
+        self.expirations
+            .iter()
+            .find(|m| m.expiration == *expiration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{ImpliedVolatilityQuote, SmilePoint};
+    use optionstratlib::prelude::pos_or_panic;
+
+    /// Smile with a linear skew `vol = 0.2 - 0.1 * k` on OTM mids.
+    fn skewed_smile(days: f64) -> VolatilitySmile {
+        let points = (50u32..=150)
+            .step_by(5)
+            .map(|strike| {
+                let k = (f64::from(strike) / 100.0).ln();
+                let quote = ImpliedVolatilityQuote {
+                    mid: Some(0.2 - 0.1 * k),
+                    ..ImpliedVolatilityQuote::default()
+                };
+                SmilePoint {
+                    strike: u64::from(strike),
+                    log_moneyness: k,
+                    call: quote,
+                    put: quote,
+                }
+            })
+            .collect();
+        VolatilitySmile::new(
+            ExpirationDate::Days(pos_or_panic!(days)),
+            100.0,
+            days / 365.0,
+            points,
+        )
+    }
+
+    #[test]
+    fn test_metrics_of_skewed_smile() {
+        let metrics = VolatilityMetrics::from_smile(&skewed_smile(91.25), 0.25).unwrap();
+
+        assert!((metrics.atm_volatility - 0.2).abs() < 1e-9);
+        assert!(metrics.put_strike < 100.0 && metrics.call_strike > 100.0);
+        // Downside skew: puts trade over calls.
+        assert!(metrics.risk_reversal < 0.0);
+        let expected = 0.1 * ((metrics.put_strike / metrics.call_strike).ln());
+        assert!((metrics.risk_reversal - expected).abs() < 1e-3);
+        // A linear skew in log-moneyness has almost no curvature.
+        assert!(metrics.butterfly.abs() < 2e-3);
+    }
+
+    #[test]
+    fn test_metrics_validation() {
+        let smile = skewed_smile(30.0);
+        assert!(VolatilityMetrics::from_smile(&smile, 0.0).is_err());
+        assert!(VolatilityMetrics::from_smile(&smile, 0.5).is_err());
+
+        let empty = VolatilitySmile::new(smile.expiration, 100.0, 0.1, Vec::new());
+        assert!(VolatilityMetrics::from_smile(&empty, 0.25).is_err());
+    }
+
+    #[test]
+    fn test_term_structure_order_and_serde() {
+        let short = VolatilityMetrics::from_smile(&skewed_smile(30.0), 0.25).unwrap();
+        let long = VolatilityMetrics::from_smile(&skewed_smile(180.0), 0.25).unwrap();
+        let missing = ExpirationDate::Days(pos_or_panic!(365.0));
+        let term = TermStructure::new("SPX", vec![long, short], vec![missing]);
+
+        assert_eq!(term.len(), 2);
+        assert_eq!(term.expirations[0], short);
+        assert!(term.get(&long.expiration).is_some());
+        assert!(term.get(&missing).is_none());
+
+        let json = serde_json::to_string(&term).unwrap();
+        let parsed: TermStructure = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.skipped, term.skipped);
+        assert_eq!(parsed.len(), 2);
+        assert!((parsed.expirations[1].butterfly - long.butterfly).abs() < 1e-12);
+    }
+}
@@ -82,10 +82,14 @@ pub enum Error {
     },
 
     /// Error when risk limits are breached.
-    #[error("risk limit breached: {limit_type}")]
+    #[error("risk limit breached: {limit_type} limit of {limit} exceeded with value {current}")]
     RiskLimitBreached {
         /// Type of risk limit that was breached.
         limit_type: String,
+        /// The configured limit value.
+        limit: Decimal,
+        /// The value that exceeded the limit.
+        current: Decimal,
     },
 
     /// Error when hedging operation fails.
@@ -227,9 +231,15 @@ impl Error {
 
     /// Creates a new risk limit breached error.
     #[must_use]
-    pub fn risk_limit_breached(limit_type: impl Into<String>) -> Self {
+    pub fn risk_limit_breached(
+        limit_type: impl Into<String>,
+        limit: Decimal,
+        current: Decimal,
+    ) -> Self {
         Self::RiskLimitBreached {
             limit_type: limit_type.into(),
+            limit,
+            current,
         }
     }
 
@@ -356,9 +366,11 @@ mod tests {
 
     #[test]
     fn test_risk_limit_breached_error() {
-        let err = Error::risk_limit_breached("max_delta");
+        let err = Error::risk_limit_breached("max_delta", dec!(1000), dec!(1200));
         let msg = err.to_string();
         assert!(msg.contains("max_delta"));
+        assert!(msg.contains("1000"));
+        assert!(msg.contains("1200"));
     }
 
     #[test]
@@ -57,6 +57,10 @@
 //! | Module | Description |
 //! |--------|-------------|
 //! | [`orderbook`] | Hierarchical order book structure with all managers |
+//! | [`pricing`] | Pricing inputs and models (rate curves, volatility, Greeks) |
+//! | [`analytics`] | Market views from live quotes (volatility metrics, term structure) |
+//! | [`quoting`] | Two-sided quote generation and maintenance from theoretical values |
+//! | [`risk`] | Fill-driven positions and inventory limits |
 //! | [`error`] | Error types and `Result` type alias |
 //! | [`utils`] | Utility functions (e.g., date formatting) |
 //!
@@ -209,8 +213,12 @@
 //! - **thiserror** (2.0): Error handling
 //! - **serde** (1.0): Serialization support
 
+pub mod analytics;
 pub mod error;
 pub mod orderbook;
+pub mod pricing;
+pub mod quoting;
+pub mod risk;
 pub mod utils;
 
 pub use error::{Error, Result};
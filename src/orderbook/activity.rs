@@ -0,0 +1,312 @@
+//! Trading activity module.
+//!
+//! This module tracks the session volume, trade count, VWAP and open
+//! interest of each contract ([`ContractActivity`]) and aggregates them up
+//! the hierarchy ([`ActivitySummary`]).
+//!
+//! Volume, trades and VWAP accumulate from the matches of the contract's
+//! order book until the session is rolled. Open interest carries across
+//! sessions: it changes with the [`PositionEffect`]s of each trade, or is
+//! loaded from an exchange file with [`OpenInterestRecord`]s.
+
+use optionstratlib::OptionStyle;
+use serde::{Deserialize, Serialize};
+use std::sync::{PoisonError, RwLock};
+
+/// Whether a side of a fill opens or closes a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PositionEffect {
+    /// The fill opens or increases a position.
+    Open,
+    /// The fill closes or reduces a position.
+    Close,
+}
+
+impl PositionEffect {
+    /// Returns the change of open interest caused by a trade: up if both
+    /// sides open, down if both close, unchanged if a position is
+    /// transferred.
+    #[must_use]
+    pub fn open_interest_change(quantity: u64, buyer: Self, seller: Self) -> i64 {
+        let quantity = i64::try_from(quantity).unwrap_or(i64::MAX);
+        match (buyer, seller) {
+            (Self::Open, Self::Open) => quantity,
+            (Self::Close, Self::Close) => -quantity,
+            _ => 0,
+        }
+    }
+}
+
+/// Session trading activity and open interest of one contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractActivity {
+    /// Quantity traded in the session.
+    pub volume: u64,
+    /// Number of trades in the session.
+    pub trade_count: u64,
+    /// Sum of price times quantity of the session trades.
+    pub notional: u128,
+    /// Open contracts, carried across sessions.
+    pub open_interest: u64,
+    /// Price of the last trade, carried across sessions.
+    pub last_price: Option<u128>,
+    /// Timestamp of the last trade in milliseconds.
+    pub last_trade_ms: Option<u64>,
+    /// Timestamp of the session start in milliseconds.
+    pub session_start_ms: u64,
+}
+
+impl ContractActivity {
+    /// Returns the volume-weighted average price of the session trades.
+    #[must_use]
+    pub fn vwap(&self) -> Option<f64> {
+        (self.volume > 0).then(|| self.notional as f64 / self.volume as f64)
+    }
+}
+
+/// Activity slot of a single contract.
+#[derive(Debug)]
+pub(crate) struct ActivityCell {
+    activity: RwLock<ContractActivity>,
+}
+
+impl Default for ActivityCell {
+    fn default() -> Self {
+        Self {
+            activity: RwLock::new(ContractActivity {
+                session_start_ms: orderbook_rs::current_time_millis(),
+                ..ContractActivity::default()
+            }),
+        }
+    }
+}
+
+impl ActivityCell {
+    /// Adds a match of the order book to the session.
+    pub(crate) fn record_match(&self, price: u128, quantity: u64, timestamp_ms: u64) {
+        let mut activity = self
+            .activity
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        activity.volume = activity.volume.saturating_add(quantity);
+        activity.trade_count += 1;
+        activity.notional = activity
+            .notional
+            .saturating_add(price.saturating_mul(u128::from(quantity)));
+        activity.last_price = Some(price);
+        activity.last_trade_ms = Some(timestamp_ms);
+    }
+
+    /// Applies a change of open interest, flooring at zero.
+    pub(crate) fn apply_open_interest(&self, change: i64) {
+        let mut activity = self
+            .activity
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        activity.open_interest = activity.open_interest.saturating_add_signed(change);
+    }
+
+    /// Overwrites the open interest.
+    pub(crate) fn set_open_interest(&self, open_interest: u64) {
+        self.activity
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .open_interest = open_interest;
+    }
+
+    /// Starts a new session and returns the activity of the closed one.
+    pub(crate) fn roll(&self) -> ContractActivity {
+        let mut activity = self
+            .activity
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let closed = *activity;
+        *activity = ContractActivity {
+            open_interest: closed.open_interest,
+            last_price: closed.last_price,
+            last_trade_ms: closed.last_trade_ms,
+            session_start_ms: orderbook_rs::current_time_millis(),
+            ..ContractActivity::default()
+        };
+        closed
+    }
+
+    /// Returns a copy of the activity.
+    pub(crate) fn get(&self) -> ContractActivity {
+        *self.activity.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Trading activity aggregated over a set of contracts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivitySummary {
+    /// Total session volume.
+    pub volume: u64,
+    /// Total number of session trades.
+    pub trade_count: u64,
+    /// Total session notional.
+    pub notional: u128,
+    /// Total open interest.
+    pub open_interest: u64,
+    /// Session volume of calls.
+    pub call_volume: u64,
+    /// Session volume of puts.
+    pub put_volume: u64,
+    /// Open interest of calls.
+    pub call_open_interest: u64,
+    /// Open interest of puts.
+    pub put_open_interest: u64,
+    /// Number of contracts with volume or open interest.
+    pub active_contracts: usize,
+}
+
+impl ActivitySummary {
+    /// Adds the activity of one contract.
+    pub fn add(&mut self, option_style: OptionStyle, activity: &ContractActivity) {
+        self.volume = self.volume.saturating_add(activity.volume);
+        self.trade_count = self.trade_count.saturating_add(activity.trade_count);
+        self.notional = self.notional.saturating_add(activity.notional);
+        self.open_interest = self.open_interest.saturating_add(activity.open_interest);
+        match option_style {
+            OptionStyle::Call => {
+                self.call_volume = self.call_volume.saturating_add(activity.volume);
+                self.call_open_interest = self
+                    .call_open_interest
+                    .saturating_add(activity.open_interest);
+            }
+            OptionStyle::Put => {
+                self.put_volume = self.put_volume.saturating_add(activity.volume);
+                self.put_open_interest = self
+                    .put_open_interest
+                    .saturating_add(activity.open_interest);
+            }
+        }
+        if activity.volume > 0 || activity.open_interest > 0 {
+            self.active_contracts = self.active_contracts.saturating_add(1);
+        }
+    }
+
+    /// Adds another summary to this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.volume = self.volume.saturating_add(other.volume);
+        self.trade_count = self.trade_count.saturating_add(other.trade_count);
+        self.notional = self.notional.saturating_add(other.notional);
+        self.open_interest = self.open_interest.saturating_add(other.open_interest);
+        self.call_volume = self.call_volume.saturating_add(other.call_volume);
+        self.put_volume = self.put_volume.saturating_add(other.put_volume);
+        self.call_open_interest = self
+            .call_open_interest
+            .saturating_add(other.call_open_interest);
+        self.put_open_interest = self
+            .put_open_interest
+            .saturating_add(other.put_open_interest);
+        self.active_contracts = self.active_contracts.saturating_add(other.active_contracts);
+    }
+
+    /// Returns the volume-weighted average price of the session trades.
+    #[must_use]
+    pub fn vwap(&self) -> Option<f64> {
+        (self.volume > 0).then(|| self.notional as f64 / self.volume as f64)
+    }
+
+    /// Returns the put volume divided by the call volume.
+    #[must_use]
+    pub fn put_call_volume_ratio(&self) -> Option<f64> {
+        (self.call_volume > 0).then(|| self.put_volume as f64 / self.call_volume as f64)
+    }
+
+    /// Returns the put open interest divided by the call open interest.
+    #[must_use]
+    pub fn put_call_open_interest_ratio(&self) -> Option<f64> {
+        (self.call_open_interest > 0)
+            .then(|| self.put_open_interest as f64 / self.call_open_interest as f64)
+    }
+}
+
+/// Open interest of one contract, as published by an exchange.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenInterestRecord {
+    /// The contract symbol.
+    pub symbol: String,
+    /// Open contracts.
+    pub open_interest: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_interest_change() {
+        use PositionEffect::{Close, Open};
+        let change = |buyer, seller| PositionEffect::open_interest_change(5, buyer, seller);
+        assert_eq!(change(Open, Open), 5);
+        assert_eq!(change(Close, Close), -5);
+        assert_eq!(change(Open, Close), 0);
+        assert_eq!(change(Close, Open), 0);
+    }
+
+    #[test]
+    fn test_record_and_roll() {
+        let cell = ActivityCell::default();
+        cell.set_open_interest(3);
+        cell.record_match(10, 4, 1);
+        cell.record_match(13, 2, 2);
+        cell.record_match(12, 9, 3);
+        cell.apply_open_interest(4);
+        // Closing more than is open floors at zero.
+        cell.apply_open_interest(-9);
+
+        let activity = cell.get();
+        assert_eq!(activity.volume, 15);
+        assert_eq!(activity.trade_count, 3);
+        assert_eq!(activity.notional, 40 + 26 + 108);
+        assert!((activity.vwap().unwrap() - 174.0 / 15.0).abs() < 1e-12);
+        assert_eq!(activity.open_interest, 0);
+        assert_eq!(activity.last_price, Some(12));
+        assert_eq!(activity.last_trade_ms, Some(3));
+
+        cell.set_open_interest(20);
+        let closed = cell.roll();
+        assert_eq!(closed.volume, 15);
+        let fresh = cell.get();
+        assert_eq!(fresh.volume, 0);
+        assert_eq!(fresh.trade_count, 0);
+        assert!(fresh.vwap().is_none());
+        assert_eq!(fresh.open_interest, 20);
+        assert_eq!(fresh.last_price, Some(12));
+    }
+
+    #[test]
+    fn test_summary_ratios() {
+        let mut summary = ActivitySummary::default();
+        assert!(summary.put_call_volume_ratio().is_none());
+        let activity = |volume, open_interest| ContractActivity {
+            volume,
+            notional: u128::from(volume) * 10,
+            open_interest,
+            ..ContractActivity::default()
+        };
+        summary.add(OptionStyle::Call, &activity(4, 10));
+        summary.add(OptionStyle::Put, &activity(6, 5));
+        summary.add(OptionStyle::Put, &activity(0, 0));
+
+        assert_eq!(summary.volume, 10);
+        assert_eq!(summary.active_contracts, 2);
+        assert_eq!(summary.vwap(), Some(10.0));
+        assert_eq!(summary.put_call_volume_ratio(), Some(1.5));
+        assert_eq!(summary.put_call_open_interest_ratio(), Some(0.5));
+
+        let mut total = summary;
+        total.merge(&summary);
+        assert_eq!(total.open_interest, 30);
+        assert_eq!(total.active_contracts, 4);
+
+        // Totals saturate instead of overflowing.
+        summary.add(OptionStyle::Call, &activity(u64::MAX, u64::MAX));
+        assert_eq!(summary.volume, u64::MAX);
+        assert_eq!(summary.call_open_interest, u64::MAX);
+        total.merge(&summary);
+        assert_eq!(total.open_interest, u64::MAX);
+    }
+}
@@ -3,10 +3,19 @@
 //! This module provides the [`OptionOrderBook`] structure that wraps the
 //! OrderBook-rs `OrderBook<T>` implementation with option-specific functionality.
 
+use super::activity::{ActivityCell, ContractActivity, PositionEffect};
+use super::execution::{Execution, ExecutionLog};
+use super::greeks::GreeksInputs;
+use super::mass_quote::{ParticipantQuote, ParticipantQuotes, QuoteLevel, SideAction};
 use super::quote::Quote;
-use crate::Result;
+use crate::pricing::{
+    ContractEdge, ImpliedVolatilityQuote, PricingModel, implied_volatility, theoretical_price,
+};
+use crate::{Error, Result};
 use optionstratlib::OptionStyle;
-use orderbook_rs::{DefaultOrderBook, OrderBookSnapshot, OrderId, Side, TimeInForce};
+use orderbook_rs::{
+    DefaultOrderBook, OrderBookSnapshot, OrderId, Side, TimeInForce, TradeListener, TradeResult,
+};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
@@ -43,6 +52,12 @@ pub struct OptionOrderBook {
     option_style: OptionStyle,
     /// Unique identifier for this order book.
     id: OrderId,
+    /// Session volume and open interest.
+    activity: Arc<ActivityCell>,
+    /// Resting quotes per participant.
+    participant_quotes: ParticipantQuotes,
+    /// Recent matches, read by fill consumers.
+    executions: Arc<ExecutionLog>,
 }
 
 impl OptionOrderBook {
@@ -57,13 +72,33 @@ impl OptionOrderBook {
         let symbol = symbol.into();
         let symbol_hash = Self::hash_symbol(&symbol);
 
+        let executions = Arc::new(ExecutionLog::default());
+        let activity = Arc::new(ActivityCell::default());
+        let listener: TradeListener = {
+            let log = Arc::clone(&executions);
+            let activity = Arc::clone(&activity);
+            Arc::new(move |trade: &TradeResult| {
+                for transaction in trade.match_result.transactions.as_vec() {
+                    activity.record_match(
+                        transaction.price,
+                        transaction.quantity,
+                        transaction.timestamp,
+                    );
+                }
+                log.record(trade);
+            })
+        };
+
         Self {
             symbol: symbol.clone(),
             symbol_hash,
-            book: Arc::new(DefaultOrderBook::new(&symbol)),
+            book: Arc::new(DefaultOrderBook::with_trade_listener(&symbol, listener)),
             last_quote: Arc::new(Quote::empty(0)),
             option_style,
             id: OrderId::new(),
+            activity,
+            participant_quotes: ParticipantQuotes::default(),
+            executions,
         }
     }
 
@@ -112,6 +147,10 @@ impl OptionOrderBook {
 
     /// Adds a limit order to the book.
     ///
+    /// The order is placed directly, without pre-trade risk checks. Orders
+    /// that must be checked are entered with
+    /// [`RiskEngine::submit`](crate::risk::RiskEngine::submit) instead.
+    ///
     /// # Arguments
     ///
     /// * `order_id` - Unique identifier for the order
@@ -170,6 +209,110 @@ impl OptionOrderBook {
         }
     }
 
+    /// Applies the open interest change of a trade in this contract.
+    ///
+    /// Volume, trade count, VWAP and last price come from the matches of
+    /// this book; the caller only supplies the opening/closing flags the
+    /// matching engine does not know.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the quantity is zero.
+    pub fn apply_position_effects(
+        &self,
+        quantity: u64,
+        buyer: PositionEffect,
+        seller: PositionEffect,
+    ) -> Result<()> {
+        if quantity == 0 {
+            return Err(Error::validation(format!(
+                "trade in {} has zero quantity",
+                self.symbol
+            )));
+        }
+        self.activity
+            .apply_open_interest(PositionEffect::open_interest_change(
+                quantity, buyer, seller,
+            ));
+        Ok(())
+    }
+
+    /// Sets the open interest, e.g. from an exchange file.
+    pub fn set_open_interest(&self, open_interest: u64) {
+        self.activity.set_open_interest(open_interest);
+    }
+
+    /// Returns the session volume, trades, VWAP and open interest.
+    #[must_use]
+    pub fn activity(&self) -> ContractActivity {
+        self.activity.get()
+    }
+
+    /// Starts a new trading session and returns the activity of the closed
+    /// one. Open interest and the last trade price carry over.
+    pub fn roll_activity(&self) -> ContractActivity {
+        self.activity.roll()
+    }
+
+    /// Returns the retained matches of this book with a sequence at or
+    /// above `sequence`, oldest first.
+    ///
+    /// The book keeps its last [`EXECUTION_LOG_CAPACITY`] matches. Reading
+    /// does not consume them, so each consumer keeps its own cursor; one
+    /// whose cursor is below the first returned sequence has missed matches.
+    ///
+    /// [`EXECUTION_LOG_CAPACITY`]: super::EXECUTION_LOG_CAPACITY
+    #[must_use]
+    pub fn executions_since(&self, sequence: u64) -> Vec<Execution> {
+        self.executions.since(sequence)
+    }
+
+    /// Returns the sequence of the next match, i.e. the number of matches
+    /// executed in this book.
+    #[must_use]
+    pub fn next_execution_sequence(&self) -> u64 {
+        self.executions.next_sequence()
+    }
+
+    /// Replaces a participant's two-sided quote in this contract.
+    ///
+    /// A missing or zero-size side cancels the participant's resting order
+    /// on that side. A side whose price and size are unchanged keeps its
+    /// order, and with it its queue priority. Stale sides are cancelled
+    /// before new ones are placed, so the new quote never trades against
+    /// the participant's previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if a price is zero or the bid is not
+    /// below the ask, in which case the previous quote is left in place, or
+    /// `Error::OrderBookError` if a new order is refused after the previous
+    /// ones were cancelled.
+    pub fn replace_quote(
+        &self,
+        participant: &str,
+        bid: Option<QuoteLevel>,
+        ask: Option<QuoteLevel>,
+    ) -> Result<(SideAction, SideAction)> {
+        self.participant_quotes.replace(self, participant, bid, ask)
+    }
+
+    /// Returns the resting quote of a participant.
+    #[must_use]
+    pub fn participant_quote(&self, participant: &str) -> Option<ParticipantQuote> {
+        self.participant_quotes.get(participant)
+    }
+
+    /// Cancels the resting quote of a participant. Returns the number of
+    /// orders cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying order book rejects a cancel.
+    pub fn cancel_participant_quote(&self, participant: &str) -> Result<usize> {
+        self.participant_quotes.cancel(self, participant)
+    }
+
     /// Returns the current best quote.
     #[must_use]
     pub fn best_quote(&self) -> Quote {
@@ -346,12 +489,193 @@ impl OptionOrderBook {
     pub fn market_impact(&self, quantity: u64, side: Side) -> orderbook_rs::MarketImpact {
         self.book.market_impact(quantity, side)
     }
+
+    /// Returns the implied volatility of a price of this contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The option price, in strike units
+    /// * `strike` - The strike price of the contract
+    /// * `inputs` - Underlying price, rates and time to expiry
+    /// * `model` - Black-Scholes, or Black-76 for futures underlyings
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PricingError` if the price is at or below intrinsic
+    /// value, above the no-arbitrage bound, or cannot be inverted.
+    pub fn implied_volatility_at(
+        &self,
+        price: f64,
+        strike: u64,
+        inputs: &GreeksInputs,
+        model: PricingModel,
+    ) -> Result<f64> {
+        implied_volatility(price, strike, self.option_style, inputs, model)
+    }
+
+    /// Returns the implied volatilities of the best bid, best ask and mid.
+    ///
+    /// Sides that are not quoted or cannot be inverted (e.g., a bid below
+    /// intrinsic value) are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PricingError` if no side of the book can be inverted.
+    pub fn implied_volatility(
+        &self,
+        strike: u64,
+        inputs: &GreeksInputs,
+        model: PricingModel,
+    ) -> Result<ImpliedVolatilityQuote> {
+        let quote = self.best_quote();
+        let invert = |price: Option<f64>| {
+            price.and_then(|p| self.implied_volatility_at(p, strike, inputs, model).ok())
+        };
+        let iv = ImpliedVolatilityQuote {
+            bid: invert(quote.bid_price().map(|p| p as f64)),
+            ask: invert(quote.ask_price().map(|p| p as f64)),
+            mid: invert(quote.mid_price()),
+        };
+        if iv.is_empty() {
+            return Err(Error::pricing(format!(
+                "no implied volatility for {}: quote cannot be inverted",
+                self.symbol
+            )));
+        }
+        Ok(iv)
+    }
+
+    /// Returns the theoretical value of this contract, in strike units.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - The strike price of the contract
+    /// * `inputs` - Underlying price, volatility, rates and time to expiry
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PricingError` if the contract cannot be priced.
+    pub fn theoretical_value(&self, strike: u64, inputs: &GreeksInputs) -> Result<f64> {
+        theoretical_price(strike, self.option_style, inputs)
+    }
+
+    /// Returns the theoretical value of this contract and the edge of the
+    /// best bid, best ask and mid against it.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - The strike price of the contract
+    /// * `inputs` - Pricing inputs; `volatility` is the theoretical volatility
+    /// * `model` - Black-Scholes, or Black-76 for futures underlyings
+    /// * `tick_size` - Tick size used to express edges in ticks
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PricingError` if the contract cannot be priced.
+    pub fn edge(
+        &self,
+        strike: u64,
+        inputs: &GreeksInputs,
+        model: PricingModel,
+        tick_size: u128,
+    ) -> Result<ContractEdge> {
+        let theo = self.theoretical_value(strike, inputs)?;
+        let iv = self
+            .implied_volatility(strike, inputs, model)
+            .unwrap_or_default();
+        Ok(ContractEdge::new(
+            self.option_style,
+            theo,
+            inputs.volatility,
+            self.best_bid(),
+            self.best_ask(),
+            &iv,
+            tick_size,
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_implied_volatility_from_quote() {
+        let book = OptionOrderBook::new("SPX-20240329-100-C", OptionStyle::Call);
+        let inputs = GreeksInputs {
+            underlying_price: 100,
+            volatility: 0.0,
+            risk_free_rate: 0.0,
+            dividend_yield: 0.0,
+            years_to_expiry: 0.25,
+        };
+        let model = PricingModel::BlackScholes;
+        assert!(book.implied_volatility(100, &inputs, model).is_err());
+
+        book.add_limit_order(OrderId::new(), Side::Buy, 3, 10)
+            .unwrap();
+        book.add_limit_order(OrderId::new(), Side::Sell, 5, 10)
+            .unwrap();
+
+        let iv = book.implied_volatility(100, &inputs, model).unwrap();
+        let (bid, mid, ask) = (iv.bid.unwrap(), iv.mid.unwrap(), iv.ask.unwrap());
+        assert!(bid < mid && mid < ask);
+        // ATM call ~ 0.4 * S * sigma * sqrt(T): a price of 4 is ~20% vol.
+        assert!((mid - 0.2).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_implied_volatility_below_intrinsic() {
+        let book = OptionOrderBook::new("SPX-20240329-80-C", OptionStyle::Call);
+        let inputs = GreeksInputs {
+            underlying_price: 100,
+            years_to_expiry: 0.25,
+            ..GreeksInputs::default()
+        };
+
+        book.add_limit_order(OrderId::new(), Side::Buy, 19, 10)
+            .unwrap();
+        book.add_limit_order(OrderId::new(), Side::Sell, 21, 10)
+            .unwrap();
+
+        let iv = book
+            .implied_volatility(80, &inputs, PricingModel::BlackScholes)
+            .unwrap();
+        assert!(iv.bid.is_none());
+        assert!(iv.ask.is_some());
+        assert!(
+            book.implied_volatility_at(19.0, 80, &inputs, PricingModel::BlackScholes)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_edge_against_theo() {
+        let book = OptionOrderBook::new("SPX-20240329-100-C", OptionStyle::Call);
+        let inputs = GreeksInputs {
+            underlying_price: 100,
+            volatility: 0.2,
+            years_to_expiry: 0.25,
+            ..GreeksInputs::default()
+        };
+        let theo = book.theoretical_value(100, &inputs).unwrap();
+        assert!((theo - 3.99).abs() < 0.01);
+
+        book.add_limit_order(OrderId::new(), Side::Buy, 3, 10)
+            .unwrap();
+        book.add_limit_order(OrderId::new(), Side::Sell, 6, 10)
+            .unwrap();
+
+        let edge = book
+            .edge(100, &inputs, PricingModel::BlackScholes, 1)
+            .unwrap();
+        assert!((edge.bid_edge.unwrap().price - (theo - 3.0)).abs() < 1e-9);
+        assert!((edge.ask_edge.unwrap().price - (6.0 - theo)).abs() < 1e-9);
+        let mid = edge.mid_edge.unwrap();
+        assert!(mid.price > 0.0);
+        assert!(mid.vol_points.unwrap() > 0.0);
+    }
+
     #[test]
     fn test_option_order_book_creation() {
         let book = OptionOrderBook::new("BTC-20240329-50000-C", OptionStyle::Call);
@@ -571,6 +895,40 @@ mod tests {
         assert_eq!(book.ask_depth_at_price(106), 0);
     }
 
+    #[test]
+    fn test_apply_position_effects() {
+        use crate::orderbook::PositionEffect::Open;
+
+        let book = OptionOrderBook::new("TEST", OptionStyle::Call);
+        assert!(book.apply_position_effects(0, Open, Open).is_err());
+
+        book.set_open_interest(5);
+        book.apply_position_effects(3, Open, Open).unwrap();
+        let activity = book.activity();
+        assert_eq!(activity.volume, 0);
+        assert_eq!(activity.open_interest, 8);
+
+        // Matches of the book drive volume, trades and VWAP.
+        book.add_limit_order(OrderId::new(), Side::Sell, 100, 2)
+            .unwrap();
+        book.add_limit_order(OrderId::new(), Side::Sell, 104, 2)
+            .unwrap();
+        book.add_limit_order(OrderId::new(), Side::Buy, 104, 3)
+            .unwrap();
+        book.inner()
+            .submit_market_order(OrderId::new(), 1, Side::Buy)
+            .unwrap();
+        let activity = book.activity();
+        assert_eq!(activity.volume, 4);
+        assert_eq!(activity.trade_count, 3);
+        assert_eq!(activity.vwap(), Some(102.0));
+        assert_eq!(activity.last_price, Some(104));
+        assert_eq!(activity.open_interest, 8);
+
+        assert_eq!(book.roll_activity().trade_count, 3);
+        assert_eq!(book.activity().trade_count, 0);
+    }
+
     #[test]
     fn test_vwap() {
         let book = OptionOrderBook::new("BTC-20240329-50000-C", OptionStyle::Call);
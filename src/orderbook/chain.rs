@@ -3,11 +3,27 @@
 //! This module provides the [`OptionChainOrderBook`] and [`OptionChainOrderBookManager`]
 //! for managing all strikes within a single expiration.
 
-use super::strike::{StrikeOrderBook, StrikeOrderBookManager};
+use super::activity::ActivitySummary;
+use super::corporate_action::{CorporateAction, CorporateActionReport};
+use super::exposure::{AggregatedGreeks, ExposureWeights};
+use super::future::UnderlyingFuture;
+use super::greeks::{GreeksInputs, GreeksRefreshReport, GreeksSnapshot, GreeksUpdate};
+use super::market_data::{MarketData, PriceSource};
+use super::mass_quote::{MassQuote, MassQuoteAck, MassQuoteEntry, MassQuoteReport, SideAction};
+use super::reference::{Moneyness, ReferencePriceFeed};
+use super::strike::{DeltaStrike, StrikeOrderBook, StrikeOrderBookManager};
+use crate::analytics::{
+    ExpectedMove, GammaExposureReport, RiskNeutralDensity, StaticArbitrage, scan_chain,
+};
 use crate::error::{Error, Result};
+use crate::pricing::{
+    ChainEdgeReport, ImpliedForward, ImpliedVolatilityQuote, ParityReport, PricingModel,
+    SmilePoint, StrikeEdge, StrikeParity, VolatilityProvider, VolatilitySmile, compute_greeks,
+};
 use crossbeam_skiplist::SkipMap;
-use optionstratlib::ExpirationDate;
+use optionstratlib::{ExpirationDate, OptionStyle};
 use orderbook_rs::OrderId;
+use rust_decimal::prelude::ToPrimitive;
 use std::sync::Arc;
 
 /// Option chain order book for a single expiration.
@@ -30,6 +46,10 @@ pub struct OptionChainOrderBook {
     expiration: ExpirationDate,
     /// Strike order book manager.
     strikes: Arc<StrikeOrderBookManager>,
+    /// Underlying price source, shared with the strikes.
+    price: Arc<PriceSource>,
+    /// Market data of the underlying, shared across the hierarchy.
+    market: Arc<MarketData>,
     /// Unique identifier for this option chain order book.
     id: OrderId,
 }
@@ -43,12 +63,29 @@ impl OptionChainOrderBook {
     /// * `expiration` - The expiration date
     #[must_use]
     pub fn new(underlying: impl Into<String>, expiration: ExpirationDate) -> Self {
+        Self::with_market(underlying, expiration, Arc::new(MarketData::new()))
+    }
+
+    /// Creates a new option chain order book sharing the market data of its
+    /// underlying.
+    pub(crate) fn with_market(
+        underlying: impl Into<String>,
+        expiration: ExpirationDate,
+        market: Arc<MarketData>,
+    ) -> Self {
         let underlying = underlying.into();
+        let price = Arc::new(PriceSource::new(Arc::clone(&market)));
 
         Self {
-            strikes: Arc::new(StrikeOrderBookManager::new(&underlying, expiration)),
+            strikes: Arc::new(StrikeOrderBookManager::with_price_source(
+                &underlying,
+                expiration,
+                Arc::clone(&price),
+            )),
             underlying,
             expiration,
+            price,
+            market,
             id: OrderId::new(),
         }
     }
@@ -120,6 +157,83 @@ impl OptionChainOrderBook {
         self.strikes.total_order_count()
     }
 
+    /// Returns the session activity and open interest of every contract.
+    #[must_use]
+    pub fn activity_summary(&self) -> ActivitySummary {
+        self.strikes
+            .iter()
+            .fold(ActivitySummary::default(), |mut summary, entry| {
+                summary.merge(&entry.value().activity_summary());
+                summary
+            })
+    }
+
+    /// Starts a new trading session for every contract and returns the
+    /// activity of the closed one.
+    pub fn roll_activity(&self) -> ActivitySummary {
+        self.strikes
+            .iter()
+            .fold(ActivitySummary::default(), |mut summary, entry| {
+                summary.merge(&entry.value().roll_activity());
+                summary
+            })
+    }
+
+    /// Applies a participant's mass quote to this chain.
+    ///
+    /// Every entry replaces the participant's quote in one contract and
+    /// gets its own acknowledgement; a rejected entry leaves that
+    /// contract's previous quote in place. Entries are applied in order and
+    /// the message as a whole is not atomic. Entries of another expiration or
+    /// of an unlisted strike are rejected. Entries are placed directly,
+    /// without pre-trade risk checks; checked quotes are applied with
+    /// [`RiskEngine::apply_mass_quote`](crate::risk::RiskEngine::apply_mass_quote)
+    /// instead.
+    pub fn apply_mass_quote(&self, quote: &MassQuote) -> MassQuoteReport {
+        let acks = quote
+            .entries
+            .iter()
+            .map(|entry| {
+                MassQuoteAck::new(
+                    entry,
+                    self.apply_mass_quote_entry(&quote.participant, entry),
+                )
+            })
+            .collect();
+        MassQuoteReport::new(quote.participant.clone(), acks)
+    }
+
+    /// Applies one mass quote entry to this chain.
+    pub(crate) fn apply_mass_quote_entry(
+        &self,
+        participant: &str,
+        entry: &MassQuoteEntry,
+    ) -> Result<(SideAction, SideAction)> {
+        if entry.expiration != self.expiration {
+            return Err(Error::expiration_not_found(entry.expiration.to_string()));
+        }
+        self.strikes
+            .get(entry.strike)?
+            .get(entry.option_style)
+            .replace_quote(participant, entry.bid, entry.ask)
+    }
+
+    /// Cancels a participant's quotes in every contract. Returns the number
+    /// of orders cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying order book rejects a cancel.
+    pub fn cancel_participant_quotes(&self, participant: &str) -> Result<usize> {
+        let mut cancelled = 0;
+        for entry in self.strikes.iter() {
+            let strike = entry.value();
+            cancelled += strike.call().cancel_participant_quote(participant)?;
+            cancelled += strike.put().cancel_participant_quote(participant)?;
+        }
+        Ok(cancelled)
+    }
+
     /// Returns the ATM strike closest to the given spot price.
     ///
     /// # Errors
@@ -129,6 +243,637 @@ impl OptionChainOrderBook {
         self.strikes.atm_strike(spot)
     }
 
+    /// Returns the listed strike whose stored delta is nearest to a target
+    /// delta (e.g., `-0.25` for the 25-delta put).
+    ///
+    /// See [`StrikeOrderBookManager::strike_for_delta`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if no strike has Greeks for the
+    /// given option style.
+    pub fn strike_for_delta(
+        &self,
+        target_delta: f64,
+        option_style: OptionStyle,
+    ) -> Result<DeltaStrike> {
+        self.strikes.strike_for_delta(target_delta, option_style)
+    }
+
+    /// Returns the listed strike whose delta is nearest to a target delta,
+    /// computing deltas from the chain's market data and the given
+    /// volatilities instead of the stored Greeks.
+    ///
+    /// Strikes without a volatility from the provider are skipped. The
+    /// computed Greeks are not stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// a pricing error if the delta of a strike with a volatility cannot be
+    /// computed, or `Error::NoDataAvailable` if no strike has a volatility.
+    pub fn strike_for_delta_with(
+        &self,
+        target_delta: f64,
+        option_style: OptionStyle,
+        volatilities: &dyn VolatilityProvider,
+    ) -> Result<DeltaStrike> {
+        let inputs = self.greeks_inputs(0.0)?;
+        let mut deltas = Vec::with_capacity(self.strikes.len());
+        for entry in self.strikes.iter() {
+            let strike = *entry.key();
+            let Some(volatility) = volatilities.volatility(&self.expiration, strike, option_style)
+            else {
+                continue;
+            };
+            let inputs = GreeksInputs {
+                volatility,
+                ..inputs
+            };
+            let delta = compute_greeks(strike, option_style, &inputs)?
+                .delta
+                .to_f64()
+                .ok_or_else(|| Error::greeks(format!("delta of strike {strike} is not finite")))?;
+            deltas.push((strike, delta));
+        }
+        DeltaStrike::from_deltas(&deltas, target_delta, option_style)
+    }
+
+    /// Links this chain to the futures contract its options deliver into.
+    ///
+    /// Once linked, ATM and moneyness calculations use the future price
+    /// instead of a spot price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if a future is already linked.
+    pub fn link_future(&self, future: UnderlyingFuture) -> Result<Arc<UnderlyingFuture>> {
+        let future = Arc::new(future);
+        if !self.price.link_future(Arc::clone(&future)) {
+            return Err(Error::validation(format!(
+                "future already linked for {}",
+                self.expiration
+            )));
+        }
+        Ok(future)
+    }
+
+    /// Returns the linked futures contract, if any.
+    #[must_use]
+    pub fn future(&self) -> Option<Arc<UnderlyingFuture>> {
+        self.price.future().map(Arc::clone)
+    }
+
+    /// Returns the reference price feed of the underlying.
+    #[must_use]
+    pub fn reference(&self) -> &ReferencePriceFeed {
+        self.market.reference()
+    }
+
+    /// Returns the market data of the underlying.
+    #[must_use]
+    pub fn market(&self) -> &MarketData {
+        &self.market
+    }
+
+    /// Returns an Arc reference to the market data of the underlying.
+    #[must_use]
+    pub fn market_arc(&self) -> Arc<MarketData> {
+        Arc::clone(&self.market)
+    }
+
+    /// Returns the risk-free rate for this expiration from the rate curve.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn risk_free_rate(&self) -> Result<f64> {
+        self.market.risk_free_rate(&self.expiration)
+    }
+
+    /// Returns the dividend or borrow yield for this expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn dividend_yield(&self) -> Result<f64> {
+        self.market.dividend_yield(&self.expiration)
+    }
+
+    /// Returns the underlying price used for ATM and moneyness calculations.
+    ///
+    /// This is the price of the linked future, if any, otherwise the
+    /// reference price of the underlying when it is not stale.
+    #[must_use]
+    pub fn underlying_price(&self) -> Option<u64> {
+        self.price.price()
+    }
+
+    /// Returns the ATM strike closest to the current underlying price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or `Error::NoDataAvailable` if there are no strikes.
+    pub fn current_atm_strike(&self) -> Result<u64> {
+        self.strikes.atm_strike(self.require_underlying_price()?)
+    }
+
+    /// Returns the moneyness of a strike, `strike / underlying_price`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn moneyness(&self, strike: u64) -> Result<f64> {
+        Ok(strike as f64 / self.require_underlying_price()? as f64)
+    }
+
+    /// Classifies a strike as in, at or out of the money for the given style.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or `Error::NoDataAvailable` if there are no strikes.
+    pub fn classify(&self, strike: u64, option_style: OptionStyle) -> Result<Moneyness> {
+        let price = self.require_underlying_price()?;
+        let atm = self.strikes.atm_strike(price)?;
+        Ok(Moneyness::classify(strike, atm, price, option_style))
+    }
+
+    /// Returns the out-of-the-money strikes for the given style (sorted),
+    /// as classified by [`Self::classify`]: the ATM strike is excluded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn otm_strikes(&self, option_style: OptionStyle) -> Result<Vec<u64>> {
+        let price = self.require_underlying_price()?;
+        if self.strikes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let atm = self.strikes.atm_strike(price)?;
+        Ok(self
+            .strike_prices()
+            .into_iter()
+            .filter(|&k| {
+                Moneyness::classify(k, atm, price, option_style) == Moneyness::OutOfTheMoney
+            })
+            .collect())
+    }
+
+    /// Returns the underlying price or a market data error.
+    fn require_underlying_price(&self) -> Result<u64> {
+        self.underlying_price().ok_or_else(|| {
+            Error::market_data(format!(
+                "no underlying price for {} {}",
+                self.underlying, self.expiration
+            ))
+        })
+    }
+
+    /// Applies a corporate action to every strike in this chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the action cannot be applied to
+    /// the listed strikes. No strike is modified on error.
+    pub fn apply_corporate_action(
+        &self,
+        action: &CorporateAction,
+    ) -> Result<CorporateActionReport> {
+        self.strikes.apply_corporate_action(action)
+    }
+
+    /// Applies a batch of Greeks updates to the strikes of this chain.
+    ///
+    /// Every targeted strike is resolved before any update is applied, so a
+    /// batch is either fully applied or not applied at all. Returns the
+    /// number of contracts updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StrikeNotFound` if an update targets a strike that is
+    /// not listed in this chain.
+    pub fn update_greeks(&self, updates: impl IntoIterator<Item = GreeksUpdate>) -> Result<usize> {
+        let resolved = updates
+            .into_iter()
+            .map(|update| Ok((self.strikes.get(update.strike)?, update)))
+            .collect::<Result<Vec<_>>>()?;
+
+        for (strike, update) in &resolved {
+            strike.set_greeks_snapshot(update.option_style, update.snapshot.clone());
+        }
+        Ok(resolved.len())
+    }
+
+    /// Returns the pricing model of this chain: Black-76 when a future is
+    /// linked, Black-Scholes on the spot price otherwise.
+    #[must_use]
+    pub fn pricing_model(&self) -> PricingModel {
+        if self.price.future().is_some() {
+            PricingModel::Black76
+        } else {
+            PricingModel::BlackScholes
+        }
+    }
+
+    /// Returns the pricing inputs of this chain for a given volatility.
+    ///
+    /// Uses the underlying price (see [`Self::underlying_price`]) and the
+    /// rate and dividend curves at this expiration, adjusted for the
+    /// [`pricing_model`](Self::pricing_model).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or an error if the expiration cannot be converted to years.
+    pub fn greeks_inputs(&self, volatility: f64) -> Result<GreeksInputs> {
+        let inputs = GreeksInputs {
+            underlying_price: self.require_underlying_price()?,
+            volatility,
+            risk_free_rate: self.risk_free_rate()?,
+            dividend_yield: self.dividend_yield()?,
+            years_to_expiry: self.expiration.get_years()?.to_f64(),
+        };
+        Ok(self.pricing_model().adjust(inputs))
+    }
+
+    /// Returns the implied volatilities of the best bid, ask and mid of a
+    /// contract, using the chain's market data and pricing model.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StrikeNotFound` if the strike does not exist,
+    /// `Error::MarketDataError` if no underlying price is available, or
+    /// `Error::PricingError` if no side of the book can be inverted.
+    pub fn implied_volatility(
+        &self,
+        strike: u64,
+        option_style: OptionStyle,
+    ) -> Result<ImpliedVolatilityQuote> {
+        let inputs = self.greeks_inputs(0.0)?;
+        self.strikes
+            .get(strike)?
+            .get(option_style)
+            .implied_volatility(strike, &inputs, self.pricing_model())
+    }
+
+    /// Returns the forward price of this expiration, in strike units.
+    ///
+    /// This is the futures price for a linked future, otherwise the
+    /// underlying price carried at the rate and dividend curves.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn forward_price(&self) -> Result<f64> {
+        let inputs = self.greeks_inputs(0.0)?;
+        let carry = inputs.risk_free_rate - inputs.dividend_yield;
+        Ok(inputs.underlying_price as f64 * (carry * inputs.years_to_expiry).exp())
+    }
+
+    /// Returns the put-call parity analytics of a strike.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StrikeNotFound` if the strike does not exist, or
+    /// `Error::MarketDataError` if no underlying price is available.
+    pub fn strike_parity(&self, strike: u64) -> Result<StrikeParity> {
+        let inputs = self.greeks_inputs(0.0)?;
+        Ok(self.strikes.get(strike)?.parity(&inputs))
+    }
+
+    /// Returns the put-call parity analytics of every strike, with the
+    /// implied forward of the chain and the parity violations.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn parity_report(&self) -> Result<ParityReport> {
+        let inputs = self.greeks_inputs(0.0)?;
+        let strikes = self
+            .strikes
+            .iter()
+            .map(|entry| entry.value().parity(&inputs))
+            .collect();
+        Ok(ParityReport::new(self.expiration, strikes, &inputs))
+    }
+
+    /// Returns the forward implied by the call and put quotes: the median
+    /// of the per-strike synthetic forwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or `Error::NoDataAvailable` if no strike has two-sided call and put
+    /// quotes.
+    pub fn implied_forward(&self) -> Result<ImpliedForward> {
+        self.parity_report()?.implied_forward.ok_or_else(|| {
+            Error::no_data(format!(
+                "no implied forward for {} {}: no strike has call and put quotes",
+                self.underlying, self.expiration
+            ))
+        })
+    }
+
+    /// Returns the risk-neutral distribution of the underlying at this
+    /// expiration, read off the out-of-the-money mids (Breeden-Litzenberger).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or `Error::NoDataAvailable` if fewer than three strikes have a mid.
+    pub fn risk_neutral_density(&self) -> Result<RiskNeutralDensity> {
+        RiskNeutralDensity::from_chain(self)
+    }
+
+    /// Returns the expected move implied by the ATM straddle mid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or `Error::NoDataAvailable` if no strike has call and put mids.
+    pub fn expected_move(&self) -> Result<ExpectedMove> {
+        ExpectedMove::from_chain(self)
+    }
+
+    /// Builds the volatility smile of this chain from the live quotes.
+    ///
+    /// Every strike where the call or the put has an implied volatility is
+    /// included; log-moneyness is measured against
+    /// [`forward_price`](Self::forward_price).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or `Error::NoDataAvailable` if no contract quote can be inverted.
+    pub fn volatility_smile(&self) -> Result<VolatilitySmile> {
+        let inputs = self.greeks_inputs(0.0)?;
+        let model = self.pricing_model();
+        let forward = self.forward_price()?;
+
+        let points: Vec<SmilePoint> = self
+            .strikes
+            .iter()
+            .filter_map(|entry| {
+                let strike = *entry.key();
+                let book = entry.value();
+                let invert = |option_style| {
+                    book.get(option_style)
+                        .implied_volatility(strike, &inputs, model)
+                        .unwrap_or_default()
+                };
+                let point = SmilePoint {
+                    strike,
+                    log_moneyness: (strike as f64 / forward).ln(),
+                    call: invert(OptionStyle::Call),
+                    put: invert(OptionStyle::Put),
+                };
+                (!point.call.is_empty() || !point.put.is_empty()).then_some(point)
+            })
+            .collect();
+
+        if points.is_empty() {
+            return Err(Error::no_data(format!(
+                "no implied volatilities for {} {}",
+                self.underlying, self.expiration
+            )));
+        }
+        Ok(VolatilitySmile::new(
+            self.expiration,
+            forward,
+            inputs.years_to_expiry,
+            points,
+        ))
+    }
+
+    /// Returns the Greeks of every contract aggregated with the given
+    /// weights.
+    ///
+    /// Each contract uses the underlying price recorded with its Greeks,
+    /// falling back to the chain's underlying price; contracts with neither
+    /// are counted in `missing_count`.
+    #[must_use]
+    pub fn aggregate_greeks(&self, weights: &ExposureWeights<'_>) -> AggregatedGreeks {
+        let underlying_price = self.underlying_price();
+        self.strikes
+            .iter()
+            .fold(AggregatedGreeks::default(), |mut aggregate, entry| {
+                aggregate.merge(&AggregatedGreeks::from_strike(
+                    entry.value(),
+                    weights,
+                    underlying_price,
+                ));
+                aggregate
+            })
+    }
+
+    /// Returns the dealer gamma exposure of this chain by strike, with the
+    /// zero-gamma level, call/put walls and max-pain strike.
+    ///
+    /// See [`GammaExposureReport::from_chain`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn gamma_exposure(&self) -> Result<GammaExposureReport> {
+        GammaExposureReport::from_chain(self)
+    }
+
+    /// Scans the resting quotes of this chain for executable static
+    /// arbitrages: price bounds, vertical spreads and butterflies.
+    ///
+    /// Bounds are only checked when an underlying price is available.
+    #[must_use]
+    pub fn scan_arbitrage(&self) -> Vec<StaticArbitrage> {
+        scan_chain(self)
+    }
+
+    /// Returns the theoretical value of a contract, in strike units.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - The strike price
+    /// * `option_style` - Call or Put
+    /// * `volatilities` - Theoretical volatilities, e.g. a fitted surface
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StrikeNotFound` if the strike does not exist,
+    /// `Error::NoDataAvailable` if the provider has no volatility for the
+    /// contract, `Error::MarketDataError` if no underlying price is
+    /// available, or a pricing error if the contract cannot be priced.
+    pub fn theoretical_value(
+        &self,
+        strike: u64,
+        option_style: OptionStyle,
+        volatilities: &dyn VolatilityProvider,
+    ) -> Result<f64> {
+        let book = self.strikes.get(strike)?;
+        let volatility = volatilities
+            .volatility(&self.expiration, strike, option_style)
+            .ok_or_else(|| {
+                Error::no_data(format!(
+                    "no theoretical volatility for {} {} {strike} {option_style:?}",
+                    self.underlying, self.expiration
+                ))
+            })?;
+        let inputs = self.greeks_inputs(volatility)?;
+        book.get(option_style).theoretical_value(strike, &inputs)
+    }
+
+    /// Returns the theoretical values and quote edges of both legs of a
+    /// strike.
+    ///
+    /// Legs without a volatility from the provider are `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StrikeNotFound` if the strike does not exist,
+    /// `Error::MarketDataError` if no underlying price is available, or a
+    /// pricing error if a contract cannot be priced.
+    pub fn strike_edge(
+        &self,
+        strike: u64,
+        volatilities: &dyn VolatilityProvider,
+        tick_size: u128,
+    ) -> Result<StrikeEdge> {
+        let book = self.strikes.get(strike)?;
+        let inputs = self.greeks_inputs(0.0)?;
+        self.legs_edge(&book, inputs, volatilities, tick_size)
+    }
+
+    /// Returns the theoretical values and quote edges of every strike,
+    /// with the mean mid edge of the chain.
+    ///
+    /// Strikes with a contract that cannot be priced are left out of the
+    /// aggregates and listed in the report's `skipped`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn edge_report(
+        &self,
+        volatilities: &dyn VolatilityProvider,
+        tick_size: u128,
+    ) -> Result<ChainEdgeReport> {
+        let inputs = self.greeks_inputs(0.0)?;
+        let mut strikes = Vec::with_capacity(self.strikes.len());
+        let mut skipped = Vec::new();
+        for entry in self.strikes.iter() {
+            match self.legs_edge(entry.value(), inputs, volatilities, tick_size) {
+                Ok(edge) => strikes.push(edge),
+                Err(_) => skipped.push(*entry.key()),
+            }
+        }
+        Ok(ChainEdgeReport::new(self.expiration, strikes).with_skipped(skipped))
+    }
+
+    /// Computes the edges of the call and put of a strike.
+    fn legs_edge(
+        &self,
+        book: &StrikeOrderBook,
+        inputs: GreeksInputs,
+        volatilities: &dyn VolatilityProvider,
+        tick_size: u128,
+    ) -> Result<StrikeEdge> {
+        let model = self.pricing_model();
+        let edge = |option_style| {
+            volatilities
+                .volatility(&self.expiration, book.strike(), option_style)
+                .map(|volatility| {
+                    let inputs = GreeksInputs {
+                        volatility,
+                        ..inputs
+                    };
+                    book.get(option_style)
+                        .edge(book.strike(), &inputs, model, tick_size)
+                })
+                .transpose()
+        };
+        Ok(StrikeEdge {
+            strike: book.strike(),
+            call: edge(OptionStyle::Call)?,
+            put: edge(OptionStyle::Put)?,
+        })
+    }
+
+    /// Computes and stores the Greeks of both legs of a strike.
+    ///
+    /// Legs without a volatility from the provider are left unchanged.
+    /// Returns the number of contracts updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StrikeNotFound` if the strike does not exist,
+    /// `Error::MarketDataError` if no underlying price is available, or a
+    /// pricing error if the Greeks cannot be computed.
+    pub fn refresh_strike_greeks(
+        &self,
+        strike: u64,
+        volatilities: &dyn VolatilityProvider,
+    ) -> Result<usize> {
+        let book = self.strikes.get(strike)?;
+        let inputs = self.greeks_inputs(0.0)?;
+        let mut updated = 0;
+        for option_style in [OptionStyle::Call, OptionStyle::Put] {
+            updated += usize::from(self.refresh_leg(&book, option_style, inputs, volatilities)?);
+        }
+        Ok(updated)
+    }
+
+    /// Computes and stores the Greeks of both legs of every strike.
+    ///
+    /// Legs without a volatility from the provider, and legs whose Greeks
+    /// cannot be computed, are left unchanged and listed in the report's
+    /// `skipped`, so one bad contract does not stop the rest of the chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn refresh_greeks(
+        &self,
+        volatilities: &dyn VolatilityProvider,
+    ) -> Result<GreeksRefreshReport> {
+        let inputs = self.greeks_inputs(0.0)?;
+        let mut report = GreeksRefreshReport::default();
+        for entry in self.strikes.iter() {
+            for option_style in [OptionStyle::Call, OptionStyle::Put] {
+                match self.refresh_leg(entry.value(), option_style, inputs, volatilities) {
+                    Ok(true) => report.updated += 1,
+                    _ => report
+                        .skipped
+                        .push((self.expiration, *entry.key(), option_style)),
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Computes and stores the Greeks of one leg of a strike.
+    ///
+    /// Returns false if the provider has no volatility for the leg.
+    fn refresh_leg(
+        &self,
+        book: &StrikeOrderBook,
+        option_style: OptionStyle,
+        inputs: GreeksInputs,
+        volatilities: &dyn VolatilityProvider,
+    ) -> Result<bool> {
+        let Some(volatility) =
+            volatilities.volatility(&self.expiration, book.strike(), option_style)
+        else {
+            return Ok(false);
+        };
+        let inputs = GreeksInputs {
+            volatility,
+            ..inputs
+        };
+        let greeks = compute_greeks(book.strike(), option_style, &inputs)?;
+        book.set_greeks_snapshot(option_style, GreeksSnapshot::new(greeks, Some(inputs)));
+        Ok(true)
+    }
+
     /// Returns statistics about this option chain.
     #[must_use]
     pub fn stats(&self) -> OptionChainStats {
@@ -386,6 +1131,381 @@ mod tests {
         assert!(chain.atm_strike(50000).is_err());
     }
 
+    #[test]
+    fn test_option_chain_linked_future() {
+        let chain = OptionChainOrderBook::new("ES", test_expiration());
+        drop(chain.get_or_create_strike(4900));
+        drop(chain.get_or_create_strike(5000));
+        drop(chain.get_or_create_strike(5100));
+
+        assert!(chain.future().is_none());
+        assert!(chain.underlying_price().is_none());
+        assert!(chain.current_atm_strike().is_err());
+        assert!(chain.moneyness(5000).is_err());
+
+        let future = chain.link_future(UnderlyingFuture::new("ESH5")).unwrap();
+        future.set_reference_price(5080);
+
+        assert_eq!(chain.future().unwrap().symbol(), "ESH5");
+        assert_eq!(chain.underlying_price(), Some(5080));
+        assert_eq!(chain.current_atm_strike().unwrap(), 5100);
+        assert!((chain.moneyness(5080).unwrap() - 1.0).abs() < 1e-12);
+
+        assert!(chain.link_future(UnderlyingFuture::new("ESM5")).is_err());
+    }
+
+    #[test]
+    fn test_strike_moneyness_reads_chain_price() {
+        use crate::orderbook::{CorporateAction, ReferencePriceKind};
+
+        let chain = OptionChainOrderBook::new("ES", test_expiration());
+        let strike = chain.get_or_create_strike(5000);
+        assert!(strike.underlying_price().is_none());
+        assert!(strike.moneyness().is_err());
+        assert!(strike.is_itm(OptionStyle::Call).is_err());
+
+        chain.reference().update(ReferencePriceKind::Index, 4000);
+        assert_eq!(strike.underlying_price(), Some(4000));
+        assert!((strike.moneyness().unwrap() - 1.25).abs() < 1e-12);
+        assert!(strike.is_otm(OptionStyle::Call).unwrap());
+        assert!(strike.is_itm(OptionStyle::Put).unwrap());
+
+        let future = chain.link_future(UnderlyingFuture::new("ESH5")).unwrap();
+        future.set_reference_price(5500);
+        assert_eq!(strike.underlying_price(), Some(5500));
+        assert!(strike.is_itm(OptionStyle::Call).unwrap());
+        assert!(!strike.is_itm(OptionStyle::Put).unwrap());
+        assert!(!strike.is_otm(OptionStyle::Call).unwrap());
+
+        chain
+            .apply_corporate_action(&CorporateAction::split(2, 1))
+            .unwrap();
+        let adjusted = chain.get_strike(2500).unwrap();
+        assert_eq!(adjusted.underlying_price(), Some(5500));
+    }
+
+    #[test]
+    fn test_option_chain_reference_price() {
+        use crate::orderbook::ReferencePriceKind;
+
+        let chain = OptionChainOrderBook::new("BTC", test_expiration());
+        drop(chain.get_or_create_strike(45000));
+        drop(chain.get_or_create_strike(50000));
+        drop(chain.get_or_create_strike(55000));
+
+        assert!(chain.classify(50000, OptionStyle::Call).is_err());
+
+        chain.reference().update(ReferencePriceKind::Index, 51000);
+
+        assert_eq!(chain.underlying_price(), Some(51000));
+        assert_eq!(chain.current_atm_strike().unwrap(), 50000);
+        assert_eq!(
+            chain.classify(45000, OptionStyle::Call).unwrap(),
+            Moneyness::InTheMoney
+        );
+        assert_eq!(
+            chain.classify(50000, OptionStyle::Put).unwrap(),
+            Moneyness::AtTheMoney
+        );
+        assert_eq!(
+            chain.classify(55000, OptionStyle::Call).unwrap(),
+            Moneyness::OutOfTheMoney
+        );
+        assert_eq!(chain.otm_strikes(OptionStyle::Call).unwrap(), vec![55000]);
+        assert_eq!(chain.otm_strikes(OptionStyle::Put).unwrap(), vec![45000]);
+
+        let stale = OptionChainOrderBook::new("BTC", test_expiration());
+        stale
+            .reference()
+            .update_at(ReferencePriceKind::Index, 51000, 1);
+        assert!(stale.underlying_price().is_none());
+    }
+
+    #[test]
+    fn test_option_chain_bulk_greeks_update() {
+        use crate::orderbook::GreeksInputs;
+        use optionstratlib::greeks::Greek;
+        use rust_decimal::Decimal;
+        use rust_decimal_macros::dec;
+
+        let greek = |delta: Decimal| Greek {
+            delta,
+            gamma: dec!(0.001),
+            theta: Decimal::ZERO,
+            vega: Decimal::ZERO,
+            rho: Decimal::ZERO,
+            rho_d: Decimal::ZERO,
+            alpha: Decimal::ZERO,
+            vanna: Decimal::ZERO,
+            vomma: Decimal::ZERO,
+            veta: Decimal::ZERO,
+            charm: Decimal::ZERO,
+            color: Decimal::ZERO,
+        };
+        let chain = OptionChainOrderBook::new("BTC", test_expiration());
+        let strike = chain.get_or_create_strike(50000);
+        let inputs = GreeksInputs {
+            underlying_price: 50000,
+            volatility: 0.5,
+            ..GreeksInputs::default()
+        };
+
+        let updated = chain
+            .update_greeks([
+                GreeksUpdate::new(50000, OptionStyle::Call, greek(dec!(0.5)), Some(inputs)),
+                GreeksUpdate::new(50000, OptionStyle::Put, greek(dec!(-0.5)), None),
+            ])
+            .unwrap();
+
+        assert_eq!(updated, 2);
+        assert_eq!(strike.call_greeks().unwrap().delta, dec!(0.5));
+        assert_eq!(strike.put_greeks().unwrap().delta, dec!(-0.5));
+        assert_eq!(
+            strike.greeks_snapshot(OptionStyle::Call).unwrap().inputs,
+            Some(inputs)
+        );
+
+        let result = chain.update_greeks([
+            GreeksUpdate::new(50000, OptionStyle::Call, greek(dec!(0.6)), None),
+            GreeksUpdate::new(60000, OptionStyle::Call, greek(dec!(0.2)), None),
+        ]);
+        assert!(result.is_err());
+        assert_eq!(strike.call_greeks().unwrap().delta, dec!(0.5));
+    }
+
+    #[test]
+    fn test_option_chain_implied_volatility() {
+        use crate::orderbook::ReferencePriceKind;
+
+        let chain = OptionChainOrderBook::new("ES", ExpirationDate::Days(pos_or_panic!(91.25)));
+        let strike = chain.get_or_create_strike(5000);
+        strike
+            .call()
+            .add_limit_order(OrderId::new(), Side::Buy, 190, 1)
+            .unwrap();
+        strike
+            .call()
+            .add_limit_order(OrderId::new(), Side::Sell, 210, 1)
+            .unwrap();
+
+        assert!(chain.implied_volatility(5000, OptionStyle::Call).is_err());
+        assert_eq!(chain.pricing_model(), PricingModel::BlackScholes);
+
+        chain.reference().update(ReferencePriceKind::Index, 5000);
+        let spot_iv = chain.implied_volatility(5000, OptionStyle::Call).unwrap();
+        assert!(spot_iv.mid.unwrap() > 0.0);
+        assert!(chain.implied_volatility(5000, OptionStyle::Put).is_err());
+        assert!(chain.implied_volatility(4000, OptionStyle::Call).is_err());
+
+        chain
+            .market()
+            .set_rate_curve(crate::pricing::YieldCurve::flat(0.05));
+        let future = chain.link_future(UnderlyingFuture::new("ESH5")).unwrap();
+        future.set_reference_price(5000);
+        assert_eq!(chain.pricing_model(), PricingModel::Black76);
+        let inputs = chain.greeks_inputs(0.2).unwrap();
+        assert_eq!(inputs.dividend_yield, 0.05);
+        let futures_iv = chain.implied_volatility(5000, OptionStyle::Call).unwrap();
+        assert!(futures_iv.mid.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_option_chain_volatility_smile() {
+        use crate::orderbook::ReferencePriceKind;
+
+        let chain = OptionChainOrderBook::new("SPX", ExpirationDate::Days(pos_or_panic!(91.25)));
+        assert!(chain.volatility_smile().is_err());
+        chain.reference().update(ReferencePriceKind::Index, 100);
+        assert!(chain.volatility_smile().is_err());
+
+        let quote = |strike: u64, option_style: OptionStyle, bid: u128, ask: u128| {
+            let book = chain.get_or_create_strike(strike);
+            let book = book.get(option_style);
+            book.add_limit_order(OrderId::new(), Side::Buy, bid, 1)
+                .unwrap();
+            book.add_limit_order(OrderId::new(), Side::Sell, ask, 1)
+                .unwrap();
+        };
+        quote(90, OptionStyle::Put, 1, 2);
+        quote(100, OptionStyle::Call, 3, 5);
+        quote(100, OptionStyle::Put, 3, 5);
+        quote(110, OptionStyle::Call, 1, 2);
+        drop(chain.get_or_create_strike(120));
+
+        let smile = chain.volatility_smile().unwrap();
+        assert_eq!(smile.len(), 3);
+        assert!((smile.forward - 100.0).abs() < 1e-9);
+        assert_eq!(smile.points[0].otm_style(), OptionStyle::Put);
+        assert!(smile.points[0].call.is_empty());
+
+        let atm = smile.atm_volatility().unwrap();
+        assert!((atm - 0.2).abs() < 0.01);
+        // Unlisted strikes are interpolated between listed ones.
+        let vol_95 = smile.volatility(95.0).unwrap();
+        let (left, right) = (smile.points[0].mid().unwrap(), atm);
+        assert!(vol_95 >= left.min(right) && vol_95 <= left.max(right));
+    }
+
+    #[test]
+    fn test_option_chain_strike_for_delta() {
+        use crate::orderbook::ReferencePriceKind;
+        use crate::pricing::{FlatVolatility, StrikeVolatility};
+
+        let chain = OptionChainOrderBook::new("SPX", ExpirationDate::Days(pos_or_panic!(91.25)));
+        for strike in (80..=120).step_by(5) {
+            drop(chain.get_or_create_strike(strike));
+        }
+        let flat = FlatVolatility(0.2);
+        assert!(chain.strike_for_delta(-0.25, OptionStyle::Put).is_err());
+        assert!(
+            chain
+                .strike_for_delta_with(-0.25, OptionStyle::Put, &flat)
+                .is_err()
+        );
+
+        chain.reference().update(ReferencePriceKind::Index, 100);
+        let computed = chain
+            .strike_for_delta_with(-0.25, OptionStyle::Put, &flat)
+            .unwrap();
+        // N(-d1) = 0.25 at d1 ~ 0.674: K ~ 100 * exp(-0.674 * 0.1 + 0.005) ~ 94.
+        assert_eq!(computed.strike, 95);
+        let fractional = computed.interpolated_strike.unwrap();
+        assert!((fractional - 94.0).abs() < 0.5);
+
+        assert!(chain.strike_for_delta(0.25, OptionStyle::Call).is_err());
+        chain.refresh_greeks(&flat).unwrap();
+        let stored = chain.strike_for_delta(-0.25, OptionStyle::Put).unwrap();
+        assert_eq!(stored, computed);
+        let call = chain.strike_for_delta(0.25, OptionStyle::Call).unwrap();
+        assert!(call.strike > 100);
+        assert!(call.delta > 0.0);
+
+        // Targets beyond the listed deltas return the wing strike only.
+        let wing = chain.strike_for_delta(0.999, OptionStyle::Call).unwrap();
+        assert_eq!(wing.strike, 80);
+        assert!(wing.interpolated_strike.is_none());
+
+        // A strike that cannot be priced fails the search instead of being
+        // silently dropped.
+        let mut vols = StrikeVolatility::new();
+        vols.insert(chain.expiration, 95, 0.0);
+        let vols = vols.with_fallback(0.2);
+        assert!(
+            chain
+                .strike_for_delta_with(-0.25, OptionStyle::Put, &vols)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_option_chain_parity() {
+        use crate::orderbook::ReferencePriceKind;
+        use crate::pricing::ParityArbitrage;
+
+        let chain = OptionChainOrderBook::new("SPX", ExpirationDate::Days(pos_or_panic!(182.5)));
+        let quote = |strike: u64, option_style: OptionStyle, bid: u128, ask: u128, size: u64| {
+            let book = chain.get_or_create_strike(strike);
+            let book = book.get(option_style);
+            book.add_limit_order(OrderId::new(), Side::Buy, bid, size)
+                .unwrap();
+            book.add_limit_order(OrderId::new(), Side::Sell, ask, size)
+                .unwrap();
+        };
+        quote(90, OptionStyle::Call, 10, 11, 5);
+        quote(90, OptionStyle::Put, 0, 1, 5);
+        quote(100, OptionStyle::Call, 3, 4, 5);
+        quote(100, OptionStyle::Put, 3, 4, 5);
+        quote(110, OptionStyle::Call, 0, 1, 5);
+        quote(110, OptionStyle::Put, 15, 16, 2);
+
+        assert!(chain.parity_report().is_err());
+        chain.reference().update(ReferencePriceKind::Index, 100);
+        assert!(chain.strike_parity(120).is_err());
+
+        let atm = chain.strike_parity(100).unwrap();
+        assert!((atm.forward.unwrap() - 100.0).abs() < 1e-9);
+        assert!(atm.violation.is_none());
+        assert!(atm.implied_borrow.unwrap().abs() < 1e-9);
+
+        // The 110 put bid of 15 sells the synthetic forward at ~ 96.
+        let deep = chain.strike_parity(110).unwrap();
+        let violation = deep.violation.unwrap();
+        assert_eq!(violation.arbitrage, ParityArbitrage::Reversal);
+        assert_eq!(violation.quantity, 2);
+        assert!((violation.edge - 4.0).abs() < 1e-9);
+
+        let report = chain.parity_report().unwrap();
+        assert_eq!(report.strikes.len(), 3);
+        assert_eq!(report.violations().count(), 1);
+        let forward = chain.implied_forward().unwrap();
+        assert!((forward.forward - 100.0).abs() < 1e-9);
+        assert_eq!(forward.strike_count, 3);
+
+        let empty = OptionChainOrderBook::new("SPX", test_expiration());
+        empty.reference().update(ReferencePriceKind::Index, 100);
+        drop(empty.get_or_create_strike(100));
+        assert!(empty.implied_forward().is_err());
+    }
+
+    #[test]
+    fn test_option_chain_edge_report() {
+        use crate::orderbook::ReferencePriceKind;
+        use crate::pricing::{FlatVolatility, StrikeVolatility};
+
+        let chain = OptionChainOrderBook::new("SPX", ExpirationDate::Days(pos_or_panic!(91.25)));
+        let strike = chain.get_or_create_strike(100);
+        strike
+            .call()
+            .add_limit_order(OrderId::new(), Side::Buy, 3, 1)
+            .unwrap();
+        strike
+            .call()
+            .add_limit_order(OrderId::new(), Side::Sell, 5, 1)
+            .unwrap();
+        drop(chain.get_or_create_strike(110));
+
+        let flat = FlatVolatility(0.2);
+        assert!(chain.edge_report(&flat, 1).is_err());
+        chain.reference().update(ReferencePriceKind::Index, 100);
+
+        let theo = chain
+            .theoretical_value(100, OptionStyle::Call, &flat)
+            .unwrap();
+        assert!((theo - 3.99).abs() < 0.01);
+        let empty = StrikeVolatility::new();
+        assert!(
+            chain
+                .theoretical_value(100, OptionStyle::Call, &empty)
+                .is_err()
+        );
+        assert!(chain.strike_edge(120, &flat, 1).is_err());
+
+        let edge = chain.strike_edge(100, &flat, 1).unwrap();
+        let call = edge.call.unwrap();
+        assert!((call.mid_edge.unwrap().price - (4.0 - theo)).abs() < 1e-9);
+        assert!(edge.put.unwrap().mid_edge.is_none());
+
+        let report = chain.edge_report(&flat, 1).unwrap();
+        assert_eq!(report.strikes.len(), 2);
+        assert_eq!(report.contract_count, 4);
+        assert_eq!(report.quoted_count, 1);
+        assert!(report.mean_mid_edge_vol_points.unwrap().abs() < 1.0);
+        assert!(report.skipped.is_empty());
+
+        // A strike that cannot be priced is reported, not the whole chain.
+        let mut vols = StrikeVolatility::new();
+        vols.insert(chain.expiration, 110, 0.0);
+        let vols = vols.with_fallback(0.2);
+        let report = chain.edge_report(&vols, 1).unwrap();
+        assert_eq!(report.strikes.len(), 1);
+        assert_eq!(report.strikes[0].strike, 100);
+        assert_eq!(report.skipped, vec![110]);
+
+        let report = chain.edge_report(&empty, 1).unwrap();
+        assert_eq!(report.contract_count, 0);
+        assert!(report.mean_mid_edge_ticks.is_none());
+    }
+
     #[test]
     fn test_option_chain_stats_display() {
         let chain = OptionChainOrderBook::new("BTC", test_expiration());
@@ -465,4 +1585,66 @@ mod tests {
 
         assert_eq!(manager.total_order_count(), 1);
     }
+
+    #[test]
+    fn test_apply_mass_quote() {
+        use crate::orderbook::{MassQuote, MassQuoteStatus, QuoteLevel};
+
+        let chain = OptionChainOrderBook::new("BTC", test_expiration());
+        chain.get_or_create_strike(100);
+        chain.get_or_create_strike(110);
+        let level = QuoteLevel::new;
+        let expiration = test_expiration();
+
+        let quote = MassQuote::new("MM1")
+            .with_entry(
+                expiration,
+                100,
+                OptionStyle::Call,
+                Some(level(50, 10)),
+                Some(level(55, 10)),
+            )
+            .with_entry(expiration, 110, OptionStyle::Put, Some(level(60, 5)), None)
+            .with_entry(expiration, 120, OptionStyle::Call, Some(level(1, 1)), None)
+            .with_entry(
+                expiration,
+                110,
+                OptionStyle::Call,
+                Some(level(9, 1)),
+                Some(level(8, 1)),
+            );
+        let report = chain.apply_mass_quote(&quote);
+        assert_eq!(report.accepted_count(), 2);
+        assert_eq!(report.rejected_count(), 2);
+        assert!(!report.acks[2].is_accepted());
+        assert_eq!(chain.total_order_count(), 3);
+
+        // Requoting only changes what moved; unlisted contracts are untouched.
+        let quote = MassQuote::new("MM1").with_entry(
+            expiration,
+            100,
+            OptionStyle::Call,
+            Some(level(50, 10)),
+            None,
+        );
+        let report = chain.apply_mass_quote(&quote);
+        assert_eq!(
+            report.acks[0].status,
+            MassQuoteStatus::Accepted {
+                bid: SideAction::Unchanged,
+                ask: SideAction::Cancelled
+            }
+        );
+        assert!(
+            chain
+                .get_strike(110)
+                .unwrap()
+                .put()
+                .participant_quote("MM1")
+                .is_some()
+        );
+
+        assert_eq!(chain.cancel_participant_quotes("MM1").unwrap(), 2);
+        assert_eq!(chain.total_order_count(), 0);
+    }
 }
@@ -0,0 +1,71 @@
+//! Contract terms module.
+//!
+//! This module provides the [`ContractTerms`] type describing the contract
+//! specification shared by the call/put pair listed at a strike.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Contract specification for the options listed at a strike.
+///
+/// Standard listings use a multiplier of one and no symbol suffix. Corporate
+/// actions produce adjusted contracts whose multiplier, symbol suffix and
+/// adjustment counter differ from the standard terms.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractTerms {
+    /// Number of units of the underlying delivered per contract.
+    pub multiplier: Decimal,
+    /// Suffix appended to the underlying symbol in contract symbols (e.g., "1").
+    pub symbol_suffix: String,
+    /// Number of corporate action adjustments applied to these terms.
+    pub adjustments: u32,
+}
+
+impl ContractTerms {
+    /// Creates standard contract terms with the given multiplier.
+    ///
+    /// # Arguments
+    ///
+    /// * `multiplier` - Number of units of the underlying per contract
+    #[must_use]
+    pub fn new(multiplier: Decimal) -> Self {
+        Self {
+            multiplier,
+            symbol_suffix: String::new(),
+            adjustments: 0,
+        }
+    }
+
+    /// Returns true if these terms were produced by a corporate action.
+    #[must_use]
+    pub const fn is_adjusted(&self) -> bool {
+        self.adjustments > 0
+    }
+}
+
+impl Default for ContractTerms {
+    fn default() -> Self {
+        Self::new(Decimal::ONE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_default_terms() {
+        let terms = ContractTerms::default();
+        assert_eq!(terms.multiplier, Decimal::ONE);
+        assert!(terms.symbol_suffix.is_empty());
+        assert!(!terms.is_adjusted());
+    }
+
+    #[test]
+    fn test_terms_with_multiplier() {
+        let terms = ContractTerms::new(dec!(100));
+        assert_eq!(terms.multiplier, dec!(100));
+        assert_eq!(terms.adjustments, 0);
+    }
+}
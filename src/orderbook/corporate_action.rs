@@ -0,0 +1,321 @@
+//! Corporate action module.
+//!
+//! This module provides the [`CorporateAction`] type used to adjust listed
+//! contracts when the underlying splits or pays a special dividend, together
+//! with the [`OrderAdjustmentPolicy`] applied to resting orders and the
+//! [`CorporateActionReport`] summarizing the adjustment.
+
+use super::contract::ContractTerms;
+use crate::error::{Error, Result};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// Kind of corporate action affecting the underlying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CorporateActionKind {
+    /// Stock split where `denominator` old units become `numerator` new units.
+    ///
+    /// Strikes and option prices are scaled by `denominator / numerator` and
+    /// the contract multiplier by `numerator / denominator`, so the notional
+    /// of each contract is unchanged.
+    Split {
+        /// New units received.
+        numerator: u32,
+        /// Old units surrendered.
+        denominator: u32,
+    },
+    /// Special cash dividend, in the same units as strike prices.
+    ///
+    /// Strikes are reduced by the dividend amount; multipliers and option
+    /// prices are unchanged.
+    SpecialDividend {
+        /// Dividend amount per unit of the underlying.
+        amount: u64,
+    },
+}
+
+/// How resting orders are treated when their contract is adjusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OrderAdjustmentPolicy {
+    /// Cancel every resting order on adjusted contracts.
+    #[default]
+    Cancel,
+    /// Move resting orders to the adjusted contract, re-pricing them with the
+    /// action's price factor and keeping their order type. Orders whose
+    /// adjusted price rounds to zero, or whose type has no fixed price, are
+    /// cancelled.
+    Preserve,
+}
+
+/// A corporate action to apply to every listed contract of an underlying.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorporateAction {
+    /// The kind of corporate action.
+    kind: CorporateActionKind,
+    /// Suffix appended to the underlying symbol of adjusted contracts.
+    symbol_suffix: String,
+    /// Policy applied to resting orders.
+    order_policy: OrderAdjustmentPolicy,
+}
+
+impl CorporateAction {
+    /// Creates a split where `denominator` old units become `numerator` new units.
+    ///
+    /// # Arguments
+    ///
+    /// * `numerator` - New units received (e.g., 3 in a 3-for-2 split)
+    /// * `denominator` - Old units surrendered (e.g., 2 in a 3-for-2 split)
+    #[must_use]
+    pub fn split(numerator: u32, denominator: u32) -> Self {
+        Self {
+            kind: CorporateActionKind::Split {
+                numerator,
+                denominator,
+            },
+            symbol_suffix: String::new(),
+            order_policy: OrderAdjustmentPolicy::default(),
+        }
+    }
+
+    /// Creates a special cash dividend.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - Dividend per unit of the underlying, in strike units
+    #[must_use]
+    pub fn special_dividend(amount: u64) -> Self {
+        Self {
+            kind: CorporateActionKind::SpecialDividend { amount },
+            symbol_suffix: String::new(),
+            order_policy: OrderAdjustmentPolicy::default(),
+        }
+    }
+
+    /// Sets the suffix appended to the underlying symbol of adjusted contracts.
+    #[must_use]
+    pub fn with_symbol_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.symbol_suffix = suffix.into();
+        self
+    }
+
+    /// Sets the policy applied to resting orders.
+    #[must_use]
+    pub const fn with_order_policy(mut self, policy: OrderAdjustmentPolicy) -> Self {
+        self.order_policy = policy;
+        self
+    }
+
+    /// Returns the kind of corporate action.
+    #[must_use]
+    pub const fn kind(&self) -> CorporateActionKind {
+        self.kind
+    }
+
+    /// Returns the suffix appended to adjusted contract symbols.
+    #[must_use]
+    pub fn symbol_suffix(&self) -> &str {
+        &self.symbol_suffix
+    }
+
+    /// Returns the policy applied to resting orders.
+    #[must_use]
+    pub const fn order_policy(&self) -> OrderAdjustmentPolicy {
+        self.order_policy
+    }
+
+    /// Validates the action parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if a split ratio is zero or a
+    /// dividend amount is zero.
+    pub fn validate(&self) -> Result<()> {
+        match self.kind {
+            CorporateActionKind::Split {
+                numerator,
+                denominator,
+            } if numerator == 0 || denominator == 0 => {
+                Err(Error::validation("split ratio must be non-zero"))
+            }
+            CorporateActionKind::SpecialDividend { amount: 0 } => {
+                Err(Error::validation("dividend amount must be non-zero"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the adjusted strike for an existing strike.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the adjusted strike is not positive.
+    pub fn adjust_strike(&self, strike: u64) -> Result<u64> {
+        let adjusted = match self.kind {
+            CorporateActionKind::Split {
+                numerator,
+                denominator,
+            } => round_to_u64(
+                Decimal::from(strike) * Decimal::from(denominator) / Decimal::from(numerator),
+            ),
+            CorporateActionKind::SpecialDividend { amount } => strike.checked_sub(amount),
+        };
+        match adjusted {
+            Some(value) if value > 0 => Ok(value),
+            _ => Err(Error::validation(format!(
+                "strike {strike} cannot be adjusted to a positive value"
+            ))),
+        }
+    }
+
+    /// Returns the adjusted price of a resting order, or `None` if the
+    /// adjusted price rounds to zero.
+    #[must_use]
+    pub fn adjust_price(&self, price: u128) -> Option<u128> {
+        match self.kind {
+            CorporateActionKind::Split {
+                numerator,
+                denominator,
+            } => {
+                let scaled = (price * u128::from(denominator) + u128::from(numerator) / 2)
+                    / u128::from(numerator);
+                (scaled > 0).then_some(scaled)
+            }
+            CorporateActionKind::SpecialDividend { .. } => Some(price),
+        }
+    }
+
+    /// Returns the adjusted contract terms.
+    #[must_use]
+    pub fn adjust_terms(&self, terms: &ContractTerms) -> ContractTerms {
+        let multiplier = match self.kind {
+            CorporateActionKind::Split {
+                numerator,
+                denominator,
+            } => terms.multiplier * Decimal::from(numerator) / Decimal::from(denominator),
+            CorporateActionKind::SpecialDividend { .. } => terms.multiplier,
+        };
+        ContractTerms {
+            multiplier,
+            symbol_suffix: format!("{}{}", terms.symbol_suffix, self.symbol_suffix),
+            adjustments: terms.adjustments + 1,
+        }
+    }
+}
+
+/// Rounds a non-negative decimal to the nearest `u64`.
+fn round_to_u64(value: Decimal) -> Option<u64> {
+    value.round().to_u64()
+}
+
+/// Summary of a corporate action applied to an underlying.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorporateActionReport {
+    /// Number of expirations adjusted.
+    pub expirations_adjusted: usize,
+    /// Number of strikes re-keyed.
+    pub strikes_adjusted: usize,
+    /// Number of resting orders moved to adjusted contracts.
+    pub orders_migrated: usize,
+    /// Number of resting orders cancelled.
+    pub orders_cancelled: usize,
+    /// Mapping of original strike to adjusted strike, per adjusted strike.
+    pub strike_map: Vec<(u64, u64)>,
+}
+
+impl CorporateActionReport {
+    /// Merges another report into this one.
+    pub fn merge(&mut self, other: Self) {
+        self.expirations_adjusted += other.expirations_adjusted;
+        self.strikes_adjusted += other.strikes_adjusted;
+        self.orders_migrated += other.orders_migrated;
+        self.orders_cancelled += other.orders_cancelled;
+        self.strike_map.extend(other.strike_map);
+    }
+}
+
+impl std::fmt::Display for CorporateActionReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} expirations, {} strikes adjusted, {} orders migrated, {} orders cancelled",
+            self.expirations_adjusted,
+            self.strikes_adjusted,
+            self.orders_migrated,
+            self.orders_cancelled
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_split_adjusts_strike_price_and_terms() {
+        let action = CorporateAction::split(2, 1).with_symbol_suffix("1");
+
+        assert_eq!(action.adjust_strike(50000).unwrap(), 25000);
+        assert_eq!(action.adjust_price(101), Some(51));
+
+        let terms = action.adjust_terms(&ContractTerms::new(dec!(100)));
+        assert_eq!(terms.multiplier, dec!(200));
+        assert_eq!(terms.symbol_suffix, "1");
+        assert!(terms.is_adjusted());
+    }
+
+    #[test]
+    fn test_uneven_split_rounds_strike() {
+        let action = CorporateAction::split(3, 2);
+        assert_eq!(action.adjust_strike(100).unwrap(), 67);
+
+        let terms = action.adjust_terms(&ContractTerms::new(dec!(100)));
+        assert_eq!(terms.multiplier, dec!(150));
+    }
+
+    #[test]
+    fn test_special_dividend_reduces_strike() {
+        let action = CorporateAction::special_dividend(500);
+
+        assert_eq!(action.adjust_strike(50000).unwrap(), 49500);
+        assert_eq!(action.adjust_price(120), Some(120));
+        assert!(action.adjust_strike(500).is_err());
+
+        let terms = action.adjust_terms(&ContractTerms::default());
+        assert_eq!(terms.multiplier, Decimal::ONE);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(CorporateAction::split(2, 1).validate().is_ok());
+        assert!(CorporateAction::split(0, 1).validate().is_err());
+        assert!(CorporateAction::special_dividend(0).validate().is_err());
+    }
+
+    #[test]
+    fn test_adjust_price_to_zero() {
+        let action = CorporateAction::split(10, 1);
+        assert_eq!(action.adjust_price(4), None);
+    }
+
+    #[test]
+    fn test_report_merge() {
+        let mut report = CorporateActionReport {
+            strikes_adjusted: 1,
+            strike_map: vec![(100, 50)],
+            ..Default::default()
+        };
+        report.merge(CorporateActionReport {
+            strikes_adjusted: 2,
+            orders_cancelled: 3,
+            strike_map: vec![(200, 100)],
+            ..Default::default()
+        });
+
+        assert_eq!(report.strikes_adjusted, 3);
+        assert_eq!(report.orders_cancelled, 3);
+        assert_eq!(report.strike_map.len(), 2);
+        assert!(report.to_string().contains("3 strikes adjusted"));
+    }
+}
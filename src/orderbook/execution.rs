@@ -0,0 +1,199 @@
+//! Execution log module.
+//!
+//! This module records the matches of each contract book as
+//! [`Execution`]s, so that fills can be consumed downstream (e.g. by a
+//! position keeper) without hooking into the matching engine.
+//!
+//! Each book keeps its last [`EXECUTION_LOG_CAPACITY`] executions, numbered
+//! by a sequence. Consumers read with their own cursor instead of draining,
+//! so several of them can follow the same book.
+
+use orderbook_rs::{OrderId, Side, TradeResult};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+
+/// Number of executions kept per contract book.
+pub const EXECUTION_LOG_CAPACITY: usize = 4096;
+
+/// A match between an incoming order and a resting order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Execution {
+    /// Position of the execution in its book's log, starting at zero.
+    pub sequence: u64,
+    /// The incoming order.
+    pub taker_order_id: OrderId,
+    /// The resting order.
+    pub maker_order_id: OrderId,
+    /// Execution price in smallest units.
+    pub price: u128,
+    /// Executed quantity.
+    pub quantity: u64,
+    /// Side of the incoming order.
+    pub taker_side: Side,
+    /// Timestamp of the match in milliseconds.
+    pub timestamp_ms: u64,
+}
+
+impl Execution {
+    /// Returns the side of the resting order.
+    #[must_use]
+    pub fn maker_side(&self) -> Side {
+        match self.taker_side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        }
+    }
+
+    /// Returns the side traded by an order of this execution, or `None` if
+    /// the order is not part of it.
+    #[must_use]
+    pub fn side_of(&self, order_id: OrderId) -> Option<Side> {
+        if order_id == self.taker_order_id {
+            Some(self.taker_side)
+        } else if order_id == self.maker_order_id {
+            Some(self.maker_side())
+        } else {
+            None
+        }
+    }
+}
+
+/// Bounded log of the executions of a single contract book.
+#[derive(Debug)]
+pub(crate) struct ExecutionLog {
+    state: Mutex<LogState>,
+    capacity: usize,
+}
+
+/// Retained executions and the sequence of the next one.
+#[derive(Debug, Default)]
+struct LogState {
+    executions: VecDeque<Execution>,
+    next_sequence: u64,
+}
+
+impl Default for ExecutionLog {
+    fn default() -> Self {
+        Self::with_capacity(EXECUTION_LOG_CAPACITY)
+    }
+}
+
+impl ExecutionLog {
+    /// Creates a log keeping at most `capacity` executions.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(LogState::default()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Appends the matches of a trade, dropping the oldest executions
+    /// beyond the capacity.
+    pub(crate) fn record(&self, trade: &TradeResult) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        for t in trade.match_result.transactions.as_vec() {
+            let sequence = state.next_sequence;
+            state.next_sequence += 1;
+            state.executions.push_back(Execution {
+                sequence,
+                taker_order_id: t.taker_order_id,
+                maker_order_id: t.maker_order_id,
+                price: t.price,
+                quantity: t.quantity,
+                taker_side: t.taker_side,
+                timestamp_ms: t.timestamp,
+            });
+            if state.executions.len() > self.capacity {
+                state.executions.pop_front();
+            }
+        }
+    }
+
+    /// Returns the retained executions with a sequence at or above
+    /// `sequence`, oldest first.
+    pub(crate) fn since(&self, sequence: u64) -> Vec<Execution> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let skip = state
+            .executions
+            .front()
+            .map_or(0, |first| sequence.saturating_sub(first.sequence));
+        state
+            .executions
+            .iter()
+            .skip(usize::try_from(skip).unwrap_or(usize::MAX))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the sequence the next execution will get.
+    pub(crate) fn next_sequence(&self) -> u64 {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .next_sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_sides() {
+        let taker = OrderId::new();
+        let maker = OrderId::new();
+        let execution = Execution {
+            sequence: 0,
+            taker_order_id: taker,
+            maker_order_id: maker,
+            price: 10,
+            quantity: 2,
+            taker_side: Side::Sell,
+            timestamp_ms: 0,
+        };
+        assert_eq!(execution.maker_side(), Side::Buy);
+        assert_eq!(execution.side_of(taker), Some(Side::Sell));
+        assert_eq!(execution.side_of(maker), Some(Side::Buy));
+        assert_eq!(execution.side_of(OrderId::new()), None);
+    }
+
+    #[test]
+    fn test_log_is_bounded_and_read_by_sequence() {
+        use orderbook_rs::DefaultOrderBook;
+        use std::sync::Arc;
+
+        let log = Arc::new(ExecutionLog::with_capacity(3));
+        let recorder = Arc::clone(&log);
+        let book = DefaultOrderBook::with_trade_listener(
+            "TEST",
+            Arc::new(move |trade: &TradeResult| recorder.record(trade)),
+        );
+        for _ in 0..5 {
+            book.add_limit_order(
+                OrderId::new(),
+                10,
+                1,
+                Side::Sell,
+                orderbook_rs::TimeInForce::Gtc,
+                None,
+            )
+            .unwrap();
+            book.submit_market_order(OrderId::new(), 1, Side::Buy)
+                .unwrap();
+        }
+
+        assert_eq!(log.next_sequence(), 5);
+        let sequences = |from| {
+            log.since(from)
+                .iter()
+                .map(|e| e.sequence)
+                .collect::<Vec<_>>()
+        };
+        // The two oldest were dropped; reading does not consume.
+        assert_eq!(sequences(0), vec![2, 3, 4]);
+        assert_eq!(sequences(0), vec![2, 3, 4]);
+        assert_eq!(sequences(4), vec![4]);
+        assert!(sequences(5).is_empty());
+    }
+}
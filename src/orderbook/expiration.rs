@@ -3,9 +3,19 @@
 //! This module provides the [`ExpirationOrderBook`] and [`ExpirationOrderBookManager`]
 //! for managing all expirations for a single underlying asset.
 
+use super::activity::ActivitySummary;
 use super::chain::OptionChainOrderBook;
+use super::corporate_action::{CorporateAction, CorporateActionReport};
+use super::exposure::{AggregatedGreeks, ExposureWeights};
+use super::future::UnderlyingFuture;
+use super::greeks::GreeksRefreshReport;
+use super::market_data::MarketData;
 use super::strike::StrikeOrderBook;
+use crate::analytics::{
+    GammaExposureSummary, STANDARD_WING_DELTA, TermStructure, VolatilityMetrics,
+};
 use crate::error::{Error, Result};
+use crate::pricing::{VolatilityProvider, VolatilitySmile};
 use crossbeam_skiplist::SkipMap;
 use optionstratlib::ExpirationDate;
 use orderbook_rs::OrderId;
@@ -43,10 +53,24 @@ impl ExpirationOrderBook {
     /// * `expiration` - The expiration date
     #[must_use]
     pub fn new(underlying: impl Into<String>, expiration: ExpirationDate) -> Self {
+        Self::with_market(underlying, expiration, Arc::new(MarketData::new()))
+    }
+
+    /// Creates a new expiration order book sharing the market data of its
+    /// underlying.
+    pub(crate) fn with_market(
+        underlying: impl Into<String>,
+        expiration: ExpirationDate,
+        market: Arc<MarketData>,
+    ) -> Self {
         let underlying = underlying.into();
 
         Self {
-            chain: Arc::new(OptionChainOrderBook::new(&underlying, expiration)),
+            chain: Arc::new(OptionChainOrderBook::with_market(
+                &underlying,
+                expiration,
+                market,
+            )),
             underlying,
             expiration,
             id: OrderId::new(),
@@ -120,6 +144,18 @@ impl ExpirationOrderBook {
         self.chain.total_order_count()
     }
 
+    /// Returns the session activity and open interest of every contract.
+    #[must_use]
+    pub fn activity_summary(&self) -> ActivitySummary {
+        self.chain.activity_summary()
+    }
+
+    /// Starts a new trading session for every contract and returns the
+    /// activity of the closed one.
+    pub fn roll_activity(&self) -> ActivitySummary {
+        self.chain.roll_activity()
+    }
+
     /// Returns the ATM strike closest to the given spot price.
     ///
     /// # Errors
@@ -128,6 +164,81 @@ impl ExpirationOrderBook {
     pub fn atm_strike(&self, spot: u64) -> Result<u64> {
         self.chain.atm_strike(spot)
     }
+
+    /// Links this expiration to the futures contract its options deliver into.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if a future is already linked.
+    pub fn link_future(&self, future: UnderlyingFuture) -> Result<Arc<UnderlyingFuture>> {
+        self.chain.link_future(future)
+    }
+
+    /// Returns the linked futures contract, if any.
+    #[must_use]
+    pub fn future(&self) -> Option<Arc<UnderlyingFuture>> {
+        self.chain.future()
+    }
+
+    /// Returns the ATM strike closest to the current underlying price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available,
+    /// or `Error::NoDataAvailable` if there are no strikes.
+    pub fn current_atm_strike(&self) -> Result<u64> {
+        self.chain.current_atm_strike()
+    }
+
+    /// Applies a corporate action to every strike of this expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the action cannot be applied to
+    /// the listed strikes. No strike is modified on error.
+    pub fn apply_corporate_action(
+        &self,
+        action: &CorporateAction,
+    ) -> Result<CorporateActionReport> {
+        let mut report = self.chain.apply_corporate_action(action)?;
+        report.expirations_adjusted = 1;
+        Ok(report)
+    }
+
+    /// Computes and stores the Greeks of every contract of this expiration.
+    ///
+    /// See [`OptionChainOrderBook::refresh_greeks`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no underlying price is available.
+    pub fn refresh_greeks(
+        &self,
+        volatilities: &dyn VolatilityProvider,
+    ) -> Result<GreeksRefreshReport> {
+        self.chain.refresh_greeks(volatilities)
+    }
+
+    /// Returns the ATM volatility, 25-delta risk reversal and 25-delta
+    /// butterfly of the live chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no smile can be built from the quotes (see
+    /// [`OptionChainOrderBook::volatility_smile`]) or a wing cannot be
+    /// reached.
+    pub fn volatility_metrics(&self) -> Result<VolatilityMetrics> {
+        VolatilityMetrics::from_smile(&self.chain.volatility_smile()?, STANDARD_WING_DELTA)
+    }
+
+    /// Returns the Greeks of every contract of this expiration aggregated
+    /// with the given weights.
+    ///
+    /// See [`OptionChainOrderBook::aggregate_greeks`].
+    #[must_use]
+    pub fn aggregate_greeks(&self, weights: &ExposureWeights<'_>) -> AggregatedGreeks {
+        self.chain.aggregate_greeks(weights)
+    }
 }
 
 /// Manages expiration order books for a single underlying.
@@ -139,6 +250,8 @@ pub struct ExpirationOrderBookManager {
     expirations: SkipMap<ExpirationDate, Arc<ExpirationOrderBook>>,
     /// The underlying asset symbol.
     underlying: String,
+    /// Market data shared with every expiration.
+    market: Arc<MarketData>,
 }
 
 impl ExpirationOrderBookManager {
@@ -149,9 +262,16 @@ impl ExpirationOrderBookManager {
     /// * `underlying` - The underlying asset symbol
     #[must_use]
     pub fn new(underlying: impl Into<String>) -> Self {
+        Self::with_market(underlying, Arc::new(MarketData::new()))
+    }
+
+    /// Creates a new expiration order book manager sharing the market data of
+    /// its underlying.
+    pub(crate) fn with_market(underlying: impl Into<String>, market: Arc<MarketData>) -> Self {
         Self {
             expirations: SkipMap::new(),
             underlying: underlying.into(),
+            market,
         }
     }
 
@@ -178,7 +298,11 @@ impl ExpirationOrderBookManager {
         if let Some(entry) = self.expirations.get(&expiration) {
             return Arc::clone(entry.value());
         }
-        let book = Arc::new(ExpirationOrderBook::new(&self.underlying, expiration));
+        let book = Arc::new(ExpirationOrderBook::with_market(
+            &self.underlying,
+            expiration,
+            Arc::clone(&self.market),
+        ));
         self.expirations.insert(expiration, Arc::clone(&book));
         book
     }
@@ -223,6 +347,29 @@ impl ExpirationOrderBookManager {
             .sum()
     }
 
+    /// Returns the session activity and open interest across all
+    /// expirations.
+    #[must_use]
+    pub fn activity_summary(&self) -> ActivitySummary {
+        self.expirations
+            .iter()
+            .fold(ActivitySummary::default(), |mut summary, entry| {
+                summary.merge(&entry.value().activity_summary());
+                summary
+            })
+    }
+
+    /// Starts a new trading session for every expiration and returns the
+    /// activity of the closed one.
+    pub fn roll_activity(&self) -> ActivitySummary {
+        self.expirations
+            .iter()
+            .fold(ActivitySummary::default(), |mut summary, entry| {
+                summary.merge(&entry.value().roll_activity());
+                summary
+            })
+    }
+
     /// Returns the total strike count across all expirations.
     #[must_use]
     pub fn total_strike_count(&self) -> usize {
@@ -232,6 +379,109 @@ impl ExpirationOrderBookManager {
             .sum()
     }
 
+    /// Applies a corporate action to every expiration and strike.
+    ///
+    /// All expirations are validated before any strike is re-keyed, so an
+    /// invalid action leaves the whole tree untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the action cannot be applied to
+    /// the strikes of any expiration.
+    pub fn apply_corporate_action(
+        &self,
+        action: &CorporateAction,
+    ) -> Result<CorporateActionReport> {
+        for entry in self.expirations.iter() {
+            entry
+                .value()
+                .chain()
+                .strikes()
+                .plan_corporate_action(action)?;
+        }
+
+        let mut report = CorporateActionReport::default();
+        for entry in self.expirations.iter() {
+            report.merge(entry.value().apply_corporate_action(action)?);
+        }
+        Ok(report)
+    }
+
+    /// Computes and stores the Greeks of every contract of every expiration.
+    ///
+    /// Contracts left unchanged are listed in the report's `skipped`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered; expirations refreshed before
+    /// the error keep their new Greeks.
+    pub fn refresh_greeks(
+        &self,
+        volatilities: &dyn VolatilityProvider,
+    ) -> Result<GreeksRefreshReport> {
+        let mut report = GreeksRefreshReport::default();
+        for entry in self.expirations.iter() {
+            report.merge(entry.value().refresh_greeks(volatilities)?);
+        }
+        Ok(report)
+    }
+
+    /// Returns the Greeks of every contract across all expirations
+    /// aggregated with the given weights.
+    #[must_use]
+    pub fn aggregate_greeks(&self, weights: &ExposureWeights<'_>) -> AggregatedGreeks {
+        self.expirations
+            .iter()
+            .fold(AggregatedGreeks::default(), |mut aggregate, entry| {
+                aggregate.merge(&entry.value().aggregate_greeks(weights));
+                aggregate
+            })
+    }
+
+    /// Returns the dealer gamma exposure of every expiration, with the
+    /// zero-gamma level of all expirations combined at the reference price.
+    ///
+    /// Expirations without an underlying price are listed as skipped.
+    #[must_use]
+    pub fn gamma_exposure(&self) -> GammaExposureSummary {
+        let expirations: Vec<Arc<ExpirationOrderBook>> = self
+            .expirations
+            .iter()
+            .map(|entry| Arc::clone(entry.value()))
+            .collect();
+        let chains: Vec<&OptionChainOrderBook> = expirations.iter().map(|e| e.chain()).collect();
+        GammaExposureSummary::from_chains(
+            &self.underlying,
+            self.market.reference().price(),
+            &chains,
+        )
+    }
+
+    /// Returns the volatility metrics of every expiration, sorted by time
+    /// to expiry; expirations without metrics are listed as skipped.
+    #[must_use]
+    pub fn term_structure(&self) -> TermStructure {
+        let mut metrics = Vec::new();
+        let mut skipped = Vec::new();
+        for entry in self.expirations.iter() {
+            match entry.value().volatility_metrics() {
+                Ok(m) => metrics.push(m),
+                Err(_) => skipped.push(*entry.key()),
+            }
+        }
+        TermStructure::new(&self.underlying, metrics, skipped)
+    }
+
+    /// Returns the volatility smiles of every expiration whose quotes can be
+    /// inverted, in expiration order.
+    #[must_use]
+    pub fn volatility_smiles(&self) -> Vec<VolatilitySmile> {
+        self.expirations
+            .iter()
+            .filter_map(|entry| entry.value().chain().volatility_smile().ok())
+            .collect()
+    }
+
     /// Returns statistics about this expiration manager.
     #[must_use]
     pub fn stats(&self) -> ExpirationManagerStats {
@@ -371,6 +621,22 @@ mod tests {
         assert!(book.atm_strike(50000).is_err());
     }
 
+    #[test]
+    fn test_expiration_order_book_linked_future() {
+        let book = ExpirationOrderBook::new("ES", test_expiration());
+        drop(book.get_or_create_strike(4900));
+        drop(book.get_or_create_strike(5000));
+
+        let future = book
+            .link_future(UnderlyingFuture::with_book("ESH5"))
+            .unwrap();
+        future.set_reference_price(4940);
+
+        assert_eq!(book.future().unwrap().symbol(), "ESH5");
+        assert!(book.future().unwrap().book().is_some());
+        assert_eq!(book.current_atm_strike().unwrap(), 4900);
+    }
+
     #[test]
     fn test_expiration_manager_get() {
         let manager = ExpirationOrderBookManager::new("BTC");
@@ -460,4 +726,53 @@ mod tests {
         let display = format!("{}", stats);
         assert!(display.contains("BTC"));
     }
+
+    #[test]
+    fn test_expiration_manager_gamma_exposure() {
+        use crate::orderbook::ReferencePriceKind;
+        use crate::pricing::compute_greeks;
+        use optionstratlib::OptionStyle;
+
+        let manager = ExpirationOrderBookManager::new("SPX");
+        for days in [7.0, 30.0] {
+            let exp = manager.get_or_create(ExpirationDate::Days(pos_or_panic!(days)));
+            exp.get_or_create_strike(4_900)
+                .put()
+                .set_open_interest(1_000);
+            exp.get_or_create_strike(5_100)
+                .call()
+                .set_open_interest(1_000);
+        }
+        let summary = manager.gamma_exposure();
+        assert!(summary.expirations.is_empty());
+        assert_eq!(summary.skipped.len(), 2);
+
+        let near = manager
+            .get(&ExpirationDate::Days(pos_or_panic!(7.0)))
+            .unwrap();
+        near.chain()
+            .reference()
+            .update(ReferencePriceKind::Index, 5_000);
+        for entry in manager.expirations.iter() {
+            let chain = entry.value().chain();
+            let inputs = chain.greeks_inputs(0.2).unwrap();
+            for (strike, option_style) in [(4_900, OptionStyle::Put), (5_100, OptionStyle::Call)] {
+                let greeks = compute_greeks(strike, option_style, &inputs).unwrap();
+                chain
+                    .get_strike(strike)
+                    .unwrap()
+                    .update_greeks(option_style, greeks, inputs);
+            }
+        }
+
+        let summary = manager.gamma_exposure();
+        assert_eq!(summary.underlying_price, Some(5_000));
+        assert_eq!(summary.expirations.len(), 2);
+        assert!(summary.skipped.is_empty());
+        let total: f64 = summary.expirations.iter().map(|r| r.total_gex).sum();
+        assert!((summary.total_gex - total).abs() < 1e-9);
+        assert_eq!(summary.expirations[0].put_wall, Some(4_900));
+        let zero = summary.zero_gamma.unwrap();
+        assert!(zero > 4_900.0 && zero < 5_100.0, "{zero}");
+    }
 }
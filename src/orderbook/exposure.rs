@@ -0,0 +1,286 @@
+//! Greeks aggregation module.
+//!
+//! This module aggregates the stored Greeks of contracts into
+//! [`AggregatedGreeks`], weighted by a [`PositionSet`] or by resting order
+//! quantity (see [`ExposureWeights`]).
+
+use super::book::OptionOrderBook;
+use super::strike::StrikeOrderBook;
+use optionstratlib::OptionStyle;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Signed contract positions keyed by contract symbol.
+///
+/// Long positions are positive and short positions negative.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionSet {
+    /// Position per contract symbol.
+    positions: HashMap<String, i64>,
+}
+
+impl PositionSet {
+    /// Creates an empty position set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the position of a contract.
+    pub fn set(&mut self, symbol: impl Into<String>, quantity: i64) {
+        self.positions.insert(symbol.into(), quantity);
+    }
+
+    /// Adds to the position of a contract.
+    pub fn add(&mut self, symbol: impl Into<String>, quantity: i64) {
+        *self.positions.entry(symbol.into()).or_default() += quantity;
+    }
+
+    /// Returns the position of a contract, zero if none.
+    #[must_use]
+    pub fn get(&self, symbol: &str) -> i64 {
+        self.positions.get(symbol).copied().unwrap_or_default()
+    }
+
+    /// Returns the number of contracts with a position entry.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns true if there are no positions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+/// Quantity used to weight the Greeks of each contract.
+#[derive(Debug, Clone, Copy)]
+pub enum ExposureWeights<'a> {
+    /// Signed positions of a position set.
+    Positions(&'a PositionSet),
+    /// Total resting bid quantity: the exposure if every bid is filled.
+    RestingBids,
+    /// Total resting ask quantity, short: the exposure if every ask is
+    /// filled.
+    RestingAsks,
+}
+
+impl ExposureWeights<'_> {
+    /// Returns the signed quantity of a contract.
+    #[must_use]
+    pub fn quantity(&self, book: &OptionOrderBook) -> i64 {
+        match self {
+            Self::Positions(positions) => positions.get(book.symbol()),
+            Self::RestingBids => i64::try_from(book.total_bid_depth()).unwrap_or(i64::MAX),
+            Self::RestingAsks => -i64::try_from(book.total_ask_depth()).unwrap_or(i64::MAX),
+        }
+    }
+}
+
+/// Greeks aggregated over a set of contracts.
+///
+/// Greeks are summed per contract quantity. Cash measures also apply the
+/// contract multiplier and the underlying price of each contract's Greeks:
+///
+/// - `cash_delta`: `q * m * delta * S`, the underlying value equivalent
+/// - `dollar_gamma`: `q * m * gamma * S^2 / 100`, change of cash delta for a
+///   1% move of the underlying
+/// - `vega_per_point`: `q * m * vega`, change of value for one volatility
+///   point
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AggregatedGreeks {
+    /// Sum of quantity times delta.
+    pub delta: f64,
+    /// Sum of quantity times gamma.
+    pub gamma: f64,
+    /// Sum of quantity times theta.
+    pub theta: f64,
+    /// Sum of quantity times vega.
+    pub vega: f64,
+    /// Sum of quantity times rho.
+    pub rho: f64,
+    /// Delta in underlying value, in price units.
+    pub cash_delta: f64,
+    /// Gamma in underlying value per 1% move, in price units.
+    pub dollar_gamma: f64,
+    /// Vega per volatility point including multipliers, in price units.
+    pub vega_per_point: f64,
+    /// Number of weighted contracts included.
+    pub contract_count: usize,
+    /// Number of weighted contracts skipped for lack of Greeks or an
+    /// underlying price.
+    pub missing_count: usize,
+}
+
+impl AggregatedGreeks {
+    /// Adds another aggregate to this one.
+    pub fn merge(&mut self, other: &Self) {
+        self.delta += other.delta;
+        self.gamma += other.gamma;
+        self.theta += other.theta;
+        self.vega += other.vega;
+        self.rho += other.rho;
+        self.cash_delta += other.cash_delta;
+        self.dollar_gamma += other.dollar_gamma;
+        self.vega_per_point += other.vega_per_point;
+        self.contract_count += other.contract_count;
+        self.missing_count += other.missing_count;
+    }
+
+    /// Aggregates the call and put of a strike.
+    ///
+    /// The underlying price recorded with each contract's Greeks is used,
+    /// falling back to `underlying_price`.
+    #[must_use]
+    pub fn from_strike(
+        strike: &StrikeOrderBook,
+        weights: &ExposureWeights<'_>,
+        underlying_price: Option<u64>,
+    ) -> Self {
+        let mut aggregate = Self::default();
+        let multiplier = strike.terms().multiplier.to_f64().unwrap_or(1.0);
+        for option_style in [OptionStyle::Call, OptionStyle::Put] {
+            let quantity = weights.quantity(strike.get(option_style));
+            if quantity == 0 {
+                continue;
+            }
+            let snapshot = strike.greeks_snapshot(option_style);
+            let price = snapshot
+                .as_ref()
+                .and_then(|s| s.inputs.map(|i| i.underlying_price))
+                .or(underlying_price);
+            let (Some(snapshot), Some(price)) = (snapshot, price) else {
+                aggregate.missing_count += 1;
+                continue;
+            };
+
+            let q = quantity as f64;
+            let s = price as f64;
+            let greek = |value: rust_decimal::Decimal| value.to_f64().unwrap_or_default();
+            let greeks = &snapshot.greeks;
+            aggregate.delta += q * greek(greeks.delta);
+            aggregate.gamma += q * greek(greeks.gamma);
+            aggregate.theta += q * greek(greeks.theta);
+            aggregate.vega += q * greek(greeks.vega);
+            aggregate.rho += q * greek(greeks.rho);
+            aggregate.cash_delta += q * multiplier * greek(greeks.delta) * s;
+            aggregate.dollar_gamma += q * multiplier * greek(greeks.gamma) * s * s / 100.0;
+            aggregate.vega_per_point += q * multiplier * greek(greeks.vega);
+            aggregate.contract_count += 1;
+        }
+        aggregate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{ContractTerms, GreeksInputs};
+    use optionstratlib::ExpirationDate;
+    use optionstratlib::greeks::Greek;
+    use optionstratlib::prelude::pos_or_panic;
+    use orderbook_rs::{OrderId, Side};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn greek(delta: Decimal, gamma: Decimal, vega: Decimal) -> Greek {
+        Greek {
+            delta,
+            gamma,
+            theta: dec!(-0.02),
+            vega,
+            rho: dec!(0.1),
+            rho_d: Decimal::ZERO,
+            alpha: Decimal::ZERO,
+            vanna: Decimal::ZERO,
+            vomma: Decimal::ZERO,
+            veta: Decimal::ZERO,
+            charm: Decimal::ZERO,
+            color: Decimal::ZERO,
+        }
+    }
+
+    fn strike() -> StrikeOrderBook {
+        StrikeOrderBook::with_terms(
+            "SPX",
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            100,
+            ContractTerms::new(dec!(100)),
+        )
+    }
+
+    #[test]
+    fn test_position_set() {
+        let mut positions = PositionSet::new();
+        assert!(positions.is_empty());
+        positions.set("A", 5);
+        positions.add("A", -2);
+        positions.add("B", 1);
+        assert_eq!(positions.get("A"), 3);
+        assert_eq!(positions.get("C"), 0);
+        assert_eq!(positions.len(), 2);
+    }
+
+    #[test]
+    fn test_aggregate_positions_with_multiplier() {
+        let strike = strike();
+        let inputs = GreeksInputs {
+            underlying_price: 200,
+            ..GreeksInputs::default()
+        };
+        strike.update_greeks(
+            OptionStyle::Call,
+            greek(dec!(0.5), dec!(0.02), dec!(0.1)),
+            inputs,
+        );
+        strike.update_put_greeks(greek(dec!(-0.5), dec!(0.02), dec!(0.1)));
+
+        let mut positions = PositionSet::new();
+        positions.set(strike.call().symbol(), 10);
+        positions.set(strike.put().symbol(), -4);
+
+        // The put has no recorded price until a fallback is given.
+        let weights = ExposureWeights::Positions(&positions);
+        let aggregate = AggregatedGreeks::from_strike(&strike, &weights, None);
+        assert_eq!(aggregate.contract_count, 1);
+        assert_eq!(aggregate.missing_count, 1);
+        assert!((aggregate.delta - 5.0).abs() < 1e-12);
+        assert!((aggregate.cash_delta - 10.0 * 100.0 * 0.5 * 200.0).abs() < 1e-9);
+        assert!((aggregate.dollar_gamma - 10.0 * 100.0 * 0.02 * 400.0).abs() < 1e-9);
+        assert!((aggregate.vega_per_point - 100.0).abs() < 1e-9);
+
+        let aggregate = AggregatedGreeks::from_strike(&strike, &weights, Some(100));
+        assert_eq!(aggregate.contract_count, 2);
+        assert!((aggregate.delta - 7.0).abs() < 1e-12);
+        assert!((aggregate.gamma - 0.12).abs() < 1e-12);
+        let put_cash = -4.0 * 100.0 * -0.5 * 100.0;
+        assert!((aggregate.cash_delta - (100_000.0 + put_cash)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_aggregate_resting_quantity() {
+        let strike = strike();
+        strike.update_call_greeks(greek(dec!(0.5), dec!(0.02), dec!(0.1)));
+        strike
+            .call()
+            .add_limit_order(OrderId::new(), Side::Buy, 5, 3)
+            .unwrap();
+        strike
+            .call()
+            .add_limit_order(OrderId::new(), Side::Sell, 6, 2)
+            .unwrap();
+
+        let bids = AggregatedGreeks::from_strike(&strike, &ExposureWeights::RestingBids, Some(100));
+        assert!((bids.delta - 1.5).abs() < 1e-12);
+        let asks = AggregatedGreeks::from_strike(&strike, &ExposureWeights::RestingAsks, Some(100));
+        assert!((asks.delta + 1.0).abs() < 1e-12);
+
+        let mut total = bids;
+        total.merge(&asks);
+        assert!((total.delta - 0.5).abs() < 1e-12);
+        assert_eq!(total.contract_count, 2);
+    }
+}
@@ -0,0 +1,152 @@
+//! Underlying future module.
+//!
+//! This module provides the [`UnderlyingFuture`] type linking an expiration
+//! to the futures contract its options deliver into (options on futures).
+
+use orderbook_rs::DefaultOrderBook;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Futures contract referenced by the options of a single expiration.
+///
+/// The price of the future is taken from its order book when one is
+/// attached and quoted, falling back to an externally supplied reference
+/// price. Prices use the same units as strike prices.
+pub struct UnderlyingFuture {
+    /// The futures contract symbol (e.g., "ESH5").
+    symbol: String,
+    /// Optional order book for the futures contract.
+    book: Option<Arc<DefaultOrderBook>>,
+    /// Externally supplied reference price (0 when unset).
+    reference_price: AtomicU64,
+}
+
+impl UnderlyingFuture {
+    /// Creates a future link without an order book.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The futures contract symbol
+    #[must_use]
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            book: None,
+            reference_price: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a future link with a new order book for the contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The futures contract symbol
+    #[must_use]
+    pub fn with_book(symbol: impl Into<String>) -> Self {
+        let symbol = symbol.into();
+        let book = Arc::new(DefaultOrderBook::new(&symbol));
+        Self::from_book(symbol, book)
+    }
+
+    /// Creates a future link to an existing order book.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The futures contract symbol
+    /// * `book` - The order book of the futures contract
+    #[must_use]
+    pub fn from_book(symbol: impl Into<String>, book: Arc<DefaultOrderBook>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            book: Some(book),
+            reference_price: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the futures contract symbol.
+    #[must_use]
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Returns the order book of the futures contract, if attached.
+    #[must_use]
+    pub fn book(&self) -> Option<Arc<DefaultOrderBook>> {
+        self.book.as_ref().map(Arc::clone)
+    }
+
+    /// Sets the externally supplied reference price.
+    ///
+    /// A price of zero clears the reference price.
+    pub fn set_reference_price(&self, price: u64) {
+        self.reference_price.store(price, Ordering::Release);
+    }
+
+    /// Returns the externally supplied reference price, if set.
+    #[must_use]
+    pub fn reference_price(&self) -> Option<u64> {
+        match self.reference_price.load(Ordering::Acquire) {
+            0 => None,
+            price => Some(price),
+        }
+    }
+
+    /// Returns the current price of the future.
+    ///
+    /// Uses the order book mid price when both sides are quoted, then the
+    /// last trade price, then the reference price.
+    #[must_use]
+    pub fn price(&self) -> Option<u64> {
+        let book_price = self.book.as_ref().and_then(|book| match book.mid_price() {
+            Some(mid) => Some(mid.round() as u64),
+            None => book.last_trade_price().and_then(|p| u64::try_from(p).ok()),
+        });
+        book_price.or_else(|| self.reference_price())
+    }
+}
+
+impl std::fmt::Debug for UnderlyingFuture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnderlyingFuture")
+            .field("symbol", &self.symbol)
+            .field("has_book", &self.book.is_some())
+            .field("reference_price", &self.reference_price())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orderbook_rs::{OrderId, Side, TimeInForce};
+
+    #[test]
+    fn test_future_reference_price() {
+        let future = UnderlyingFuture::new("ESH5");
+
+        assert_eq!(future.symbol(), "ESH5");
+        assert!(future.book().is_none());
+        assert!(future.price().is_none());
+
+        future.set_reference_price(5000);
+        assert_eq!(future.price(), Some(5000));
+
+        future.set_reference_price(0);
+        assert!(future.reference_price().is_none());
+    }
+
+    #[test]
+    fn test_future_book_price_overrides_reference() {
+        let future = UnderlyingFuture::with_book("ESH5");
+        future.set_reference_price(4990);
+        assert_eq!(future.price(), Some(4990));
+
+        let book = future.book().unwrap();
+        book.add_limit_order(OrderId::new(), 5000, 1, Side::Buy, TimeInForce::Gtc, None)
+            .unwrap();
+        book.add_limit_order(OrderId::new(), 5004, 1, Side::Sell, TimeInForce::Gtc, None)
+            .unwrap();
+
+        assert_eq!(future.price(), Some(5002));
+    }
+}
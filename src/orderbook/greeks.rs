@@ -0,0 +1,139 @@
+//! Greeks storage module.
+//!
+//! This module provides the [`GreeksSnapshot`] stored per option contract,
+//! together with the [`GreeksInputs`] used to compute it, and the
+//! [`GreeksUpdate`] used for bulk updates of a chain.
+
+use optionstratlib::greeks::Greek;
+use optionstratlib::{ExpirationDate, OptionStyle};
+use serde::{Deserialize, Serialize};
+use std::sync::{PoisonError, RwLock};
+
+/// Market inputs used to compute a set of Greeks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct GreeksInputs {
+    /// Underlying (spot or forward) price, in strike units.
+    pub underlying_price: u64,
+    /// Annualized volatility (e.g., 0.2 for 20%).
+    pub volatility: f64,
+    /// Continuously compounded risk-free rate.
+    pub risk_free_rate: f64,
+    /// Continuously compounded dividend or borrow yield.
+    pub dividend_yield: f64,
+    /// Time to expiration in years.
+    pub years_to_expiry: f64,
+}
+
+/// Greeks of an option contract with the time and inputs they were computed with.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GreeksSnapshot {
+    /// The Greeks.
+    pub greeks: Greek,
+    /// The inputs used to compute the Greeks, if known.
+    pub inputs: Option<GreeksInputs>,
+    /// Timestamp of the update in milliseconds.
+    pub timestamp_ms: u64,
+}
+
+impl GreeksSnapshot {
+    /// Creates a snapshot timestamped at the current time.
+    #[must_use]
+    pub fn new(greeks: Greek, inputs: Option<GreeksInputs>) -> Self {
+        Self::at(greeks, inputs, orderbook_rs::current_time_millis())
+    }
+
+    /// Creates a snapshot with an explicit timestamp.
+    #[must_use]
+    pub const fn at(greeks: Greek, inputs: Option<GreeksInputs>, timestamp_ms: u64) -> Self {
+        Self {
+            greeks,
+            inputs,
+            timestamp_ms,
+        }
+    }
+
+    /// Returns the age of this snapshot at `now_ms` in milliseconds.
+    #[must_use]
+    pub const fn age_ms(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.timestamp_ms)
+    }
+}
+
+/// A Greeks update for one contract of a chain, used for bulk updates.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GreeksUpdate {
+    /// The strike price.
+    pub strike: u64,
+    /// Call or Put.
+    pub option_style: OptionStyle,
+    /// The new Greeks snapshot.
+    pub snapshot: GreeksSnapshot,
+}
+
+impl GreeksUpdate {
+    /// Creates an update timestamped at the current time.
+    #[must_use]
+    pub fn new(
+        strike: u64,
+        option_style: OptionStyle,
+        greeks: Greek,
+        inputs: Option<GreeksInputs>,
+    ) -> Self {
+        Self {
+            strike,
+            option_style,
+            snapshot: GreeksSnapshot::new(greeks, inputs),
+        }
+    }
+}
+
+/// Outcome of a bulk Greeks refresh.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GreeksRefreshReport {
+    /// Number of contracts whose Greeks were updated.
+    pub updated: usize,
+    /// Contracts left unchanged, as `(expiration, strike, style)`: the
+    /// provider had no volatility for them or their Greeks could not be
+    /// computed.
+    pub skipped: Vec<(ExpirationDate, u64, OptionStyle)>,
+}
+
+impl GreeksRefreshReport {
+    /// Adds the counts and skipped contracts of another report.
+    pub fn merge(&mut self, other: Self) {
+        self.updated += other.updated;
+        self.skipped.extend(other.skipped);
+    }
+}
+
+/// Concurrently updatable Greeks slot of a single contract.
+#[derive(Debug, Default)]
+pub(crate) struct GreeksCell {
+    snapshot: RwLock<Option<GreeksSnapshot>>,
+}
+
+impl GreeksCell {
+    /// Stores a snapshot, replacing any previous one.
+    pub(crate) fn set(&self, snapshot: GreeksSnapshot) {
+        *self
+            .snapshot
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(snapshot);
+    }
+
+    /// Returns a copy of the stored snapshot.
+    pub(crate) fn get(&self) -> Option<GreeksSnapshot> {
+        self.snapshot
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Removes the stored snapshot.
+    pub(crate) fn clear(&self) {
+        *self
+            .snapshot
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
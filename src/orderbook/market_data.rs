@@ -0,0 +1,147 @@
+//! Market data module.
+//!
+//! This module provides [`MarketData`], the per-underlying state shared by
+//! every expiration and chain: the reference price feed, the rate and
+//! dividend curves, and the last fitted volatility surface.
+
+use super::future::UnderlyingFuture;
+use super::reference::ReferencePriceFeed;
+use crate::error::Result;
+use crate::pricing::{RateCurves, VolatilitySurface, YieldCurve};
+use optionstratlib::ExpirationDate;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+/// Market data of an underlying, shared across its hierarchy.
+///
+/// All updates go through shared references, so a market data handler can
+/// publish prices and curves while chains read them for pricing.
+#[derive(Debug, Default)]
+pub struct MarketData {
+    /// Reference price feed of the underlying.
+    reference: ReferencePriceFeed,
+    /// Risk-free rate and dividend/borrow curves.
+    curves: RwLock<RateCurves>,
+    /// Last fitted volatility surface.
+    surface: RwLock<Option<Arc<VolatilitySurface>>>,
+}
+
+impl MarketData {
+    /// Creates market data with an empty price feed and flat zero curves.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the reference price feed.
+    #[must_use]
+    pub const fn reference(&self) -> &ReferencePriceFeed {
+        &self.reference
+    }
+
+    /// Returns a copy of the current rate and dividend curves.
+    #[must_use]
+    pub fn curves(&self) -> RateCurves {
+        self.curves
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replaces both curves.
+    pub fn set_curves(&self, curves: RateCurves) {
+        *self.curves.write().unwrap_or_else(PoisonError::into_inner) = curves;
+    }
+
+    /// Replaces the risk-free rate curve.
+    pub fn set_rate_curve(&self, curve: YieldCurve) {
+        self.curves
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .rate = curve;
+    }
+
+    /// Replaces the dividend or borrow yield curve.
+    pub fn set_dividend_curve(&self, curve: YieldCurve) {
+        self.curves
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .dividend = curve;
+    }
+
+    /// Returns the risk-free rate for an expiration date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn risk_free_rate(&self, expiration: &ExpirationDate) -> Result<f64> {
+        self.curves
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .risk_free_rate(expiration)
+    }
+
+    /// Returns the dividend or borrow yield for an expiration date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn dividend_yield(&self, expiration: &ExpirationDate) -> Result<f64> {
+        self.curves
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .dividend_yield(expiration)
+    }
+
+    /// Returns the last fitted volatility surface, if any.
+    #[must_use]
+    pub fn volatility_surface(&self) -> Option<Arc<VolatilitySurface>> {
+        self.surface
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replaces the volatility surface.
+    pub fn set_volatility_surface(&self, surface: Arc<VolatilitySurface>) {
+        *self.surface.write().unwrap_or_else(PoisonError::into_inner) = Some(surface);
+    }
+}
+
+/// Underlying price source of a chain, shared with its strikes.
+///
+/// Resolves to the price of the linked future, if any, otherwise to the
+/// reference price of the underlying.
+pub(crate) struct PriceSource {
+    /// Market data of the underlying.
+    market: Arc<MarketData>,
+    /// Futures contract the options deliver into, for options on futures.
+    future: OnceLock<Arc<UnderlyingFuture>>,
+}
+
+impl PriceSource {
+    /// Creates a price source reading the reference feed of `market`.
+    pub(crate) fn new(market: Arc<MarketData>) -> Self {
+        Self {
+            market,
+            future: OnceLock::new(),
+        }
+    }
+
+    /// Links the future, returning false if one is already linked.
+    pub(crate) fn link_future(&self, future: Arc<UnderlyingFuture>) -> bool {
+        self.future.set(future).is_ok()
+    }
+
+    /// Returns the linked future, if any.
+    pub(crate) fn future(&self) -> Option<&Arc<UnderlyingFuture>> {
+        self.future.get()
+    }
+
+    /// Returns the current underlying price, if available.
+    pub(crate) fn price(&self) -> Option<u64> {
+        self.future
+            .get()
+            .and_then(|f| f.price())
+            .or_else(|| self.market.reference().price())
+    }
+}
@@ -0,0 +1,528 @@
+//! Mass quote module.
+//!
+//! This module provides the [`MassQuote`] message that replaces a
+//! participant's bid and ask in many contracts in one call, with a
+//! [`MassQuoteAck`] per entry.
+//!
+//! Each entry carries the full two-sided quote of a contract: a missing or
+//! zero-size side is cancelled, and a side whose price and size are
+//! unchanged keeps its resting order and queue priority. Contracts not
+//! listed in the message are left untouched.
+//!
+//! A mass quote is not atomic: other orders can trade between two of its
+//! entries.
+
+use super::book::OptionOrderBook;
+use crate::error::{Error, Result};
+use optionstratlib::{ExpirationDate, OptionStyle};
+use orderbook_rs::{OrderId, Side};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
+/// Price and size of one side of a quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteLevel {
+    /// Limit price in smallest units.
+    pub price: u128,
+    /// Quantity; zero cancels the side.
+    pub size: u64,
+}
+
+impl QuoteLevel {
+    /// Creates a quote level.
+    #[must_use]
+    pub const fn new(price: u128, size: u64) -> Self {
+        Self { price, size }
+    }
+}
+
+/// A resting order of a participant's quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantOrder {
+    /// Identifier of the order in the contract book.
+    pub order_id: OrderId,
+    /// Limit price.
+    pub price: u128,
+    /// Quantity placed.
+    pub size: u64,
+}
+
+/// A participant's quote in one contract.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantQuote {
+    /// Resting bid, if any.
+    pub bid: Option<ParticipantOrder>,
+    /// Resting ask, if any.
+    pub ask: Option<ParticipantOrder>,
+}
+
+/// What a quote update did to one side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SideAction {
+    /// A new order was placed where none was resting.
+    Placed,
+    /// The resting order was cancelled and a new one placed.
+    Replaced,
+    /// The resting order was kept.
+    Unchanged,
+    /// The resting order was cancelled.
+    Cancelled,
+    /// No order before or after the update.
+    Empty,
+}
+
+/// Participant quotes of a single contract.
+#[derive(Debug, Default)]
+pub(crate) struct ParticipantQuotes {
+    quotes: RwLock<HashMap<String, ParticipantQuote>>,
+}
+
+impl ParticipantQuotes {
+    /// Replaces the quote of a participant in a contract book.
+    pub(crate) fn replace(
+        &self,
+        book: &OptionOrderBook,
+        participant: &str,
+        bid: Option<QuoteLevel>,
+        ask: Option<QuoteLevel>,
+    ) -> Result<(SideAction, SideAction)> {
+        let bid = bid.filter(|level| level.size > 0);
+        let ask = ask.filter(|level| level.size > 0);
+        if bid.is_some_and(|level| level.price == 0) || ask.is_some_and(|level| level.price == 0) {
+            return Err(Error::validation(format!(
+                "quote in {} has a zero price",
+                book.symbol()
+            )));
+        }
+        if let (Some(bid), Some(ask)) = (bid, ask)
+            && bid.price >= ask.price
+        {
+            return Err(Error::validation(format!(
+                "quote in {} is crossed: bid {} >= ask {}",
+                book.symbol(),
+                bid.price,
+                ask.price
+            )));
+        }
+
+        let mut quotes = self.quotes.write().unwrap_or_else(PoisonError::into_inner);
+        let quote = quotes.entry(participant.to_string()).or_default();
+        // Cancel both stale sides before placing either, so a new side never
+        // trades against the participant's own previous order. The new bid
+        // and ask do not cross, so the sides left resting cannot either.
+        let bid_state = retire_side(book, &mut quote.bid, bid)?;
+        let ask_state = retire_side(book, &mut quote.ask, ask)?;
+        let placed =
+            place_side(book, &mut quote.bid, Side::Buy, bid, bid_state).and_then(|bid_action| {
+                place_side(book, &mut quote.ask, Side::Sell, ask, ask_state)
+                    .map(|ask_action| (bid_action, ask_action))
+            });
+        if quote.bid.is_none() && quote.ask.is_none() {
+            quotes.remove(participant);
+        }
+        placed.map_err(|e| {
+            Error::orderbook(format!(
+                "quote of {participant} in {} was cancelled but could not be replaced: {e}",
+                book.symbol()
+            ))
+        })
+    }
+
+    /// Returns the quote of a participant.
+    pub(crate) fn get(&self, participant: &str) -> Option<ParticipantQuote> {
+        self.quotes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(participant)
+            .copied()
+    }
+
+    /// Cancels the quote of a participant. Returns the number of orders
+    /// cancelled.
+    pub(crate) fn cancel(&self, book: &OptionOrderBook, participant: &str) -> Result<usize> {
+        let quote = self
+            .quotes
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(participant);
+        let mut cancelled = 0;
+        for order in quote.iter().flat_map(|q| [q.bid, q.ask]).flatten() {
+            if book.cancel_order(order.order_id)? {
+                cancelled += 1;
+            }
+        }
+        Ok(cancelled)
+    }
+}
+
+/// State of one side of a quote once its stale order is cancelled.
+#[derive(Debug, Clone, Copy)]
+enum RetiredSide {
+    /// The resting order matches the new level and was kept.
+    Kept,
+    /// The previous order, if any, was cancelled.
+    Cleared {
+        /// An order was resting before the update.
+        had_order: bool,
+        /// The previous order was partially or fully filled.
+        filled: bool,
+    },
+}
+
+/// Keeps one side of a participant's quote if it already matches a level,
+/// or cancels whatever remains of its previous order.
+fn retire_side(
+    book: &OptionOrderBook,
+    resting: &mut Option<ParticipantOrder>,
+    level: Option<QuoteLevel>,
+) -> Result<RetiredSide> {
+    let live = resting.filter(|order| {
+        book.inner()
+            .get_order(order.order_id)
+            .is_some_and(|o| o.visible_quantity() == order.size)
+    });
+    let filled = resting.is_some() && live.is_none();
+    if let (Some(order), Some(level)) = (live, level)
+        && order.price == level.price
+        && order.size == level.size
+    {
+        return Ok(RetiredSide::Kept);
+    }
+
+    let had_order = match *resting {
+        Some(order) => book.cancel_order(order.order_id)? || live.is_some(),
+        None => false,
+    };
+    *resting = None;
+    Ok(RetiredSide::Cleared { had_order, filled })
+}
+
+/// Places one side of a participant's quote after [`retire_side`].
+fn place_side(
+    book: &OptionOrderBook,
+    resting: &mut Option<ParticipantOrder>,
+    side: Side,
+    level: Option<QuoteLevel>,
+    state: RetiredSide,
+) -> Result<SideAction> {
+    let RetiredSide::Cleared { had_order, filled } = state else {
+        return Ok(SideAction::Unchanged);
+    };
+    let Some(level) = level else {
+        return Ok(if had_order {
+            SideAction::Cancelled
+        } else {
+            SideAction::Empty
+        });
+    };
+    let order_id = OrderId::new();
+    book.add_limit_order(order_id, side, level.price, level.size)?;
+    *resting = Some(ParticipantOrder {
+        order_id,
+        price: level.price,
+        size: level.size,
+    });
+    Ok(if had_order || filled {
+        SideAction::Replaced
+    } else {
+        SideAction::Placed
+    })
+}
+
+/// The quote of one contract in a mass quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MassQuoteEntry {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// The strike price.
+    pub strike: u64,
+    /// Call or Put.
+    pub option_style: OptionStyle,
+    /// Bid; `None` cancels the resting bid.
+    pub bid: Option<QuoteLevel>,
+    /// Ask; `None` cancels the resting ask.
+    pub ask: Option<QuoteLevel>,
+}
+
+/// A participant's quotes in many contracts.
+///
+/// Entries are applied one contract at a time, in order, with no lock
+/// spanning the message: the update is not atomic, and a rejected entry
+/// does not undo the entries applied before it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MassQuote {
+    /// The quoting participant.
+    pub participant: String,
+    /// Quote per contract.
+    pub entries: Vec<MassQuoteEntry>,
+}
+
+impl MassQuote {
+    /// Creates an empty mass quote for a participant.
+    #[must_use]
+    pub fn new(participant: impl Into<String>) -> Self {
+        Self {
+            participant: participant.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds the quote of a contract.
+    #[must_use]
+    pub fn with_entry(
+        mut self,
+        expiration: ExpirationDate,
+        strike: u64,
+        option_style: OptionStyle,
+        bid: Option<QuoteLevel>,
+        ask: Option<QuoteLevel>,
+    ) -> Self {
+        self.entries.push(MassQuoteEntry {
+            expiration,
+            strike,
+            option_style,
+            bid,
+            ask,
+        });
+        self
+    }
+
+    /// Returns the number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Outcome of one mass quote entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MassQuoteStatus {
+    /// The quote was applied.
+    Accepted {
+        /// What happened to the bid.
+        bid: SideAction,
+        /// What happened to the ask.
+        ask: SideAction,
+    },
+    /// The quote was rejected.
+    ///
+    /// An invalid quote leaves the contract's previous quote unchanged. If
+    /// the book refuses a new order, the previous orders have already been
+    /// cancelled and the sides not placed are left empty.
+    Rejected {
+        /// Reason for the rejection.
+        reason: String,
+    },
+}
+
+/// Acknowledgement of one mass quote entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MassQuoteAck {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// The strike price.
+    pub strike: u64,
+    /// Call or Put.
+    pub option_style: OptionStyle,
+    /// Outcome of the entry.
+    pub status: MassQuoteStatus,
+}
+
+impl MassQuoteAck {
+    /// Creates the acknowledgement of an entry from its outcome.
+    pub(crate) fn new(entry: &MassQuoteEntry, result: Result<(SideAction, SideAction)>) -> Self {
+        let status = match result {
+            Ok((bid, ask)) => MassQuoteStatus::Accepted { bid, ask },
+            Err(e) => MassQuoteStatus::Rejected {
+                reason: e.to_string(),
+            },
+        };
+        Self {
+            expiration: entry.expiration,
+            strike: entry.strike,
+            option_style: entry.option_style,
+            status,
+        }
+    }
+
+    /// Returns true if the entry was applied.
+    #[must_use]
+    pub const fn is_accepted(&self) -> bool {
+        matches!(self.status, MassQuoteStatus::Accepted { .. })
+    }
+}
+
+/// Acknowledgements of a mass quote, in entry order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MassQuoteReport {
+    /// The quoting participant.
+    pub participant: String,
+    /// Timestamp of the report in milliseconds.
+    pub timestamp_ms: u64,
+    /// One acknowledgement per entry.
+    pub acks: Vec<MassQuoteAck>,
+}
+
+impl MassQuoteReport {
+    /// Creates a report from the acknowledgements of a mass quote.
+    #[must_use]
+    pub fn new(participant: impl Into<String>, acks: Vec<MassQuoteAck>) -> Self {
+        Self {
+            participant: participant.into(),
+            timestamp_ms: orderbook_rs::current_time_millis(),
+            acks,
+        }
+    }
+
+    /// Returns the number of accepted entries.
+    #[must_use]
+    pub fn accepted_count(&self) -> usize {
+        self.acks.iter().filter(|a| a.is_accepted()).count()
+    }
+
+    /// Returns the number of rejected entries.
+    #[must_use]
+    pub fn rejected_count(&self) -> usize {
+        self.acks.len() - self.accepted_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use optionstratlib::prelude::pos_or_panic;
+
+    #[test]
+    fn test_replace_with_minimal_churn() {
+        let book = OptionOrderBook::new("TEST", OptionStyle::Call);
+        let quotes = ParticipantQuotes::default();
+        let level = QuoteLevel::new;
+
+        let actions = quotes
+            .replace(&book, "MM1", Some(level(10, 5)), Some(level(12, 5)))
+            .unwrap();
+        assert_eq!(actions, (SideAction::Placed, SideAction::Placed));
+        let first = quotes.get("MM1").unwrap();
+
+        // Same bid, new ask price.
+        let actions = quotes
+            .replace(&book, "MM1", Some(level(10, 5)), Some(level(13, 5)))
+            .unwrap();
+        assert_eq!(actions, (SideAction::Unchanged, SideAction::Replaced));
+        assert_eq!(quotes.get("MM1").unwrap().bid, first.bid);
+        assert_eq!(book.order_count(), 2);
+
+        // A zero-size bid and a missing ask cancel both sides.
+        let actions = quotes
+            .replace(&book, "MM1", Some(level(10, 0)), None)
+            .unwrap();
+        assert_eq!(actions, (SideAction::Cancelled, SideAction::Cancelled));
+        assert!(quotes.get("MM1").is_none());
+        assert_eq!(book.order_count(), 0);
+        let actions = quotes.replace(&book, "MM1", None, None).unwrap();
+        assert_eq!(actions, (SideAction::Empty, SideAction::Empty));
+    }
+
+    #[test]
+    fn test_replace_validation_and_fills() {
+        let book = OptionOrderBook::new("TEST", OptionStyle::Call);
+        let quotes = ParticipantQuotes::default();
+        let level = QuoteLevel::new;
+        assert!(
+            quotes
+                .replace(&book, "MM1", Some(level(12, 1)), Some(level(12, 1)))
+                .is_err()
+        );
+        assert!(
+            quotes
+                .replace(&book, "MM1", Some(level(0, 1)), None)
+                .is_err()
+        );
+
+        quotes
+            .replace(&book, "MM1", Some(level(10, 5)), Some(level(12, 5)))
+            .unwrap();
+        quotes
+            .replace(&book, "MM2", Some(level(9, 5)), None)
+            .unwrap();
+        // A partial fill of MM1's bid is refreshed to the full size.
+        book.inner()
+            .submit_market_order(OrderId::new(), 2, Side::Sell)
+            .unwrap();
+        let actions = quotes
+            .replace(&book, "MM1", Some(level(10, 5)), Some(level(12, 5)))
+            .unwrap();
+        assert_eq!(actions, (SideAction::Replaced, SideAction::Unchanged));
+        assert_eq!(book.bid_depth_at_price(10), 5);
+
+        assert_eq!(quotes.cancel(&book, "MM1").unwrap(), 2);
+        assert_eq!(quotes.cancel(&book, "MM1").unwrap(), 0);
+        assert_eq!(book.order_count(), 1);
+    }
+
+    #[test]
+    fn test_replace_does_not_trade_with_own_quote() {
+        let book = OptionOrderBook::new("TEST", OptionStyle::Call);
+        let quotes = ParticipantQuotes::default();
+        let level = QuoteLevel::new;
+        quotes
+            .replace(&book, "MM1", Some(level(50, 5)), Some(level(55, 5)))
+            .unwrap();
+
+        // The new bid is above the old ask.
+        let actions = quotes
+            .replace(&book, "MM1", Some(level(56, 5)), Some(level(58, 5)))
+            .unwrap();
+        assert_eq!(actions, (SideAction::Replaced, SideAction::Replaced));
+        assert_eq!(book.activity().volume, 0);
+        assert_eq!(book.next_execution_sequence(), 0);
+        assert_eq!((book.best_bid(), book.best_ask()), (Some(56), Some(58)));
+
+        // And back down, below the old bid.
+        let actions = quotes
+            .replace(&book, "MM1", Some(level(40, 5)), Some(level(45, 5)))
+            .unwrap();
+        assert_eq!(actions, (SideAction::Replaced, SideAction::Replaced));
+        assert_eq!(book.activity().volume, 0);
+        assert_eq!(book.order_count(), 2);
+
+        // A quote crossing itself is rejected without touching the book.
+        assert!(
+            quotes
+                .replace(&book, "MM1", Some(level(46, 5)), Some(level(44, 5)))
+                .is_err()
+        );
+        assert_eq!((book.best_bid(), book.best_ask()), (Some(40), Some(45)));
+    }
+
+    #[test]
+    fn test_mass_quote_builder_and_report() {
+        let expiration = ExpirationDate::Days(pos_or_panic!(30.0));
+        let quote = MassQuote::new("MM1")
+            .with_entry(expiration, 100, OptionStyle::Call, None, None)
+            .with_entry(expiration, 110, OptionStyle::Put, None, None);
+        assert_eq!(quote.len(), 2);
+
+        let acks = vec![
+            MassQuoteAck::new(
+                &quote.entries[0],
+                Ok((SideAction::Empty, SideAction::Empty)),
+            ),
+            MassQuoteAck::new(&quote.entries[1], Err(Error::strike_not_found(110))),
+        ];
+        let report = MassQuoteReport::new("MM1", acks);
+        assert_eq!(report.accepted_count(), 1);
+        assert_eq!(report.rejected_count(), 1);
+        assert!(matches!(
+            &report.acks[1].status,
+            MassQuoteStatus::Rejected { reason } if reason.contains("110")
+        ));
+    }
+}
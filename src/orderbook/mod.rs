@@ -27,6 +27,17 @@
 //! - [`StrikeOrderBook`]: Call/put pair at a strike price
 //! - [`OptionOrderBook`]: Single option order book (call or put)
 //! - [`Quote`]: Represents a two-sided quote (bid and ask)
+//! - [`ContractTerms`]: Contract specification (multiplier, symbol suffix) per strike
+//! - [`CorporateAction`]: Split or special dividend adjustment of listed contracts
+//! - [`UnderlyingFuture`]: Futures contract referenced by an expiration (options on futures)
+//! - [`ReferencePriceFeed`]: Timestamped index/mark/last prices of an underlying
+//! - [`GreeksSnapshot`]: Greeks of a contract with their inputs and timestamp
+//! - [`Execution`]: Sequenced match kept by a contract book for fill consumers
+//! - [`ContractActivity`]: Session volume, trades, VWAP and open interest of a contract
+//! - [`ActivitySummary`]: Activity aggregated at strike, chain and underlying level
+//! - [`AggregatedGreeks`]: Greeks and cash risk aggregated by position or resting quantity
+//! - [`MassQuote`]: Atomic update of a participant's quotes across many contracts
+//! - [`MarketData`]: Reference prices and rate/dividend curves shared by an underlying
 //!
 //! ## Example
 //!
@@ -47,19 +58,46 @@
 //! let quote = strike.call().best_quote();
 //! ```
 
+mod activity;
 mod book;
 mod chain;
+mod contract;
+mod corporate_action;
+mod execution;
 mod expiration;
+mod exposure;
+mod future;
+mod greeks;
+mod market_data;
+mod mass_quote;
 mod quote;
+mod reference;
 mod strike;
 mod underlying;
 
 // Re-export all public types
+pub use activity::{ActivitySummary, ContractActivity, OpenInterestRecord, PositionEffect};
 pub use book::OptionOrderBook;
 pub use chain::{OptionChainOrderBook, OptionChainOrderBookManager, OptionChainStats};
+pub use contract::ContractTerms;
+pub use corporate_action::{
+    CorporateAction, CorporateActionKind, CorporateActionReport, OrderAdjustmentPolicy,
+};
+pub use execution::{EXECUTION_LOG_CAPACITY, Execution};
 pub use expiration::{ExpirationManagerStats, ExpirationOrderBook, ExpirationOrderBookManager};
+pub use exposure::{AggregatedGreeks, ExposureWeights, PositionSet};
+pub use future::UnderlyingFuture;
+pub use greeks::{GreeksInputs, GreeksRefreshReport, GreeksSnapshot, GreeksUpdate};
+pub use market_data::MarketData;
+pub use mass_quote::{
+    MassQuote, MassQuoteAck, MassQuoteEntry, MassQuoteReport, MassQuoteStatus, ParticipantOrder,
+    ParticipantQuote, QuoteLevel, SideAction,
+};
 pub use quote::{Quote, QuoteUpdate};
-pub use strike::{StrikeOrderBook, StrikeOrderBookManager};
+pub use reference::{
+    DEFAULT_MAX_AGE_MS, Moneyness, ReferencePrice, ReferencePriceFeed, ReferencePriceKind,
+};
+pub use strike::{DeltaStrike, StrikeOrderBook, StrikeOrderBookManager};
 pub use underlying::{
     GlobalStats, UnderlyingOrderBook, UnderlyingOrderBookManager, UnderlyingStats,
 };
@@ -0,0 +1,351 @@
+//! Reference price module.
+//!
+//! This module provides the [`ReferencePriceFeed`] holding the timestamped
+//! index, mark and last prices of an underlying, and the [`Moneyness`]
+//! classification of strikes relative to that price.
+
+use optionstratlib::OptionStyle;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock};
+
+/// Default maximum age of a reference price before it is considered stale.
+pub const DEFAULT_MAX_AGE_MS: u64 = 5_000;
+
+/// Kind of reference price published for an underlying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReferencePriceKind {
+    /// Index price (e.g., an exchange-weighted spot index).
+    Index,
+    /// Mark price used for valuation.
+    Mark,
+    /// Last traded price.
+    Last,
+}
+
+/// Timestamped reference prices of an underlying.
+///
+/// Prices use the same units as strike prices. Each kind carries its own
+/// timestamp, so a fresh last trade does not make a stale index look
+/// fresh.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferencePrice {
+    /// Index price, if published.
+    pub index: Option<u64>,
+    /// Mark price, if published.
+    pub mark: Option<u64>,
+    /// Last traded price, if published.
+    pub last: Option<u64>,
+    /// Timestamp of the index price in milliseconds.
+    pub index_timestamp_ms: u64,
+    /// Timestamp of the mark price in milliseconds.
+    pub mark_timestamp_ms: u64,
+    /// Timestamp of the last traded price in milliseconds.
+    pub last_timestamp_ms: u64,
+    /// Timestamp of the most recent update in milliseconds.
+    pub timestamp_ms: u64,
+}
+
+impl ReferencePrice {
+    /// Returns the price of the given kind.
+    #[must_use]
+    pub const fn get(&self, kind: ReferencePriceKind) -> Option<u64> {
+        match kind {
+            ReferencePriceKind::Index => self.index,
+            ReferencePriceKind::Mark => self.mark,
+            ReferencePriceKind::Last => self.last,
+        }
+    }
+
+    /// Returns the timestamp of the price of the given kind.
+    #[must_use]
+    pub const fn timestamp_of(&self, kind: ReferencePriceKind) -> u64 {
+        match kind {
+            ReferencePriceKind::Index => self.index_timestamp_ms,
+            ReferencePriceKind::Mark => self.mark_timestamp_ms,
+            ReferencePriceKind::Last => self.last_timestamp_ms,
+        }
+    }
+
+    /// Returns the preferred price: index, then mark, then last.
+    #[must_use]
+    pub fn price(&self) -> Option<u64> {
+        self.index.or(self.mark).or(self.last)
+    }
+
+    /// Returns the preferred price among those no older than `max_age_ms`
+    /// at `now_ms`: index, then mark, then last.
+    #[must_use]
+    pub fn fresh_price(&self, now_ms: u64, max_age_ms: u64) -> Option<u64> {
+        [
+            ReferencePriceKind::Index,
+            ReferencePriceKind::Mark,
+            ReferencePriceKind::Last,
+        ]
+        .into_iter()
+        .filter(|&kind| now_ms.saturating_sub(self.timestamp_of(kind)) <= max_age_ms)
+        .find_map(|kind| self.get(kind))
+    }
+
+    /// Returns the age of this price at `now_ms` in milliseconds.
+    #[must_use]
+    pub const fn age_ms(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.timestamp_ms)
+    }
+}
+
+/// Feed of reference prices for an underlying, shared across its hierarchy.
+///
+/// Updates and reads go through shared references, so the feed can be
+/// updated by a market data handler while chains read it for ATM and
+/// moneyness calculations.
+#[derive(Debug)]
+pub struct ReferencePriceFeed {
+    /// Latest reference prices.
+    price: RwLock<ReferencePrice>,
+    /// Maximum age in milliseconds before the price is considered stale.
+    max_age_ms: AtomicU64,
+}
+
+impl Default for ReferencePriceFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReferencePriceFeed {
+    /// Creates an empty feed with the default staleness threshold.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            price: RwLock::new(ReferencePrice::default()),
+            max_age_ms: AtomicU64::new(DEFAULT_MAX_AGE_MS),
+        }
+    }
+
+    /// Updates a reference price at the current time.
+    pub fn update(&self, kind: ReferencePriceKind, price: u64) {
+        self.update_at(kind, price, orderbook_rs::current_time_millis());
+    }
+
+    /// Updates a reference price with an explicit timestamp.
+    ///
+    /// An update older than the stored price of the same kind arrived out
+    /// of order and is ignored. Returns true if the price was applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of price being updated
+    /// * `price` - The new price, in strike units
+    /// * `timestamp_ms` - Timestamp of the price in milliseconds
+    pub fn update_at(&self, kind: ReferencePriceKind, price: u64, timestamp_ms: u64) -> bool {
+        let mut current = self.price.write().unwrap_or_else(PoisonError::into_inner);
+        if current.get(kind).is_some() && timestamp_ms < current.timestamp_of(kind) {
+            return false;
+        }
+        match kind {
+            ReferencePriceKind::Index => {
+                current.index = Some(price);
+                current.index_timestamp_ms = timestamp_ms;
+            }
+            ReferencePriceKind::Mark => {
+                current.mark = Some(price);
+                current.mark_timestamp_ms = timestamp_ms;
+            }
+            ReferencePriceKind::Last => {
+                current.last = Some(price);
+                current.last_timestamp_ms = timestamp_ms;
+            }
+        }
+        current.timestamp_ms = current.timestamp_ms.max(timestamp_ms);
+        true
+    }
+
+    /// Returns the latest reference prices, if any price was published.
+    #[must_use]
+    pub fn snapshot(&self) -> Option<ReferencePrice> {
+        let current = *self.price.read().unwrap_or_else(PoisonError::into_inner);
+        current.price().map(|_| current)
+    }
+
+    /// Sets the maximum age before the price is considered stale.
+    pub fn set_max_age_ms(&self, max_age_ms: u64) {
+        self.max_age_ms.store(max_age_ms, Ordering::Release);
+    }
+
+    /// Returns the maximum age before the price is considered stale.
+    #[must_use]
+    pub fn max_age_ms(&self) -> u64 {
+        self.max_age_ms.load(Ordering::Acquire)
+    }
+
+    /// Returns true if no price of any kind is within the maximum age at
+    /// `now_ms`.
+    #[must_use]
+    pub fn is_stale_at(&self, now_ms: u64) -> bool {
+        self.price_at(now_ms).is_none()
+    }
+
+    /// Returns the preferred price at `now_ms` among the kinds within the
+    /// maximum age: a stale index falls back to a fresh mark or last.
+    #[must_use]
+    pub fn price_at(&self, now_ms: u64) -> Option<u64> {
+        self.price
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .fresh_price(now_ms, self.max_age_ms())
+    }
+
+    /// Returns true if no price of any kind is within the maximum age.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.is_stale_at(orderbook_rs::current_time_millis())
+    }
+
+    /// Returns the preferred price among the kinds that are not stale.
+    #[must_use]
+    pub fn price(&self) -> Option<u64> {
+        self.price_at(orderbook_rs::current_time_millis())
+    }
+}
+
+/// Classification of a strike relative to the underlying price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Moneyness {
+    /// The option has intrinsic value.
+    InTheMoney,
+    /// The strike is the listed strike closest to the underlying price.
+    AtTheMoney,
+    /// The option has no intrinsic value.
+    OutOfTheMoney,
+}
+
+impl Moneyness {
+    /// Classifies a strike for the given option style.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - The strike to classify
+    /// * `atm_strike` - The listed strike closest to the underlying price
+    /// * `underlying_price` - The underlying price
+    /// * `option_style` - Call or Put
+    #[must_use]
+    pub fn classify(
+        strike: u64,
+        atm_strike: u64,
+        underlying_price: u64,
+        option_style: OptionStyle,
+    ) -> Self {
+        if strike == atm_strike {
+            return Self::AtTheMoney;
+        }
+        let in_the_money = match option_style {
+            OptionStyle::Call => strike < underlying_price,
+            OptionStyle::Put => strike > underlying_price,
+        };
+        if in_the_money {
+            Self::InTheMoney
+        } else {
+            Self::OutOfTheMoney
+        }
+    }
+}
+
+impl std::fmt::Display for Moneyness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InTheMoney => write!(f, "ITM"),
+            Self::AtTheMoney => write!(f, "ATM"),
+            Self::OutOfTheMoney => write!(f, "OTM"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_price_preference() {
+        let price = ReferencePrice {
+            index: None,
+            mark: Some(101),
+            last: Some(102),
+            mark_timestamp_ms: 10,
+            last_timestamp_ms: 10,
+            timestamp_ms: 10,
+            ..ReferencePrice::default()
+        };
+
+        assert_eq!(price.price(), Some(101));
+        assert_eq!(price.get(ReferencePriceKind::Last), Some(102));
+        assert_eq!(price.age_ms(25), 15);
+    }
+
+    #[test]
+    fn test_feed_updates_and_snapshot() {
+        let feed = ReferencePriceFeed::new();
+        assert!(feed.snapshot().is_none());
+        assert!(feed.price().is_none());
+
+        feed.update(ReferencePriceKind::Last, 50010);
+        feed.update(ReferencePriceKind::Index, 50000);
+
+        let snapshot = feed.snapshot().unwrap();
+        assert_eq!(snapshot.index, Some(50000));
+        assert_eq!(snapshot.last, Some(50010));
+        assert_eq!(feed.price(), Some(50000));
+    }
+
+    #[test]
+    fn test_feed_staleness() {
+        let feed = ReferencePriceFeed::new();
+        feed.update_at(ReferencePriceKind::Mark, 100, 1_000);
+        feed.set_max_age_ms(500);
+
+        assert_eq!(feed.max_age_ms(), 500);
+        assert!(!feed.is_stale_at(1_400));
+        assert!(feed.is_stale_at(1_600));
+        assert!(feed.is_stale());
+        assert!(feed.price().is_none());
+    }
+
+    #[test]
+    fn test_feed_staleness_per_kind() {
+        let feed = ReferencePriceFeed::new();
+        feed.set_max_age_ms(500);
+        feed.update_at(ReferencePriceKind::Index, 100, 1_000);
+        feed.update_at(ReferencePriceKind::Last, 101, 2_000);
+
+        // A fresh last does not make the stale index fresh.
+        assert_eq!(feed.price_at(1_200), Some(100));
+        assert_eq!(feed.price_at(2_200), Some(101));
+        assert!(!feed.is_stale_at(2_200));
+        assert!(feed.is_stale_at(2_600));
+
+        // Out-of-order ticks are ignored.
+        assert!(!feed.update_at(ReferencePriceKind::Index, 90, 900));
+        assert!(feed.update_at(ReferencePriceKind::Index, 102, 2_100));
+        assert_eq!(feed.price_at(2_200), Some(102));
+        let snapshot = feed.snapshot().unwrap();
+        assert_eq!(snapshot.timestamp_of(ReferencePriceKind::Index), 2_100);
+        assert_eq!(snapshot.timestamp_ms, 2_100);
+    }
+
+    #[test]
+    fn test_moneyness_classification() {
+        assert_eq!(
+            Moneyness::classify(45000, 50000, 49000, OptionStyle::Call),
+            Moneyness::InTheMoney
+        );
+        assert_eq!(
+            Moneyness::classify(45000, 50000, 49000, OptionStyle::Put),
+            Moneyness::OutOfTheMoney
+        );
+        assert_eq!(
+            Moneyness::classify(50000, 50000, 49000, OptionStyle::Put),
+            Moneyness::AtTheMoney
+        );
+        assert_eq!(Moneyness::InTheMoney.to_string(), "ITM");
+    }
+}
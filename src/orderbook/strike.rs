@@ -3,14 +3,22 @@
 //! This module provides the [`StrikeOrderBook`] and [`StrikeOrderBookManager`]
 //! for managing call/put pairs at a specific strike price.
 
+use super::activity::ActivitySummary;
 use super::book::OptionOrderBook;
+use super::contract::ContractTerms;
+use super::corporate_action::{CorporateAction, CorporateActionReport, OrderAdjustmentPolicy};
+use super::greeks::{GreeksCell, GreeksInputs, GreeksSnapshot};
+use super::market_data::PriceSource;
 use super::quote::Quote;
 use crate::error::{Error, Result};
+use crate::pricing::StrikeParity;
 use crate::utils::format_expiration_yyyymmdd;
 use crossbeam_skiplist::SkipMap;
 use optionstratlib::greeks::Greek;
 use optionstratlib::{ExpirationDate, OptionStyle};
-use orderbook_rs::OrderId;
+use orderbook_rs::{OrderId, OrderType};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Order book for a single strike price containing both call and put.
@@ -37,10 +45,14 @@ pub struct StrikeOrderBook {
     call: Arc<OptionOrderBook>,
     /// Put option order book.
     put: Arc<OptionOrderBook>,
+    /// Contract terms shared by the call and put.
+    terms: ContractTerms,
     /// Greeks for the call option.
-    call_greeks: Option<Greek>,
+    call_greeks: GreeksCell,
     /// Greeks for the put option.
-    put_greeks: Option<Greek>,
+    put_greeks: GreeksCell,
+    /// Underlying price source of the owning chain, if any.
+    price: Option<Arc<PriceSource>>,
     /// Unique identifier for this strike order book.
     id: OrderId,
 }
@@ -55,14 +67,36 @@ impl StrikeOrderBook {
     /// * `strike` - The strike price
     #[must_use]
     pub fn new(underlying: impl Into<String>, expiration: ExpirationDate, strike: u64) -> Self {
+        Self::with_terms(underlying, expiration, strike, ContractTerms::default())
+    }
+
+    /// Creates a new strike order book with explicit contract terms.
+    ///
+    /// The symbol suffix of the terms is appended to the underlying symbol
+    /// when building the call and put contract symbols.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying` - The underlying asset symbol (e.g., "BTC")
+    /// * `expiration` - The expiration date
+    /// * `strike` - The strike price
+    /// * `terms` - The contract terms for the call/put pair
+    #[must_use]
+    pub fn with_terms(
+        underlying: impl Into<String>,
+        expiration: ExpirationDate,
+        strike: u64,
+        terms: ContractTerms,
+    ) -> Self {
         let underlying = underlying.into();
 
         // Format expiration as YYYYMMDD, fallback to Display if formatting fails
         let exp_str =
             format_expiration_yyyymmdd(&expiration).unwrap_or_else(|_| expiration.to_string());
 
-        let call_symbol = format!("{}-{}-{}-C", underlying, exp_str, strike);
-        let put_symbol = format!("{}-{}-{}-P", underlying, exp_str, strike);
+        let root = format!("{}{}", underlying, terms.symbol_suffix);
+        let call_symbol = format!("{}-{}-{}-C", root, exp_str, strike);
+        let put_symbol = format!("{}-{}-{}-P", root, exp_str, strike);
 
         Self {
             underlying,
@@ -70,12 +104,20 @@ impl StrikeOrderBook {
             strike,
             call: Arc::new(OptionOrderBook::new(call_symbol, OptionStyle::Call)),
             put: Arc::new(OptionOrderBook::new(put_symbol, OptionStyle::Put)),
-            call_greeks: None,
-            put_greeks: None,
+            terms,
+            call_greeks: GreeksCell::default(),
+            put_greeks: GreeksCell::default(),
+            price: None,
             id: OrderId::new(),
         }
     }
 
+    /// Links this strike to the underlying price source of its chain.
+    fn with_price_source(mut self, price: Option<Arc<PriceSource>>) -> Self {
+        self.price = price;
+        self
+    }
+
     /// Returns the underlying asset symbol.
     #[must_use]
     pub fn underlying(&self) -> &str {
@@ -94,12 +136,76 @@ impl StrikeOrderBook {
         self.strike
     }
 
+    /// Returns the underlying price of the owning chain.
+    ///
+    /// This is the price of the chain's linked future, if any, otherwise the
+    /// reference price of the underlying. Standalone strikes have none.
+    #[must_use]
+    pub fn underlying_price(&self) -> Option<u64> {
+        self.price.as_ref().and_then(|p| p.price())
+    }
+
+    /// Returns the moneyness of this strike, `strike / underlying_price`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn moneyness(&self) -> Result<f64> {
+        Ok(self.strike as f64 / self.require_underlying_price()? as f64)
+    }
+
+    /// Returns true if the option of the given style has intrinsic value.
+    ///
+    /// Unlike the chain's classification, this does not bucket the strike
+    /// closest to the underlying price as at the money.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn is_itm(&self, option_style: OptionStyle) -> Result<bool> {
+        let price = self.require_underlying_price()?;
+        Ok(match option_style {
+            OptionStyle::Call => self.strike < price,
+            OptionStyle::Put => self.strike > price,
+        })
+    }
+
+    /// Returns true if the option of the given style is out of the money,
+    /// with the strike strictly away from the underlying price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if no underlying price is available.
+    pub fn is_otm(&self, option_style: OptionStyle) -> Result<bool> {
+        let price = self.require_underlying_price()?;
+        Ok(match option_style {
+            OptionStyle::Call => self.strike > price,
+            OptionStyle::Put => self.strike < price,
+        })
+    }
+
+    /// Returns the underlying price or a market data error.
+    fn require_underlying_price(&self) -> Result<u64> {
+        self.underlying_price().ok_or_else(|| {
+            Error::market_data(format!(
+                "no underlying price for {} {} strike {}",
+                self.underlying, self.expiration, self.strike
+            ))
+        })
+    }
+
     /// Returns the unique identifier for this strike order book.
     #[must_use]
     pub const fn id(&self) -> OrderId {
         self.id
     }
 
+    /// Returns the contract terms for the call/put pair.
+    #[must_use]
+    pub const fn terms(&self) -> &ContractTerms {
+        &self.terms
+    }
+
     /// Returns a reference to the call order book.
     #[must_use]
     pub fn call(&self) -> &OptionOrderBook {
@@ -154,6 +260,19 @@ impl StrikeOrderBook {
         self.put.best_quote()
     }
 
+    /// Returns the put-call parity analytics of this strike: the synthetic
+    /// forward, the implied rate and borrow, and any conversion or reversal
+    /// arbitrage against the forward of `inputs`.
+    #[must_use]
+    pub fn parity(&self, inputs: &GreeksInputs) -> StrikeParity {
+        StrikeParity::from_quotes(
+            self.strike,
+            &self.call.best_quote(),
+            &self.put.best_quote(),
+            inputs,
+        )
+    }
+
     /// Returns true if both call and put have two-sided quotes.
     #[must_use]
     pub fn is_fully_quoted(&self) -> bool {
@@ -178,26 +297,134 @@ impl StrikeOrderBook {
         self.put.clear();
     }
 
+    /// Returns the session activity and open interest of the call and put.
+    #[must_use]
+    pub fn activity_summary(&self) -> ActivitySummary {
+        let mut summary = ActivitySummary::default();
+        summary.add(OptionStyle::Call, &self.call.activity());
+        summary.add(OptionStyle::Put, &self.put.activity());
+        summary
+    }
+
+    /// Starts a new trading session for the call and put and returns the
+    /// activity of the closed one.
+    pub fn roll_activity(&self) -> ActivitySummary {
+        let mut summary = ActivitySummary::default();
+        summary.add(OptionStyle::Call, &self.call.roll_activity());
+        summary.add(OptionStyle::Put, &self.put.roll_activity());
+        summary
+    }
+
     /// Updates the Greeks for the call option.
-    pub fn update_call_greeks(&mut self, greeks: Greek) {
-        self.call_greeks = Some(greeks);
+    pub fn update_call_greeks(&self, greeks: Greek) {
+        self.call_greeks.set(GreeksSnapshot::new(greeks, None));
     }
 
     /// Updates the Greeks for the put option.
-    pub fn update_put_greeks(&mut self, greeks: Greek) {
-        self.put_greeks = Some(greeks);
+    pub fn update_put_greeks(&self, greeks: Greek) {
+        self.put_greeks.set(GreeksSnapshot::new(greeks, None));
+    }
+
+    /// Updates the Greeks for the given option style, recording the inputs
+    /// used to compute them.
+    pub fn update_greeks(&self, option_style: OptionStyle, greeks: Greek, inputs: GreeksInputs) {
+        self.set_greeks_snapshot(option_style, GreeksSnapshot::new(greeks, Some(inputs)));
+    }
+
+    /// Stores a Greeks snapshot for the given option style.
+    pub fn set_greeks_snapshot(&self, option_style: OptionStyle, snapshot: GreeksSnapshot) {
+        self.greeks_cell(option_style).set(snapshot);
     }
 
     /// Returns the Greeks for the call option.
     #[must_use]
-    pub const fn call_greeks(&self) -> Option<&Greek> {
-        self.call_greeks.as_ref()
+    pub fn call_greeks(&self) -> Option<Greek> {
+        self.call_greeks.get().map(|s| s.greeks)
     }
 
     /// Returns the Greeks for the put option.
     #[must_use]
-    pub const fn put_greeks(&self) -> Option<&Greek> {
-        self.put_greeks.as_ref()
+    pub fn put_greeks(&self) -> Option<Greek> {
+        self.put_greeks.get().map(|s| s.greeks)
+    }
+
+    /// Returns the Greeks snapshot (Greeks, inputs and timestamp) for the
+    /// given option style.
+    #[must_use]
+    pub fn greeks_snapshot(&self, option_style: OptionStyle) -> Option<GreeksSnapshot> {
+        self.greeks_cell(option_style).get()
+    }
+
+    /// Removes the Greeks of both the call and the put.
+    pub fn clear_greeks(&self) {
+        self.call_greeks.clear();
+        self.put_greeks.clear();
+    }
+
+    /// Returns the Greeks slot for the given option style.
+    const fn greeks_cell(&self, option_style: OptionStyle) -> &GreeksCell {
+        match option_style {
+            OptionStyle::Call => &self.call_greeks,
+            OptionStyle::Put => &self.put_greeks,
+        }
+    }
+}
+
+/// Result of a delta-based strike lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DeltaStrike {
+    /// Call or Put.
+    pub option_style: OptionStyle,
+    /// Listed strike whose delta is nearest to the target.
+    pub strike: u64,
+    /// Delta of the listed strike.
+    pub delta: f64,
+    /// Fractional strike at the target delta, interpolated linearly between
+    /// the two listed strikes that bracket it; `None` if the target is
+    /// outside the listed deltas.
+    pub interpolated_strike: Option<f64>,
+}
+
+impl DeltaStrike {
+    /// Finds the strike for a target delta among `(strike, delta)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if `deltas` is empty.
+    pub(crate) fn from_deltas(
+        deltas: &[(u64, f64)],
+        target_delta: f64,
+        option_style: OptionStyle,
+    ) -> Result<Self> {
+        let &(strike, delta) = deltas
+            .iter()
+            .min_by(|a, b| {
+                (a.1 - target_delta)
+                    .abs()
+                    .total_cmp(&(b.1 - target_delta).abs())
+            })
+            .ok_or_else(|| Error::no_data(format!("no {option_style:?} deltas available")))?;
+
+        let mut sorted = deltas.to_vec();
+        sorted.sort_by_key(|&(k, _)| k);
+        let interpolated_strike = sorted.windows(2).find_map(|pair| {
+            let [(k0, d0), (k1, d1)] = [pair[0], pair[1]];
+            let (lo, hi) = (d0.min(d1), d0.max(d1));
+            if target_delta < lo || target_delta > hi {
+                return None;
+            }
+            if d1 == d0 {
+                return Some(k0 as f64);
+            }
+            Some(k0 as f64 + (target_delta - d0) / (d1 - d0) * (k1 - k0) as f64)
+        });
+
+        Ok(Self {
+            option_style,
+            strike,
+            delta,
+            interpolated_strike,
+        })
     }
 }
 
@@ -212,6 +439,8 @@ pub struct StrikeOrderBookManager {
     underlying: String,
     /// The expiration date.
     expiration: ExpirationDate,
+    /// Underlying price source handed to new strikes.
+    price: Option<Arc<PriceSource>>,
 }
 
 impl StrikeOrderBookManager {
@@ -227,6 +456,20 @@ impl StrikeOrderBookManager {
             strikes: SkipMap::new(),
             underlying: underlying.into(),
             expiration,
+            price: None,
+        }
+    }
+
+    /// Creates a strike order book manager whose strikes read the
+    /// underlying price from the given source.
+    pub(crate) fn with_price_source(
+        underlying: impl Into<String>,
+        expiration: ExpirationDate,
+        price: Arc<PriceSource>,
+    ) -> Self {
+        Self {
+            price: Some(price),
+            ..Self::new(underlying, expiration)
         }
     }
 
@@ -259,11 +502,10 @@ impl StrikeOrderBookManager {
         if let Some(entry) = self.strikes.get(&strike) {
             return Arc::clone(entry.value());
         }
-        let book = Arc::new(StrikeOrderBook::new(
-            &self.underlying,
-            self.expiration,
-            strike,
-        ));
+        let book = Arc::new(
+            StrikeOrderBook::new(&self.underlying, self.expiration, strike)
+                .with_price_source(self.price.clone()),
+        );
         self.strikes.insert(strike, Arc::clone(&book));
         book
     }
@@ -324,6 +566,175 @@ impl StrikeOrderBookManager {
             .min_by_key(|&k| (k as i64 - spot as i64).unsigned_abs())
             .ok_or_else(|| Error::no_data("no strikes available"))
     }
+
+    /// Returns the listed strike whose stored delta is nearest to a target
+    /// delta, with the strike interpolated between listed strikes.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_delta` - Signed delta, e.g. `-0.25` for the 25-delta put
+    /// * `option_style` - Call or Put
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if no strike has Greeks for the
+    /// given option style.
+    pub fn strike_for_delta(
+        &self,
+        target_delta: f64,
+        option_style: OptionStyle,
+    ) -> Result<DeltaStrike> {
+        let deltas: Vec<(u64, f64)> = self
+            .strikes
+            .iter()
+            .filter_map(|e| {
+                let greeks = e.value().greeks_snapshot(option_style)?.greeks;
+                Some((*e.key(), greeks.delta.to_f64()?))
+            })
+            .collect();
+        DeltaStrike::from_deltas(&deltas, target_delta, option_style)
+    }
+
+    /// Applies a corporate action to every strike in this manager.
+    ///
+    /// Each strike is re-keyed at its adjusted strike price with adjusted
+    /// contract terms and symbols. Resting orders are moved to the adjusted
+    /// books or cancelled according to the action's order policy, and the
+    /// original books are cleared. Every original strike is removed before
+    /// any adjusted strike is inserted, so an adjusted strike may take the
+    /// price of an original one (e.g. in a reverse split). Trading should be
+    /// halted while the adjustment runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the action is invalid, if a strike
+    /// cannot be adjusted to a positive value, or if two strikes would be
+    /// adjusted to the same strike price. No strike is modified on error.
+    pub fn apply_corporate_action(
+        &self,
+        action: &CorporateAction,
+    ) -> Result<CorporateActionReport> {
+        let books: Vec<(u64, Arc<StrikeOrderBook>)> = self
+            .strikes
+            .iter()
+            .map(|e| (*e.key(), Arc::clone(e.value())))
+            .collect();
+        let strike_map = plan_strikes(action, books.iter().map(|(strike, _)| *strike))?;
+
+        let mut report = CorporateActionReport::default();
+        let mut adjusted_books = Vec::with_capacity(books.len());
+        for ((_, book), &(_, new_strike)) in books.iter().zip(&strike_map) {
+            let adjusted = StrikeOrderBook::with_terms(
+                &self.underlying,
+                self.expiration,
+                new_strike,
+                action.adjust_terms(book.terms()),
+            )
+            .with_price_source(self.price.clone());
+            for style in [OptionStyle::Call, OptionStyle::Put] {
+                let (migrated, cancelled) =
+                    migrate_orders(book.get(style), adjusted.get(style), action);
+                report.orders_migrated += migrated;
+                report.orders_cancelled += cancelled;
+            }
+            book.clear();
+            adjusted_books.push((new_strike, Arc::new(adjusted)));
+        }
+        for (old_strike, _) in &books {
+            self.strikes.remove(old_strike);
+        }
+        for (new_strike, adjusted) in adjusted_books {
+            self.strikes.insert(new_strike, adjusted);
+        }
+
+        report.strikes_adjusted = strike_map.len();
+        report.strike_map = strike_map;
+        Ok(report)
+    }
+
+    /// Computes the original-to-adjusted strike mapping for a corporate
+    /// action without modifying any strike.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` under the same conditions as
+    /// [`Self::apply_corporate_action`].
+    pub(crate) fn plan_corporate_action(
+        &self,
+        action: &CorporateAction,
+    ) -> Result<Vec<(u64, u64)>> {
+        plan_strikes(action, self.strikes.iter().map(|e| *e.key()))
+    }
+}
+
+/// Computes the original-to-adjusted mapping of a set of strikes.
+fn plan_strikes(
+    action: &CorporateAction,
+    strikes: impl Iterator<Item = u64>,
+) -> Result<Vec<(u64, u64)>> {
+    action.validate()?;
+
+    let mut strike_map: Vec<(u64, u64)> = Vec::new();
+    for strike in strikes {
+        let adjusted = action.adjust_strike(strike)?;
+        if strike_map.iter().any(|&(_, new)| new == adjusted) {
+            return Err(Error::validation(format!(
+                "adjusted strike {adjusted} collides with another adjusted strike"
+            )));
+        }
+        strike_map.push((strike, adjusted));
+    }
+    Ok(strike_map)
+}
+
+/// Moves resting orders from `source` to `target` according to the action's
+/// order policy, returning the number of migrated and cancelled orders.
+///
+/// Migrated orders keep their type, side, time in force and displayed and
+/// hidden quantities; only the price is adjusted. Orders whose price follows
+/// a reference (trailing stop, pegged, market-to-limit) cannot be carried
+/// over as they are and are cancelled, as are post-only orders that would
+/// cross at the adjusted price.
+fn migrate_orders(
+    source: &OptionOrderBook,
+    target: &OptionOrderBook,
+    action: &CorporateAction,
+) -> (usize, usize) {
+    let orders = source.inner().get_all_orders();
+    if action.order_policy() == OrderAdjustmentPolicy::Cancel {
+        return (0, orders.len());
+    }
+
+    let mut migrated = 0;
+    let mut cancelled = 0;
+    for order in orders {
+        let added = action
+            .adjust_price(order.price())
+            .and_then(|price| reprice(&order, price))
+            .is_some_and(|order| target.inner().add_order(order).is_ok());
+        if added {
+            migrated += 1;
+        } else {
+            cancelled += 1;
+        }
+    }
+    (migrated, cancelled)
+}
+
+/// Returns a copy of a resting order at a new price, or `None` if the order
+/// type does not rest at a fixed price.
+fn reprice(order: &OrderType<()>, price: u128) -> Option<OrderType<()>> {
+    let mut order = *order;
+    match &mut order {
+        OrderType::Standard { price: p, .. }
+        | OrderType::IcebergOrder { price: p, .. }
+        | OrderType::PostOnly { price: p, .. }
+        | OrderType::ReserveOrder { price: p, .. } => *p = price,
+        OrderType::TrailingStop { .. }
+        | OrderType::PeggedOrder { .. }
+        | OrderType::MarketToLimit { .. } => return None,
+    }
+    Some(order)
 }
 
 #[cfg(test)]
@@ -402,6 +813,25 @@ mod tests {
         assert_eq!(manager.atm_strike(53000).unwrap(), 55000);
     }
 
+    #[test]
+    fn test_strike_manager_strike_for_delta() {
+        let manager = StrikeOrderBookManager::new("BTC", test_expiration());
+        drop(manager.get_or_create(40000));
+        assert!(manager.strike_for_delta(-0.25, OptionStyle::Put).is_err());
+
+        let deltas = [(45000, -0.1), (50000, -0.4), (55000, -0.7)];
+        let found = DeltaStrike::from_deltas(&deltas, -0.25, OptionStyle::Put).unwrap();
+        assert_eq!(found.strike, 45000);
+        assert_eq!(found.delta, -0.1);
+        assert!((found.interpolated_strike.unwrap() - 47500.0).abs() < 1e-6);
+
+        let found = DeltaStrike::from_deltas(&deltas, -0.6, OptionStyle::Put).unwrap();
+        assert_eq!(found.strike, 55000);
+        assert!((found.interpolated_strike.unwrap() - 53333.333).abs() < 1e-2);
+        let outside = DeltaStrike::from_deltas(&deltas, -0.05, OptionStyle::Put).unwrap();
+        assert!(outside.interpolated_strike.is_none());
+    }
+
     #[test]
     fn test_strike_manager_atm_empty() {
         let manager = StrikeOrderBookManager::new("BTC", test_expiration());
@@ -551,7 +981,7 @@ mod tests {
         use optionstratlib::greeks::Greek;
         use rust_decimal_macros::dec;
 
-        let mut strike = StrikeOrderBook::new("BTC", test_expiration(), 50000);
+        let strike = StrikeOrderBook::new("BTC", test_expiration(), 50000);
 
         assert!(strike.call_greeks().is_none());
         assert!(strike.put_greeks().is_none());
@@ -590,6 +1020,69 @@ mod tests {
 
         assert!(strike.call_greeks().is_some());
         assert!(strike.put_greeks().is_some());
+        assert!(
+            strike
+                .greeks_snapshot(OptionStyle::Call)
+                .unwrap()
+                .inputs
+                .is_none()
+        );
+
+        strike.clear_greeks();
+        assert!(strike.call_greeks().is_none());
+    }
+
+    #[test]
+    fn test_strike_greeks_shared_updates() {
+        use crate::orderbook::GreeksInputs;
+        use optionstratlib::greeks::Greek;
+        use rust_decimal::Decimal;
+        use rust_decimal_macros::dec;
+
+        let manager = Arc::new(StrikeOrderBookManager::new("BTC", test_expiration()));
+        let strike = manager.get_or_create(50000);
+        let inputs = GreeksInputs {
+            underlying_price: 51000,
+            volatility: 0.6,
+            risk_free_rate: 0.05,
+            dividend_yield: 0.0,
+            years_to_expiry: 0.25,
+        };
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let manager = Arc::clone(&manager);
+                std::thread::spawn(move || {
+                    let greeks = Greek {
+                        delta: Decimal::from(i) / dec!(10),
+                        gamma: Decimal::ZERO,
+                        theta: Decimal::ZERO,
+                        vega: Decimal::ZERO,
+                        rho: Decimal::ZERO,
+                        rho_d: Decimal::ZERO,
+                        alpha: Decimal::ZERO,
+                        vanna: Decimal::ZERO,
+                        vomma: Decimal::ZERO,
+                        veta: Decimal::ZERO,
+                        charm: Decimal::ZERO,
+                        color: Decimal::ZERO,
+                    };
+                    manager
+                        .get(50000)
+                        .unwrap()
+                        .update_greeks(OptionStyle::Put, greeks, inputs);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let snapshot = strike.greeks_snapshot(OptionStyle::Put).unwrap();
+        assert_eq!(snapshot.inputs, Some(inputs));
+        assert!(snapshot.timestamp_ms > 0);
+        assert!(snapshot.greeks.delta < dec!(0.4));
+        assert!(strike.call_greeks().is_none());
     }
 
     #[test]
@@ -625,6 +1118,141 @@ mod tests {
         assert!(!manager.remove(50000));
     }
 
+    #[test]
+    fn test_strike_with_terms_symbols() {
+        use rust_decimal_macros::dec;
+
+        let terms = ContractTerms {
+            multiplier: dec!(150),
+            symbol_suffix: "1".to_string(),
+            adjustments: 1,
+        };
+        let strike = StrikeOrderBook::with_terms("AAPL", test_expiration(), 100, terms);
+
+        assert!(strike.call().symbol().starts_with("AAPL1-"));
+        assert!(strike.put().symbol().ends_with("-100-P"));
+        assert_eq!(strike.terms().multiplier, dec!(150));
+    }
+
+    #[test]
+    fn test_strike_manager_corporate_action_cancel() {
+        let manager = StrikeOrderBookManager::new("AAPL", test_expiration());
+
+        let strike = manager.get_or_create(200);
+        strike
+            .call()
+            .add_limit_order(OrderId::new(), Side::Buy, 10, 5)
+            .unwrap();
+        drop(manager.get_or_create(220));
+
+        let action = CorporateAction::split(2, 1).with_symbol_suffix("1");
+        let report = manager.apply_corporate_action(&action).unwrap();
+
+        assert_eq!(report.strikes_adjusted, 2);
+        assert_eq!(report.orders_cancelled, 1);
+        assert_eq!(report.strike_map, vec![(200, 100), (220, 110)]);
+        assert_eq!(manager.strike_prices(), vec![100, 110]);
+        assert!(strike.is_empty());
+
+        let adjusted = manager.get(100).unwrap();
+        assert!(adjusted.is_empty());
+        assert!(adjusted.call().symbol().starts_with("AAPL1-"));
+        assert_eq!(adjusted.terms().adjustments, 1);
+    }
+
+    #[test]
+    fn test_strike_manager_corporate_action_preserve() {
+        let manager = StrikeOrderBookManager::new("AAPL", test_expiration());
+
+        let order_id = OrderId::new();
+        manager
+            .get_or_create(200)
+            .put()
+            .add_limit_order(order_id, Side::Sell, 30, 7)
+            .unwrap();
+
+        let action =
+            CorporateAction::split(2, 1).with_order_policy(OrderAdjustmentPolicy::Preserve);
+        let report = manager.apply_corporate_action(&action).unwrap();
+
+        assert_eq!(report.orders_migrated, 1);
+        let adjusted = manager.get(100).unwrap();
+        assert_eq!(adjusted.put().best_ask(), Some(15));
+        assert_eq!(adjusted.put().ask_depth_at_price(15), 7);
+        assert!(adjusted.put().cancel_order(order_id).unwrap());
+    }
+
+    #[test]
+    fn test_strike_manager_corporate_action_reverse_split() {
+        let manager = StrikeOrderBookManager::new("AAPL", test_expiration());
+        manager
+            .get_or_create(50)
+            .call()
+            .add_limit_order(OrderId::new(), Side::Buy, 10, 1)
+            .unwrap();
+        manager
+            .get_or_create(100)
+            .call()
+            .add_limit_order(OrderId::new(), Side::Buy, 20, 2)
+            .unwrap();
+
+        let action =
+            CorporateAction::split(1, 2).with_order_policy(OrderAdjustmentPolicy::Preserve);
+        let report = manager.apply_corporate_action(&action).unwrap();
+
+        // 50 -> 100 takes the price of the original 100 -> 200.
+        assert_eq!(report.strike_map, vec![(50, 100), (100, 200)]);
+        assert_eq!(manager.strike_prices(), vec![100, 200]);
+        assert_eq!(manager.get(100).unwrap().call().bid_depth_at_price(20), 1);
+        assert_eq!(manager.get(200).unwrap().call().bid_depth_at_price(40), 2);
+    }
+
+    #[test]
+    fn test_strike_manager_corporate_action_keeps_order_types() {
+        use orderbook_rs::TimeInForce;
+
+        let manager = StrikeOrderBookManager::new("AAPL", test_expiration());
+        let strike = manager.get_or_create(200);
+        let put = strike.put();
+        let iceberg = OrderId::new();
+        put.inner()
+            .add_iceberg_order(iceberg, 30, 2, 8, Side::Sell, TimeInForce::Gtc, None)
+            .unwrap();
+        put.inner()
+            .add_post_only_order(OrderId::new(), 20, 3, Side::Buy, TimeInForce::Gtc, None)
+            .unwrap();
+
+        let action =
+            CorporateAction::split(2, 1).with_order_policy(OrderAdjustmentPolicy::Preserve);
+        let report = manager.apply_corporate_action(&action).unwrap();
+
+        assert_eq!((report.orders_migrated, report.orders_cancelled), (2, 0));
+        let adjusted = manager.get(100).unwrap();
+        // The hidden size stays hidden.
+        let order = adjusted.put().inner().get_order(iceberg).unwrap();
+        assert!(matches!(*order, OrderType::IcebergOrder { .. }));
+        assert_eq!((order.visible_quantity(), order.hidden_quantity()), (2, 8));
+        assert!(
+            adjusted
+                .put()
+                .inner()
+                .get_all_orders()
+                .iter()
+                .any(|o| o.is_post_only())
+        );
+    }
+
+    #[test]
+    fn test_strike_manager_corporate_action_invalid() {
+        let manager = StrikeOrderBookManager::new("AAPL", test_expiration());
+        drop(manager.get_or_create(100));
+        drop(manager.get_or_create(300));
+
+        let action = CorporateAction::special_dividend(200);
+        assert!(manager.apply_corporate_action(&action).is_err());
+        assert_eq!(manager.strike_prices(), vec![100, 300]);
+    }
+
     #[test]
     fn test_strike_manager_total_order_count() {
         let manager = StrikeOrderBookManager::new("BTC", test_expiration());
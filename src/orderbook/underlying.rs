@@ -3,11 +3,24 @@
 //! This module provides the [`UnderlyingOrderBook`] and [`UnderlyingOrderBookManager`]
 //! for managing all underlyings in the system.
 
+use super::activity::{ActivitySummary, OpenInterestRecord};
+use super::corporate_action::{CorporateAction, CorporateActionReport};
 use super::expiration::{ExpirationOrderBook, ExpirationOrderBookManager};
+use super::exposure::{AggregatedGreeks, ExposureWeights};
+use super::greeks::GreeksRefreshReport;
+use super::market_data::MarketData;
+use super::mass_quote::{MassQuote, MassQuoteAck, MassQuoteReport};
+use super::reference::{ReferencePrice, ReferencePriceFeed, ReferencePriceKind};
+use crate::analytics::{
+    ArbitrageReport, ExpiryVariance, TermStructure, VolatilityIndex, scan_calendar,
+};
 use crate::error::{Error, Result};
+use crate::pricing::{RateCurves, VolatilityProvider, VolatilitySurface, YieldCurve};
 use crossbeam_skiplist::SkipMap;
-use optionstratlib::ExpirationDate;
-use std::sync::Arc;
+use optionstratlib::{ExpirationDate, OptionStyle};
+use orderbook_rs::DefaultOrderBook;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 /// Order book for a single underlying asset.
 ///
@@ -17,6 +30,7 @@ use std::sync::Arc;
 ///
 /// ```text
 /// UnderlyingOrderBook (per underlying)
+///   ├── OrderBook<T> (optional, the underlying instrument itself)
 ///   └── ExpirationOrderBookManager
 ///         └── ExpirationOrderBook (per expiry)
 ///               └── OptionChainOrderBook
@@ -27,6 +41,10 @@ pub struct UnderlyingOrderBook {
     underlying: String,
     /// Expiration order book manager.
     expirations: ExpirationOrderBookManager,
+    /// Order book for the underlying instrument (spot, perpetual or future).
+    instrument: OnceLock<Arc<DefaultOrderBook>>,
+    /// Market data (reference prices and curves) shared with every expiration.
+    market: Arc<MarketData>,
 }
 
 impl UnderlyingOrderBook {
@@ -38,10 +56,13 @@ impl UnderlyingOrderBook {
     #[must_use]
     pub fn new(underlying: impl Into<String>) -> Self {
         let underlying = underlying.into();
+        let market = Arc::new(MarketData::new());
 
         Self {
-            expirations: ExpirationOrderBookManager::new(&underlying),
+            expirations: ExpirationOrderBookManager::with_market(&underlying, Arc::clone(&market)),
             underlying,
+            instrument: OnceLock::new(),
+            market,
         }
     }
 
@@ -57,6 +78,131 @@ impl UnderlyingOrderBook {
         &self.expirations
     }
 
+    /// Gets or creates the order book of the underlying instrument.
+    ///
+    /// The instrument book uses the underlying symbol and lives alongside the
+    /// option tree, so hedging orders can be placed in the same hierarchy.
+    pub fn get_or_create_instrument_book(&self) -> Arc<DefaultOrderBook> {
+        Arc::clone(
+            self.instrument
+                .get_or_init(|| Arc::new(DefaultOrderBook::new(&self.underlying))),
+        )
+    }
+
+    /// Returns the order book of the underlying instrument, if created.
+    #[must_use]
+    pub fn instrument_book(&self) -> Option<Arc<DefaultOrderBook>> {
+        self.instrument.get().map(Arc::clone)
+    }
+
+    /// Returns the spot price of the underlying.
+    ///
+    /// Uses the reference price when it is not stale, otherwise derives the
+    /// price from the underlying instrument book (mid price when both sides
+    /// are quoted, then last trade price).
+    #[must_use]
+    pub fn spot_price(&self) -> Option<u64> {
+        self.market
+            .reference()
+            .price()
+            .or_else(|| self.instrument_book_price())
+    }
+
+    /// Returns the price derived from the underlying instrument book.
+    fn instrument_book_price(&self) -> Option<u64> {
+        let book = self.instrument.get()?;
+        match book.mid_price() {
+            Some(mid) => Some(mid.round() as u64),
+            None => book.last_trade_price().and_then(|p| u64::try_from(p).ok()),
+        }
+    }
+
+    /// Returns the reference price feed shared with every expiration.
+    #[must_use]
+    pub fn reference(&self) -> &ReferencePriceFeed {
+        self.market.reference()
+    }
+
+    /// Returns the market data shared with every expiration.
+    #[must_use]
+    pub fn market(&self) -> &MarketData {
+        &self.market
+    }
+
+    /// Returns an Arc reference to the market data.
+    #[must_use]
+    pub fn market_arc(&self) -> Arc<MarketData> {
+        Arc::clone(&self.market)
+    }
+
+    /// Returns a copy of the rate and dividend curves of the underlying.
+    #[must_use]
+    pub fn rate_curves(&self) -> RateCurves {
+        self.market.curves()
+    }
+
+    /// Replaces the rate and dividend curves of the underlying.
+    pub fn set_rate_curves(&self, curves: RateCurves) {
+        self.market.set_curves(curves);
+    }
+
+    /// Replaces the risk-free rate curve of the underlying.
+    pub fn set_rate_curve(&self, curve: YieldCurve) {
+        self.market.set_rate_curve(curve);
+    }
+
+    /// Replaces the dividend or borrow yield curve of the underlying.
+    pub fn set_dividend_curve(&self, curve: YieldCurve) {
+        self.market.set_dividend_curve(curve);
+    }
+
+    /// Updates a reference price of the underlying at the current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Index, mark or last
+    /// * `price` - The new price, in strike units
+    pub fn update_reference_price(&self, kind: ReferencePriceKind, price: u64) {
+        self.market.reference().update(kind, price);
+    }
+
+    /// Returns the latest reference prices, if any price was published.
+    #[must_use]
+    pub fn reference_price(&self) -> Option<ReferencePrice> {
+        self.market.reference().snapshot()
+    }
+
+    /// Publishes the price of the underlying instrument book as the mark
+    /// price of the reference feed, returning the published price.
+    pub fn update_reference_from_instrument_book(&self) -> Option<u64> {
+        let price = self.instrument_book_price()?;
+        self.market
+            .reference()
+            .update(ReferencePriceKind::Mark, price);
+        Some(price)
+    }
+
+    /// Returns the ATM strike of an expiration.
+    ///
+    /// Uses the price of the future linked to the expiration when there is
+    /// one, otherwise the spot price derived from the underlying instrument
+    /// book.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ExpirationNotFound` if the expiration does not exist,
+    /// `Error::MarketDataError` if no price is available, or
+    /// `Error::NoDataAvailable` if the expiration has no strikes.
+    pub fn atm_strike(&self, expiration: &ExpirationDate) -> Result<u64> {
+        let exp = self.expirations.get(expiration)?;
+        let price = exp
+            .chain()
+            .underlying_price()
+            .or_else(|| self.spot_price())
+            .ok_or_else(|| Error::market_data(format!("no spot price for {}", self.underlying)))?;
+        exp.atm_strike(price)
+    }
+
     /// Gets or creates an expiration order book, returning an Arc reference.
     pub fn get_or_create_expiration(&self, expiration: ExpirationDate) -> Arc<ExpirationOrderBook> {
         self.expirations.get_or_create(expiration)
@@ -95,6 +241,205 @@ impl UnderlyingOrderBook {
         self.expirations.total_strike_count()
     }
 
+    /// Returns the session activity and open interest across all
+    /// expirations.
+    #[must_use]
+    pub fn activity_summary(&self) -> ActivitySummary {
+        self.expirations.activity_summary()
+    }
+
+    /// Starts a new trading session for every contract and returns the
+    /// activity of the closed one.
+    pub fn roll_activity(&self) -> ActivitySummary {
+        self.expirations.roll_activity()
+    }
+
+    /// Applies a participant's mass quote across the expirations of this
+    /// underlying.
+    ///
+    /// Entries are routed to their expiration's chain one at a time, so the
+    /// update is per entry and not atomic; entries of an unlisted
+    /// expiration are rejected. Acknowledgements keep the order of the
+    /// entries. Entries are placed directly, without pre-trade risk checks;
+    /// checked quotes are applied with
+    /// [`RiskEngine::apply_mass_quote`](crate::risk::RiskEngine::apply_mass_quote)
+    /// instead.
+    pub fn apply_mass_quote(&self, quote: &MassQuote) -> MassQuoteReport {
+        let acks = quote
+            .entries
+            .iter()
+            .map(|entry| {
+                let result = self
+                    .get_expiration(&entry.expiration)
+                    .and_then(|expiration| {
+                        expiration
+                            .chain()
+                            .apply_mass_quote_entry(&quote.participant, entry)
+                    });
+                MassQuoteAck::new(entry, result)
+            })
+            .collect();
+        MassQuoteReport::new(quote.participant.clone(), acks)
+    }
+
+    /// Sets the open interest of listed contracts from exchange records.
+    ///
+    /// Records are matched by contract symbol; records of unlisted
+    /// contracts are ignored. Returns the number of contracts updated.
+    pub fn load_open_interest(&self, records: &[OpenInterestRecord]) -> usize {
+        let open_interest: HashMap<&str, u64> = records
+            .iter()
+            .map(|r| (r.symbol.as_str(), r.open_interest))
+            .collect();
+        let mut updated = 0;
+        for expiration in self.expirations.iter() {
+            for strike in expiration.value().chain().strikes().iter() {
+                for option_style in [OptionStyle::Call, OptionStyle::Put] {
+                    let book = strike.value().get(option_style);
+                    if let Some(&value) = open_interest.get(book.symbol()) {
+                        book.set_open_interest(value);
+                        updated += 1;
+                    }
+                }
+            }
+        }
+        updated
+    }
+
+    /// Sets the open interest of listed contracts from a JSON array of
+    /// [`OpenInterestRecord`]s, e.g. an exchange end-of-day file.
+    ///
+    /// Returns the number of contracts updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SerializationError` if the input is not a valid
+    /// record array.
+    pub fn load_open_interest_json(&self, reader: impl std::io::Read) -> Result<usize> {
+        let records: Vec<OpenInterestRecord> = serde_json::from_reader(reader)?;
+        Ok(self.load_open_interest(&records))
+    }
+
+    /// Applies a corporate action to every listed contract of this underlying.
+    ///
+    /// Every `StrikeOrderBook` of every expiration is re-keyed at its
+    /// adjusted strike with adjusted contract terms and symbols, and resting
+    /// orders are migrated or cancelled according to the action's
+    /// [`OrderAdjustmentPolicy`](super::OrderAdjustmentPolicy).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the action is invalid or cannot be
+    /// applied to the listed strikes. No contract is modified on error.
+    pub fn apply_corporate_action(
+        &self,
+        action: &CorporateAction,
+    ) -> Result<CorporateActionReport> {
+        self.expirations.apply_corporate_action(action)
+    }
+
+    /// Computes and stores the Greeks of every contract of this underlying
+    /// from its reference price and rate curves.
+    ///
+    /// Contracts left unchanged are listed in the report's `skipped`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered (e.g., no underlying price).
+    pub fn refresh_greeks(
+        &self,
+        volatilities: &dyn VolatilityProvider,
+    ) -> Result<GreeksRefreshReport> {
+        self.expirations.refresh_greeks(volatilities)
+    }
+
+    /// Returns the Greeks of every contract of this underlying aggregated
+    /// with the given weights.
+    #[must_use]
+    pub fn aggregate_greeks(&self, weights: &ExposureWeights<'_>) -> AggregatedGreeks {
+        self.expirations.aggregate_greeks(weights)
+    }
+
+    /// Scans the resting quotes of every expiration for executable static
+    /// arbitrages, including calendars between consecutive expirations.
+    ///
+    /// See [`OptionChainOrderBook::scan_arbitrage`](super::OptionChainOrderBook::scan_arbitrage).
+    #[must_use]
+    pub fn scan_arbitrage(&self) -> ArbitrageReport {
+        let mut chains: Vec<(f64, Arc<ExpirationOrderBook>)> = self
+            .expirations
+            .iter()
+            .map(|entry| {
+                let years = entry
+                    .key()
+                    .get_years()
+                    .map_or(f64::INFINITY, |y| y.to_f64());
+                (years, Arc::clone(entry.value()))
+            })
+            .collect();
+        chains.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut opportunities: Vec<_> = chains
+            .iter()
+            .flat_map(|(_, exp)| exp.chain().scan_arbitrage())
+            .collect();
+        for pair in chains.windows(2) {
+            opportunities.extend(scan_calendar(pair[0].1.chain(), pair[1].1.chain()));
+        }
+        ArbitrageReport::new(&self.underlying, opportunities)
+    }
+
+    /// Computes a model-free volatility index at a constant maturity with
+    /// the CBOE VIX methodology (e.g., 30 days).
+    ///
+    /// Expirations whose variance cannot be computed are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if no expiration has a variance, or
+    /// an error if the constant maturity cannot be converted to years.
+    pub fn volatility_index(&self, constant_maturity: &ExpirationDate) -> Result<VolatilityIndex> {
+        let variances = self
+            .expirations
+            .iter()
+            .filter_map(|entry| ExpiryVariance::from_chain(entry.value().chain()).ok())
+            .collect();
+        VolatilityIndex::from_variances(
+            &self.underlying,
+            variances,
+            constant_maturity.get_years()?.to_f64(),
+        )
+    }
+
+    /// Returns the ATM volatility, risk reversal and butterfly of every
+    /// expiration.
+    ///
+    /// See [`ExpirationOrderBookManager::term_structure`].
+    #[must_use]
+    pub fn term_structure(&self) -> TermStructure {
+        self.expirations.term_structure()
+    }
+
+    /// Fits a volatility surface to the current quotes of every expiration
+    /// and stores it in the shared market data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if no expiration has enough
+    /// invertible quotes to fit a slice.
+    pub fn refresh_volatility_surface(&self) -> Result<Arc<VolatilitySurface>> {
+        let smiles = self.expirations.volatility_smiles();
+        let surface = Arc::new(VolatilitySurface::from_smiles(&self.underlying, &smiles)?);
+        self.market.set_volatility_surface(Arc::clone(&surface));
+        Ok(surface)
+    }
+
+    /// Returns the last fitted volatility surface, if any.
+    #[must_use]
+    pub fn volatility_surface(&self) -> Option<Arc<VolatilitySurface>> {
+        self.market.volatility_surface()
+    }
+
     /// Returns statistics about this underlying.
     #[must_use]
     pub fn stats(&self) -> UnderlyingStats {
@@ -254,6 +599,53 @@ impl UnderlyingOrderBookManager {
             .sum()
     }
 
+    /// Returns the session activity and open interest across all
+    /// underlyings.
+    #[must_use]
+    pub fn activity_summary(&self) -> ActivitySummary {
+        self.underlyings
+            .iter()
+            .fold(ActivitySummary::default(), |mut summary, entry| {
+                summary.merge(&entry.value().activity_summary());
+                summary
+            })
+    }
+
+    /// Starts a new trading session for every underlying and returns the
+    /// activity of the closed one.
+    pub fn roll_activity(&self) -> ActivitySummary {
+        self.underlyings
+            .iter()
+            .fold(ActivitySummary::default(), |mut summary, entry| {
+                summary.merge(&entry.value().roll_activity());
+                summary
+            })
+    }
+
+    /// Sets the open interest of listed contracts of every underlying from
+    /// exchange records. Returns the number of contracts updated.
+    pub fn load_open_interest(&self, records: &[OpenInterestRecord]) -> usize {
+        self.underlyings
+            .iter()
+            .map(|entry| entry.value().load_open_interest(records))
+            .sum()
+    }
+
+    /// Returns the Greeks of every contract across all underlyings
+    /// aggregated with the given weights.
+    ///
+    /// Unit Greeks add up across underlyings as quantities; the cash
+    /// measures are the meaningful cross-underlying totals.
+    #[must_use]
+    pub fn aggregate_greeks(&self, weights: &ExposureWeights<'_>) -> AggregatedGreeks {
+        self.underlyings
+            .iter()
+            .fold(AggregatedGreeks::default(), |mut aggregate, entry| {
+                aggregate.merge(&entry.value().aggregate_greeks(weights));
+                aggregate
+            })
+    }
+
     /// Returns statistics about the entire order book system.
     #[must_use]
     pub fn stats(&self) -> GlobalStats {
@@ -293,7 +685,7 @@ impl std::fmt::Display for GlobalStats {
 mod tests {
     use super::*;
     use optionstratlib::prelude::pos_or_panic;
-    use orderbook_rs::{OrderId, Side};
+    use orderbook_rs::{OrderId, Side, TimeInForce};
 
     fn test_expiration() -> ExpirationDate {
         ExpirationDate::Days(pos_or_panic!(30.0))
@@ -338,6 +730,496 @@ mod tests {
         assert!(missing.is_err());
     }
 
+    #[test]
+    fn test_underlying_order_book_corporate_action() {
+        use crate::orderbook::CorporateAction;
+
+        let book = UnderlyingOrderBook::new("AAPL");
+        let near = book.get_or_create_expiration(test_expiration());
+        let far = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(60.0)));
+        near.get_or_create_strike(200)
+            .call()
+            .add_limit_order(OrderId::new(), Side::Buy, 10, 1)
+            .unwrap();
+        far.get_or_create_strike(210);
+        far.get_or_create_strike(230);
+
+        let report = book
+            .apply_corporate_action(&CorporateAction::special_dividend(10))
+            .unwrap();
+
+        assert_eq!(report.expirations_adjusted, 2);
+        assert_eq!(report.strikes_adjusted, 3);
+        assert_eq!(report.orders_cancelled, 1);
+        assert_eq!(near.strike_prices(), vec![190]);
+        assert_eq!(far.strike_prices(), vec![200, 220]);
+        assert_eq!(book.total_order_count(), 0);
+    }
+
+    #[test]
+    fn test_underlying_order_book_corporate_action_is_atomic() {
+        use crate::orderbook::CorporateAction;
+
+        let book = UnderlyingOrderBook::new("AAPL");
+        book.get_or_create_expiration(test_expiration())
+            .get_or_create_strike(500);
+        book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(60.0)))
+            .get_or_create_strike(50);
+
+        let result = book.apply_corporate_action(&CorporateAction::special_dividend(100));
+        assert!(result.is_err());
+
+        let exp = book.get_expiration(&test_expiration()).unwrap();
+        assert_eq!(exp.strike_prices(), vec![500]);
+    }
+
+    #[test]
+    fn test_underlying_instrument_book() {
+        let book = UnderlyingOrderBook::new("BTC");
+        assert!(book.instrument_book().is_none());
+        assert!(book.spot_price().is_none());
+
+        let spot = book.get_or_create_instrument_book();
+        assert_eq!(spot.symbol(), "BTC");
+        spot.add_limit_order(OrderId::new(), 49990, 1, Side::Buy, TimeInForce::Gtc, None)
+            .unwrap();
+        spot.add_limit_order(OrderId::new(), 50010, 1, Side::Sell, TimeInForce::Gtc, None)
+            .unwrap();
+
+        assert!(book.instrument_book().is_some());
+        assert_eq!(book.spot_price(), Some(50000));
+        assert_eq!(book.get_or_create_instrument_book().best_bid(), Some(49990));
+    }
+
+    #[test]
+    fn test_underlying_atm_strike_from_instrument_book() {
+        let book = UnderlyingOrderBook::new("BTC");
+        let exp_date = test_expiration();
+        let exp = book.get_or_create_expiration(exp_date);
+        exp.get_or_create_strike(45000);
+        exp.get_or_create_strike(50000);
+        exp.get_or_create_strike(55000);
+
+        assert!(book.atm_strike(&exp_date).is_err());
+
+        let spot = book.get_or_create_instrument_book();
+        spot.add_limit_order(OrderId::new(), 53000, 1, Side::Buy, TimeInForce::Gtc, None)
+            .unwrap();
+        spot.add_limit_order(OrderId::new(), 53200, 1, Side::Sell, TimeInForce::Gtc, None)
+            .unwrap();
+
+        assert_eq!(book.atm_strike(&exp_date).unwrap(), 55000);
+        assert!(
+            book.atm_strike(&ExpirationDate::Days(pos_or_panic!(90.0)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_underlying_atm_strike_prefers_linked_future() {
+        use crate::orderbook::UnderlyingFuture;
+
+        let book = UnderlyingOrderBook::new("ES");
+        let exp_date = test_expiration();
+        let exp = book.get_or_create_expiration(exp_date);
+        exp.get_or_create_strike(4900);
+        exp.get_or_create_strike(5000);
+
+        let spot = book.get_or_create_instrument_book();
+        spot.add_limit_order(OrderId::new(), 4899, 1, Side::Buy, TimeInForce::Gtc, None)
+            .unwrap();
+        spot.add_limit_order(OrderId::new(), 4901, 1, Side::Sell, TimeInForce::Gtc, None)
+            .unwrap();
+        assert_eq!(book.atm_strike(&exp_date).unwrap(), 4900);
+
+        exp.link_future(UnderlyingFuture::new("ESH5"))
+            .unwrap()
+            .set_reference_price(4990);
+        assert_eq!(book.atm_strike(&exp_date).unwrap(), 5000);
+    }
+
+    #[test]
+    fn test_underlying_reference_price_feed() {
+        let book = UnderlyingOrderBook::new("BTC");
+        let exp_date = test_expiration();
+        let exp = book.get_or_create_expiration(exp_date);
+        exp.get_or_create_strike(45000);
+        exp.get_or_create_strike(50000);
+        exp.get_or_create_strike(55000);
+
+        assert!(book.reference_price().is_none());
+        assert!(exp.current_atm_strike().is_err());
+
+        book.update_reference_price(ReferencePriceKind::Index, 54000);
+
+        assert_eq!(book.reference_price().unwrap().index, Some(54000));
+        assert_eq!(book.spot_price(), Some(54000));
+        assert_eq!(exp.current_atm_strike().unwrap(), 55000);
+        assert_eq!(book.atm_strike(&exp_date).unwrap(), 55000);
+
+        let late = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(60.0)));
+        late.get_or_create_strike(50000);
+        assert_eq!(late.current_atm_strike().unwrap(), 50000);
+    }
+
+    #[test]
+    fn test_underlying_reference_from_instrument_book() {
+        let book = UnderlyingOrderBook::new("BTC");
+        assert!(book.update_reference_from_instrument_book().is_none());
+
+        let spot = book.get_or_create_instrument_book();
+        spot.add_limit_order(OrderId::new(), 100, 1, Side::Buy, TimeInForce::Gtc, None)
+            .unwrap();
+        spot.add_limit_order(OrderId::new(), 102, 1, Side::Sell, TimeInForce::Gtc, None)
+            .unwrap();
+
+        assert_eq!(book.update_reference_from_instrument_book(), Some(101));
+        assert_eq!(book.reference_price().unwrap().mark, Some(101));
+        assert!(!book.reference().is_stale());
+    }
+
+    #[test]
+    fn test_underlying_rate_curves_shared_with_chains() {
+        use crate::pricing::CurvePoint;
+
+        let book = UnderlyingOrderBook::new("SPX");
+        let near = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(182.5)));
+        let far = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(365.0)));
+
+        assert_eq!(near.chain().risk_free_rate().unwrap(), 0.0);
+
+        book.set_rate_curve(
+            YieldCurve::piecewise(vec![CurvePoint::new(0.5, 0.04), CurvePoint::new(1.0, 0.05)])
+                .unwrap(),
+        );
+        book.set_dividend_curve(YieldCurve::flat(0.015));
+
+        assert!((near.chain().risk_free_rate().unwrap() - 0.04).abs() < 1e-9);
+        assert!((far.chain().risk_free_rate().unwrap() - 0.05).abs() < 1e-9);
+        assert_eq!(far.chain().dividend_yield().unwrap(), 0.015);
+        assert_eq!(book.rate_curves().dividend, YieldCurve::flat(0.015));
+    }
+
+    #[test]
+    fn test_underlying_refresh_volatility_surface() {
+        use crate::pricing::theoretical_price;
+        use optionstratlib::OptionStyle;
+
+        let book = UnderlyingOrderBook::new("SPX");
+        book.update_reference_price(ReferencePriceKind::Index, 1000);
+        assert!(book.refresh_volatility_surface().is_err());
+        assert!(book.volatility_surface().is_none());
+
+        for days in [30.0, 91.25] {
+            let exp = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(days)));
+            for strike in [900, 950, 1000, 1050, 1100] {
+                let option_style = if strike < 1000 {
+                    OptionStyle::Put
+                } else {
+                    OptionStyle::Call
+                };
+                let vol = 0.2 + (1000.0 - strike as f64).abs() / 1000.0;
+                let inputs = exp.chain().greeks_inputs(vol).unwrap();
+                let fair = theoretical_price(strike, option_style, &inputs).unwrap();
+                let strike_book = exp.get_or_create_strike(strike);
+                let contract = strike_book.get(option_style);
+                contract
+                    .add_limit_order(OrderId::new(), Side::Buy, fair.floor() as u128, 1)
+                    .unwrap();
+                contract
+                    .add_limit_order(OrderId::new(), Side::Sell, fair.ceil() as u128, 1)
+                    .unwrap();
+            }
+        }
+
+        let surface = book.refresh_volatility_surface().unwrap();
+        assert_eq!(surface.slices.len(), 2);
+        assert!(surface.skipped.is_empty());
+        assert!(book.volatility_surface().is_some());
+
+        let expiration = ExpirationDate::Days(pos_or_panic!(91.25));
+        let atm = surface.volatility(&expiration, 1000.0).unwrap();
+        let wing = surface.volatility(&expiration, 900.0).unwrap();
+        assert!(wing > atm);
+        assert!((atm - 0.2).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_underlying_scan_arbitrage() {
+        use crate::analytics::ArbitrageKind;
+        use optionstratlib::OptionStyle;
+
+        let book = UnderlyingOrderBook::new("BTC");
+        book.update_reference_price(ReferencePriceKind::Index, 100);
+        let quote = |days: f64, strike: u64, bid: u128, ask: u128| {
+            let exp = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(days)));
+            let call = exp.get_or_create_strike(strike).get_arc(OptionStyle::Call);
+            call.add_limit_order(OrderId::new(), Side::Buy, bid, 2)
+                .unwrap();
+            call.add_limit_order(OrderId::new(), Side::Sell, ask, 2)
+                .unwrap();
+        };
+        quote(60.0, 100, 4, 5);
+        quote(30.0, 100, 6, 7);
+        quote(30.0, 110, 1, 2);
+
+        let report = book.scan_arbitrage();
+        assert_eq!(report.underlying, "BTC");
+        assert_eq!(report.len(), 1);
+        let calendar = report.of_kind(ArbitrageKind::Calendar).next().unwrap();
+        assert_eq!(calendar.legs[0].side, Side::Sell);
+        assert_eq!(calendar.legs[0].price, 6);
+        assert_eq!(calendar.legs[1].price, 5);
+        assert!((report.total_profit() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_underlying_volatility_index() {
+        use crate::pricing::theoretical_price;
+        use optionstratlib::OptionStyle;
+
+        let book = UnderlyingOrderBook::new("BTC");
+        let constant_maturity = ExpirationDate::Days(pos_or_panic!(30.0));
+        assert!(book.volatility_index(&constant_maturity).is_err());
+
+        book.update_reference_price(ReferencePriceKind::Index, 10_000);
+        book.set_rate_curve(YieldCurve::flat(0.03));
+        for (days, vol) in [(20.0, 0.5), (40.0, 0.6)] {
+            let exp = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(days)));
+            let inputs = exp.chain().greeks_inputs(vol).unwrap();
+            for strike in (2_000..=30_000).step_by(250) {
+                for option_style in [OptionStyle::Call, OptionStyle::Put] {
+                    let fair = theoretical_price(strike, option_style, &inputs).unwrap();
+                    let bid = fair.floor() as u128;
+                    let contract = exp.get_or_create_strike(strike).get_arc(option_style);
+                    if bid > 0 {
+                        contract
+                            .add_limit_order(OrderId::new(), Side::Buy, bid, 1)
+                            .unwrap();
+                    }
+                    contract
+                        .add_limit_order(OrderId::new(), Side::Sell, bid + 1, 1)
+                        .unwrap();
+                }
+            }
+        }
+
+        let index = book.volatility_index(&constant_maturity).unwrap();
+        let near = &index.near;
+        assert!((near.forward - 10_000.0 * (0.03 * near.years_to_expiry).exp()).abs() < 2.0);
+        assert_eq!(near.atm_strike, 10_000);
+        assert!((near.variance.sqrt() - 0.5).abs() < 0.01);
+        // Total variance interpolation: (20 * 0.25 + 40 * 0.36) / 2 / 30.
+        let expected = ((20.0 * 0.25 + 40.0 * 0.36) / 2.0 / 30.0f64).sqrt() * 100.0;
+        assert!((index.value - expected).abs() < 1.0, "{}", index.value);
+    }
+
+    #[test]
+    fn test_underlying_term_structure() {
+        use crate::pricing::theoretical_price;
+        use optionstratlib::OptionStyle;
+
+        let book = UnderlyingOrderBook::new("SPX");
+        book.update_reference_price(ReferencePriceKind::Index, 1000);
+        for days in [30.0, 91.25] {
+            let exp = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(days)));
+            for strike in (800..=1200).step_by(25) {
+                let option_style = if strike < 1000 {
+                    OptionStyle::Put
+                } else {
+                    OptionStyle::Call
+                };
+                // Downside skew: 2 vol points per 100 strikes below the money.
+                let vol = 0.2 + 0.2 * (1000.0 - strike as f64) / 1000.0;
+                let inputs = exp.chain().greeks_inputs(vol).unwrap();
+                let fair = theoretical_price(strike, option_style, &inputs).unwrap();
+                let contract = exp.get_or_create_strike(strike).get_arc(option_style);
+                contract
+                    .add_limit_order(OrderId::new(), Side::Buy, fair.floor() as u128, 1)
+                    .unwrap();
+                contract
+                    .add_limit_order(OrderId::new(), Side::Sell, fair.ceil() as u128, 1)
+                    .unwrap();
+            }
+        }
+        let unquoted = ExpirationDate::Days(pos_or_panic!(182.5));
+        drop(
+            book.get_or_create_expiration(unquoted)
+                .get_or_create_strike(1000),
+        );
+
+        let term = book.term_structure();
+        assert_eq!(term.underlying, "SPX");
+        assert_eq!(term.len(), 2);
+        assert_eq!(term.skipped, vec![unquoted]);
+        let front = &term.expirations[0];
+        assert!(front.years_to_expiry < term.expirations[1].years_to_expiry);
+        for metrics in &term.expirations {
+            assert!((metrics.atm_volatility - 0.2).abs() < 0.02);
+            assert!(metrics.risk_reversal < 0.0);
+            assert!(metrics.put_strike < 1000.0 && metrics.call_strike > 1000.0);
+        }
+        let exp = book
+            .get_expiration(&ExpirationDate::Days(pos_or_panic!(30.0)))
+            .unwrap();
+        assert_eq!(exp.volatility_metrics().unwrap(), *front);
+    }
+
+    #[test]
+    fn test_underlying_refresh_greeks() {
+        use crate::pricing::{FlatVolatility, StrikeVolatility};
+        use optionstratlib::OptionStyle;
+        use rust_decimal::Decimal;
+
+        let book = UnderlyingOrderBook::new("SPX");
+        let expiration = ExpirationDate::Days(pos_or_panic!(90.0));
+        let exp = book.get_or_create_expiration(expiration);
+        let atm = exp.get_or_create_strike(5000);
+        let otm = exp.get_or_create_strike(5500);
+
+        assert!(book.refresh_greeks(&FlatVolatility(0.2)).is_err());
+
+        book.update_reference_price(ReferencePriceKind::Index, 5000);
+        book.set_rate_curve(YieldCurve::flat(0.04));
+        let report = book.refresh_greeks(&FlatVolatility(0.2)).unwrap();
+        assert_eq!(report.updated, 4);
+        assert!(report.skipped.is_empty());
+
+        let call = atm.greeks_snapshot(OptionStyle::Call).unwrap();
+        assert!(call.greeks.delta > Decimal::new(5, 1));
+        let inputs = call.inputs.unwrap();
+        assert_eq!(inputs.underlying_price, 5000);
+        assert_eq!(inputs.risk_free_rate, 0.04);
+        assert!(otm.call_greeks().unwrap().delta < call.greeks.delta);
+
+        let mut vols = StrikeVolatility::new();
+        vols.insert(expiration, 5500, 0.3);
+        assert_eq!(exp.chain().refresh_strike_greeks(5500, &vols).unwrap(), 2);
+        assert!(exp.chain().refresh_strike_greeks(4000, &vols).is_err());
+        let inputs = otm.greeks_snapshot(OptionStyle::Put).unwrap().inputs;
+        assert_eq!(inputs.unwrap().volatility, 0.3);
+
+        // A contract that fails to price is skipped, not the whole chain.
+        vols.insert(expiration, 5500, 0.0);
+        let vols = vols.with_fallback(0.25);
+        let report = book.refresh_greeks(&vols).unwrap();
+        assert_eq!(report.updated, 2);
+        assert_eq!(
+            report.skipped,
+            vec![
+                (expiration, 5500, OptionStyle::Call),
+                (expiration, 5500, OptionStyle::Put)
+            ]
+        );
+        let inputs = atm.greeks_snapshot(OptionStyle::Call).unwrap().inputs;
+        assert_eq!(inputs.unwrap().volatility, 0.25);
+        assert!(exp.chain().refresh_strike_greeks(5500, &vols).is_err());
+        let inputs = otm.greeks_snapshot(OptionStyle::Put).unwrap().inputs;
+        assert_eq!(inputs.unwrap().volatility, 0.3);
+    }
+
+    #[test]
+    fn test_activity_across_hierarchy() {
+        use crate::orderbook::PositionEffect::{Close, Open};
+
+        let manager = UnderlyingOrderBookManager::new();
+        let book = manager.get_or_create("BTC");
+        let near = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(7.0)));
+        let far = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(30.0)));
+        let near_strike = near.get_or_create_strike(50_000);
+        let far_strike = far.get_or_create_strike(60_000);
+
+        let json = format!(
+            r#"[{{"symbol":"{}","open_interest":100}},{{"symbol":"{}","open_interest":40}},{{"symbol":"ETH-X","open_interest":7}}]"#,
+            near_strike.call().symbol(),
+            far_strike.put().symbol()
+        );
+        assert_eq!(book.load_open_interest_json(json.as_bytes()).unwrap(), 2);
+        assert!(book.load_open_interest_json("not json".as_bytes()).is_err());
+
+        let cross = |book: &crate::orderbook::OptionOrderBook, price, quantity| {
+            book.add_limit_order(OrderId::new(), Side::Sell, price, quantity)
+                .unwrap();
+            book.add_limit_order(OrderId::new(), Side::Buy, price, quantity)
+                .unwrap();
+        };
+        cross(near_strike.call(), 500, 10);
+        cross(far_strike.put(), 800, 5);
+        near_strike
+            .call()
+            .apply_position_effects(10, Open, Open)
+            .unwrap();
+        far_strike
+            .put()
+            .apply_position_effects(5, Close, Close)
+            .unwrap();
+
+        let strike = near_strike.activity_summary();
+        assert_eq!(strike.call_open_interest, 110);
+        assert_eq!(near.activity_summary(), strike);
+
+        let summary = manager.activity_summary();
+        assert_eq!(summary, book.activity_summary());
+        assert_eq!(summary.volume, 15);
+        assert_eq!(summary.trade_count, 2);
+        assert_eq!(summary.open_interest, 145);
+        assert_eq!(summary.put_open_interest, 35);
+        assert_eq!(summary.active_contracts, 2);
+        assert!((summary.vwap().unwrap() - 9_000.0 / 15.0).abs() < 1e-9);
+
+        let closed = manager.roll_activity();
+        assert_eq!(closed.volume, 15);
+        let fresh = manager.activity_summary();
+        assert_eq!(fresh.volume, 0);
+        assert_eq!(fresh.open_interest, 145);
+        assert_eq!(near_strike.call().activity().last_price, Some(500));
+    }
+
+    #[test]
+    fn test_aggregate_greeks_across_hierarchy() {
+        use crate::orderbook::{ExposureWeights, PositionSet};
+        use crate::pricing::FlatVolatility;
+
+        let manager = UnderlyingOrderBookManager::new();
+        let mut positions = PositionSet::new();
+        for (symbol, spot) in [("SPX", 5000), ("NDX", 20000)] {
+            let book = manager.get_or_create(symbol);
+            book.update_reference_price(ReferencePriceKind::Index, spot);
+            for days in [30.0, 90.0] {
+                let exp = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(days)));
+                let strike = exp.get_or_create_strike(spot);
+                positions.set(strike.call().symbol(), 2);
+                positions.set(strike.put().symbol(), 2);
+            }
+            book.refresh_greeks(&FlatVolatility(0.2)).unwrap();
+        }
+        let weights = ExposureWeights::Positions(&positions);
+
+        let spx = manager.get("SPX").unwrap();
+        let exp = spx
+            .get_expiration(&ExpirationDate::Days(pos_or_panic!(30.0)))
+            .unwrap();
+        let chain = exp.aggregate_greeks(&weights);
+        assert_eq!(chain.contract_count, 2);
+        // A long straddle: deltas offset, gamma and vega add up.
+        assert!(chain.delta.abs() < 0.2);
+        assert!(chain.gamma > 0.0);
+        assert!((chain.cash_delta - chain.delta * 5000.0).abs() < 1e-6);
+
+        let underlying = spx.aggregate_greeks(&weights);
+        assert_eq!(underlying.contract_count, 4);
+        assert!(underlying.vega_per_point > chain.vega_per_point);
+
+        let total = manager.aggregate_greeks(&weights);
+        assert_eq!(total.contract_count, 8);
+        assert_eq!(total.missing_count, 0);
+        assert!(total.dollar_gamma > underlying.dollar_gamma);
+        assert_eq!(
+            manager.aggregate_greeks(&ExposureWeights::RestingBids),
+            AggregatedGreeks::default()
+        );
+    }
+
     #[test]
     fn test_underlying_manager_creation() {
         let manager = UnderlyingOrderBookManager::new();
@@ -504,4 +1386,36 @@ mod tests {
         assert!(display.contains("1 expirations"));
         assert!(display.contains("1 strikes"));
     }
+
+    #[test]
+    fn test_apply_mass_quote_across_expirations() {
+        use crate::orderbook::{MassQuote, QuoteLevel};
+
+        let book = UnderlyingOrderBook::new("BTC");
+        let near_date = ExpirationDate::Days(pos_or_panic!(7.0));
+        let far_date = ExpirationDate::Days(pos_or_panic!(30.0));
+        let near = book.get_or_create_expiration(near_date);
+        let far = book.get_or_create_expiration(far_date);
+        near.get_or_create_strike(50_000);
+        far.get_or_create_strike(50_000);
+
+        let bid = Some(QuoteLevel::new(100, 1));
+        let quote = MassQuote::new("MM1")
+            .with_entry(near_date, 50_000, OptionStyle::Call, bid, None)
+            .with_entry(
+                ExpirationDate::Days(pos_or_panic!(90.0)),
+                50_000,
+                OptionStyle::Call,
+                bid,
+                None,
+            )
+            .with_entry(far_date, 50_000, OptionStyle::Put, bid, None);
+        let report = book.apply_mass_quote(&quote);
+
+        assert_eq!(report.participant, "MM1");
+        assert_eq!(report.accepted_count(), 2);
+        assert!(!report.acks[1].is_accepted());
+        assert_eq!(near.total_order_count(), 1);
+        assert_eq!(far.total_order_count(), 1);
+    }
 }
@@ -0,0 +1,325 @@
+//! Rate curve module.
+//!
+//! This module provides the [`YieldCurve`] used for risk-free rates and
+//! dividend/borrow yields, and the [`RateCurves`] pair attached to each
+//! underlying. Rates are annualized and continuously compounded.
+
+use crate::error::{Error, Result};
+use optionstratlib::ExpirationDate;
+use serde::{Deserialize, Serialize};
+
+/// A point of a piecewise curve.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CurvePoint {
+    /// Time to expiry in years.
+    pub years: f64,
+    /// Continuously compounded annual rate at this tenor.
+    pub rate: f64,
+}
+
+impl CurvePoint {
+    /// Creates a new curve point.
+    #[must_use]
+    pub const fn new(years: f64, rate: f64) -> Self {
+        Self { years, rate }
+    }
+}
+
+/// Term structure of a continuously compounded rate.
+///
+/// Piecewise curves interpolate linearly in rate between tenors and are
+/// extrapolated flat beyond the first and last tenor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum YieldCurve {
+    /// The same rate for every tenor.
+    Flat(f64),
+    /// Rates at increasing tenors.
+    Piecewise(Vec<CurvePoint>),
+}
+
+impl Default for YieldCurve {
+    fn default() -> Self {
+        Self::Flat(0.0)
+    }
+}
+
+impl YieldCurve {
+    /// Creates a flat curve.
+    #[must_use]
+    pub const fn flat(rate: f64) -> Self {
+        Self::Flat(rate)
+    }
+
+    /// Creates a piecewise curve from points in any order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if there are no points, a tenor is not
+    /// positive, a value is not finite, or two points share a tenor.
+    pub fn piecewise(mut points: Vec<CurvePoint>) -> Result<Self> {
+        if points.is_empty() {
+            return Err(Error::validation("curve requires at least one point"));
+        }
+        if points
+            .iter()
+            .any(|p| !p.years.is_finite() || !p.rate.is_finite() || p.years <= 0.0)
+        {
+            return Err(Error::validation(
+                "curve points require positive tenors and finite rates",
+            ));
+        }
+        points.sort_by(|a, b| a.years.total_cmp(&b.years));
+        if points.windows(2).any(|w| w[0].years == w[1].years) {
+            return Err(Error::validation("curve points must have distinct tenors"));
+        }
+        Ok(Self::Piecewise(points))
+    }
+
+    /// Creates a piecewise curve keyed by expiration date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an expiration cannot be converted to years or the
+    /// points are invalid (see [`Self::piecewise`]).
+    pub fn from_expirations(points: &[(ExpirationDate, f64)]) -> Result<Self> {
+        let points = points
+            .iter()
+            .map(|(expiration, rate)| Ok(CurvePoint::new(expiration.get_years()?.to_f64(), *rate)))
+            .collect::<Result<Vec<_>>>()?;
+        Self::piecewise(points)
+    }
+
+    /// Bootstraps a curve from simple-interest deposit quotes.
+    ///
+    /// Each quote `(years, simple_rate)` is converted to the continuously
+    /// compounded zero rate `ln(1 + simple_rate * years) / years`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if a quote implies a non-positive
+    /// growth factor or the resulting points are invalid.
+    pub fn bootstrap_deposits(quotes: &[(f64, f64)]) -> Result<Self> {
+        let points = quotes
+            .iter()
+            .map(|&(years, simple_rate)| {
+                let growth = 1.0 + simple_rate * years;
+                if growth <= 0.0 || years <= 0.0 {
+                    return Err(Error::validation(format!(
+                        "invalid deposit quote at {years} years"
+                    )));
+                }
+                Ok(CurvePoint::new(years, growth.ln() / years))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::piecewise(points)
+    }
+
+    /// Bootstraps a dividend/borrow curve from forward or futures quotes.
+    ///
+    /// Each quote `(years, forward)` implies the carry yield
+    /// `q = r - ln(forward / spot) / years`, with `r` read from `rates`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the spot or a forward is not
+    /// positive, or the resulting points are invalid.
+    pub fn bootstrap_carry(spot: f64, forwards: &[(f64, f64)], rates: &Self) -> Result<Self> {
+        if spot <= 0.0 {
+            return Err(Error::validation("spot must be positive"));
+        }
+        let points = forwards
+            .iter()
+            .map(|&(years, forward)| {
+                if forward <= 0.0 || years <= 0.0 {
+                    return Err(Error::validation(format!(
+                        "invalid forward quote at {years} years"
+                    )));
+                }
+                let carry = (forward / spot).ln() / years;
+                Ok(CurvePoint::new(years, rates.rate_at(years) - carry))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::piecewise(points)
+    }
+
+    /// Returns the rate at a tenor in years.
+    #[must_use]
+    pub fn rate_at(&self, years: f64) -> f64 {
+        let points = match self {
+            Self::Flat(rate) => return *rate,
+            Self::Piecewise(points) => points,
+        };
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return 0.0,
+        };
+        if years <= first.years {
+            return first.rate;
+        }
+        if years >= last.years {
+            return last.rate;
+        }
+        points
+            .windows(2)
+            .find(|w| years <= w[1].years)
+            .map(|w| {
+                let weight = (years - w[0].years) / (w[1].years - w[0].years);
+                w[0].rate + weight * (w[1].rate - w[0].rate)
+            })
+            .unwrap_or(last.rate)
+    }
+
+    /// Returns the rate for an expiration date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn rate(&self, expiration: &ExpirationDate) -> Result<f64> {
+        Ok(self.rate_at(expiration.get_years()?.to_f64()))
+    }
+
+    /// Returns the discount factor `exp(-rate * years)` at a tenor in years.
+    #[must_use]
+    pub fn discount_factor_at(&self, years: f64) -> f64 {
+        (-self.rate_at(years) * years).exp()
+    }
+
+    /// Returns the discount factor for an expiration date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn discount_factor(&self, expiration: &ExpirationDate) -> Result<f64> {
+        Ok(self.discount_factor_at(expiration.get_years()?.to_f64()))
+    }
+}
+
+/// Risk-free rate and dividend/borrow curves of an underlying.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RateCurves {
+    /// Risk-free rate curve.
+    pub rate: YieldCurve,
+    /// Dividend or borrow yield curve.
+    pub dividend: YieldCurve,
+}
+
+impl RateCurves {
+    /// Creates a new pair of curves.
+    #[must_use]
+    pub const fn new(rate: YieldCurve, dividend: YieldCurve) -> Self {
+        Self { rate, dividend }
+    }
+
+    /// Returns the risk-free rate for an expiration date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn risk_free_rate(&self, expiration: &ExpirationDate) -> Result<f64> {
+        self.rate.rate(expiration)
+    }
+
+    /// Returns the dividend or borrow yield for an expiration date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn dividend_yield(&self, expiration: &ExpirationDate) -> Result<f64> {
+        self.dividend.rate(expiration)
+    }
+
+    /// Returns the forward price `spot * exp((r - q) * years)` at a tenor.
+    #[must_use]
+    pub fn forward_at(&self, spot: f64, years: f64) -> f64 {
+        spot * ((self.rate.rate_at(years) - self.dividend.rate_at(years)) * years).exp()
+    }
+
+    /// Returns the forward price for an expiration date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn forward(&self, spot: f64, expiration: &ExpirationDate) -> Result<f64> {
+        Ok(self.forward_at(spot, expiration.get_years()?.to_f64()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use optionstratlib::prelude::pos_or_panic;
+
+    #[test]
+    fn test_flat_curve() {
+        let curve = YieldCurve::flat(0.05);
+        assert_eq!(curve.rate_at(0.1), 0.05);
+        assert_eq!(curve.rate_at(10.0), 0.05);
+        assert!((curve.discount_factor_at(1.0) - (-0.05f64).exp()).abs() < 1e-12);
+        assert_eq!(YieldCurve::default().rate_at(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_piecewise_interpolation_and_extrapolation() {
+        let curve =
+            YieldCurve::piecewise(vec![CurvePoint::new(1.0, 0.04), CurvePoint::new(0.5, 0.02)])
+                .unwrap();
+
+        assert!((curve.rate_at(0.25) - 0.02).abs() < 1e-12);
+        assert!((curve.rate_at(0.75) - 0.03).abs() < 1e-12);
+        assert!((curve.rate_at(2.0) - 0.04).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_piecewise_validation() {
+        assert!(YieldCurve::piecewise(vec![]).is_err());
+        assert!(YieldCurve::piecewise(vec![CurvePoint::new(0.0, 0.01)]).is_err());
+        assert!(
+            YieldCurve::piecewise(vec![CurvePoint::new(1.0, 0.01), CurvePoint::new(1.0, 0.02)])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_from_expirations() {
+        let curve = YieldCurve::from_expirations(&[
+            (ExpirationDate::Days(pos_or_panic!(365.0)), 0.05),
+            (ExpirationDate::Days(pos_or_panic!(730.0)), 0.06),
+        ])
+        .unwrap();
+
+        let rate = curve
+            .rate(&ExpirationDate::Days(pos_or_panic!(547.5)))
+            .unwrap();
+        assert!((rate - 0.055).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bootstrap_deposits() {
+        let curve = YieldCurve::bootstrap_deposits(&[(0.5, 0.04), (1.0, 0.05)]).unwrap();
+        assert!((curve.rate_at(1.0) - 1.05f64.ln()).abs() < 1e-12);
+        assert!((curve.discount_factor_at(0.5) - 1.0 / 1.02).abs() < 1e-12);
+        assert!(YieldCurve::bootstrap_deposits(&[(1.0, -2.0)]).is_err());
+    }
+
+    #[test]
+    fn test_bootstrap_carry_recovers_dividend() {
+        let rates = YieldCurve::flat(0.05);
+        let curves = RateCurves::new(rates.clone(), YieldCurve::flat(0.02));
+        let forward = curves.forward_at(100.0, 1.0);
+
+        let dividend = YieldCurve::bootstrap_carry(100.0, &[(1.0, forward)], &rates).unwrap();
+        assert!((dividend.rate_at(1.0) - 0.02).abs() < 1e-12);
+        assert!(YieldCurve::bootstrap_carry(0.0, &[(1.0, forward)], &rates).is_err());
+    }
+
+    #[test]
+    fn test_rate_curves_lookup() {
+        let curves = RateCurves::new(YieldCurve::flat(0.03), YieldCurve::flat(0.01));
+        let expiration = ExpirationDate::Days(pos_or_panic!(365.0));
+
+        assert_eq!(curves.risk_free_rate(&expiration).unwrap(), 0.03);
+        assert_eq!(curves.dividend_yield(&expiration).unwrap(), 0.01);
+        let forward = curves.forward(100.0, &expiration).unwrap();
+        assert!((forward - 100.0 * 0.02f64.exp()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,255 @@
+//! Theoretical value and edge module.
+//!
+//! This module measures where the market sits relative to a theoretical
+//! value: per contract ([`ContractEdge`]), per strike ([`StrikeEdge`]) and
+//! per chain ([`ChainEdgeReport`]).
+
+use super::implied::ImpliedVolatilityQuote;
+use optionstratlib::{ExpirationDate, OptionStyle};
+use serde::{Deserialize, Serialize};
+
+/// Distance between a market price and the theoretical value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Edge {
+    /// Edge in price units.
+    pub price: f64,
+    /// Edge in ticks.
+    pub ticks: f64,
+    /// Edge in volatility points (1.0 = one percent of volatility), if the
+    /// market price could be inverted.
+    pub vol_points: Option<f64>,
+}
+
+impl Edge {
+    /// Creates an edge from a price distance and a volatility distance.
+    #[must_use]
+    pub fn new(price: f64, tick_size: u128, volatility: Option<f64>) -> Self {
+        Self {
+            price,
+            ticks: price / tick_size.max(1) as f64,
+            vol_points: volatility.map(|v| v * 100.0),
+        }
+    }
+}
+
+/// Theoretical value of a contract and the edge of its quotes.
+///
+/// Edges are signed so that a positive value is favorable to a market
+/// maker: the bid below theo, the ask above theo. The mid edge is
+/// `mid - theo`, positive when the market is rich.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContractEdge {
+    /// Call or Put.
+    pub option_style: OptionStyle,
+    /// Theoretical value, in price units.
+    pub theo: f64,
+    /// Volatility used for the theoretical value.
+    pub theo_volatility: f64,
+    /// Best bid price.
+    pub bid: Option<u128>,
+    /// Best ask price.
+    pub ask: Option<u128>,
+    /// `theo - bid`.
+    pub bid_edge: Option<Edge>,
+    /// `ask - theo`.
+    pub ask_edge: Option<Edge>,
+    /// `mid - theo`.
+    pub mid_edge: Option<Edge>,
+}
+
+impl ContractEdge {
+    /// Computes the edges of a quote against a theoretical value.
+    ///
+    /// # Arguments
+    ///
+    /// * `option_style` - Call or Put
+    /// * `theo` - Theoretical value, in price units
+    /// * `theo_volatility` - Volatility used for the theoretical value
+    /// * `bid` - Best bid price
+    /// * `ask` - Best ask price
+    /// * `iv` - Implied volatilities of the quote
+    /// * `tick_size` - Tick size, in price units
+    #[must_use]
+    pub fn new(
+        option_style: OptionStyle,
+        theo: f64,
+        theo_volatility: f64,
+        bid: Option<u128>,
+        ask: Option<u128>,
+        iv: &ImpliedVolatilityQuote,
+        tick_size: u128,
+    ) -> Self {
+        let mid = bid.zip(ask).map(|(b, a)| (b as f64 + a as f64) / 2.0);
+        Self {
+            option_style,
+            theo,
+            theo_volatility,
+            bid,
+            ask,
+            bid_edge: bid.map(|b| {
+                Edge::new(
+                    theo - b as f64,
+                    tick_size,
+                    iv.bid.map(|v| theo_volatility - v),
+                )
+            }),
+            ask_edge: ask.map(|a| {
+                Edge::new(
+                    a as f64 - theo,
+                    tick_size,
+                    iv.ask.map(|v| v - theo_volatility),
+                )
+            }),
+            mid_edge: mid
+                .map(|m| Edge::new(m - theo, tick_size, iv.mid.map(|v| v - theo_volatility))),
+        }
+    }
+}
+
+/// Theoretical values and edges of the call and put at one strike.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StrikeEdge {
+    /// The strike price.
+    pub strike: u64,
+    /// Edge of the call, if it has a theoretical value.
+    pub call: Option<ContractEdge>,
+    /// Edge of the put, if it has a theoretical value.
+    pub put: Option<ContractEdge>,
+}
+
+impl StrikeEdge {
+    /// Returns the contract edges of this strike.
+    pub fn contracts(&self) -> impl Iterator<Item = &ContractEdge> {
+        self.call.iter().chain(self.put.iter())
+    }
+}
+
+/// Edges of every strike of a chain with aggregate statistics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainEdgeReport {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Edges per strike, sorted by strike.
+    pub strikes: Vec<StrikeEdge>,
+    /// Number of contracts with a theoretical value.
+    pub contract_count: usize,
+    /// Number of contracts with a two-sided market.
+    pub quoted_count: usize,
+    /// Mean mid edge in ticks over two-sided contracts.
+    pub mean_mid_edge_ticks: Option<f64>,
+    /// Mean mid edge in volatility points over invertible contracts.
+    pub mean_mid_edge_vol_points: Option<f64>,
+    /// Strikes left out because a contract could not be priced, sorted.
+    #[serde(default)]
+    pub skipped: Vec<u64>,
+}
+
+impl ChainEdgeReport {
+    /// Builds a report from per-strike edges.
+    #[must_use]
+    pub fn new(expiration: ExpirationDate, mut strikes: Vec<StrikeEdge>) -> Self {
+        strikes.sort_by_key(|s| s.strike);
+        let contracts: Vec<&ContractEdge> =
+            strikes.iter().flat_map(StrikeEdge::contracts).collect();
+        let mids: Vec<&Edge> = contracts
+            .iter()
+            .filter_map(|c| c.mid_edge.as_ref())
+            .collect();
+        let vol_points: Vec<f64> = mids.iter().filter_map(|e| e.vol_points).collect();
+        let mean = |values: &[f64]| {
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+        let ticks: Vec<f64> = mids.iter().map(|e| e.ticks).collect();
+
+        Self {
+            expiration,
+            contract_count: contracts.len(),
+            quoted_count: mids.len(),
+            mean_mid_edge_ticks: mean(&ticks),
+            mean_mid_edge_vol_points: mean(&vol_points),
+            strikes,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Records the strikes left out of this report.
+    #[must_use]
+    pub fn with_skipped(mut self, mut skipped: Vec<u64>) -> Self {
+        skipped.sort_unstable();
+        self.skipped = skipped;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use optionstratlib::prelude::pos_or_panic;
+
+    fn iv() -> ImpliedVolatilityQuote {
+        ImpliedVolatilityQuote {
+            bid: Some(0.18),
+            ask: Some(0.23),
+            mid: Some(0.205),
+        }
+    }
+
+    #[test]
+    fn test_contract_edge_signs() {
+        let edge = ContractEdge::new(OptionStyle::Call, 10.0, 0.2, Some(9), Some(12), &iv(), 1);
+
+        let bid = edge.bid_edge.unwrap();
+        assert_eq!(bid.price, 1.0);
+        assert!((bid.vol_points.unwrap() - 2.0).abs() < 1e-9);
+        let ask = edge.ask_edge.unwrap();
+        assert_eq!(ask.ticks, 2.0);
+        assert!((ask.vol_points.unwrap() - 3.0).abs() < 1e-9);
+        let mid = edge.mid_edge.unwrap();
+        assert_eq!(mid.price, 0.5);
+        assert!((mid.vol_points.unwrap() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_contract_edge_ticks_and_one_sided() {
+        let edge = ContractEdge::new(
+            OptionStyle::Put,
+            100.0,
+            0.2,
+            Some(90),
+            None,
+            &ImpliedVolatilityQuote::default(),
+            5,
+        );
+        assert_eq!(edge.bid_edge.unwrap().ticks, 2.0);
+        assert!(edge.bid_edge.unwrap().vol_points.is_none());
+        assert!(edge.ask_edge.is_none());
+        assert!(edge.mid_edge.is_none());
+    }
+
+    #[test]
+    fn test_chain_edge_report_aggregates() {
+        let call = ContractEdge::new(OptionStyle::Call, 10.0, 0.2, Some(9), Some(13), &iv(), 1);
+        let put = ContractEdge::new(OptionStyle::Put, 10.0, 0.2, Some(8), None, &iv(), 1);
+        let report = ChainEdgeReport::new(
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            vec![
+                StrikeEdge {
+                    strike: 110,
+                    call: Some(call),
+                    put: None,
+                },
+                StrikeEdge {
+                    strike: 100,
+                    call: Some(call),
+                    put: Some(put),
+                },
+            ],
+        );
+
+        assert_eq!(report.strikes[0].strike, 100);
+        assert_eq!(report.contract_count, 3);
+        assert_eq!(report.quoted_count, 2);
+        assert_eq!(report.mean_mid_edge_ticks, Some(1.0));
+        assert!((report.mean_mid_edge_vol_points.unwrap() - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,279 @@
+//! Greeks engine module.
+//!
+//! This module computes option Greeks with optionstratlib's Black-Scholes
+//! equations from the [`GreeksInputs`] recorded alongside them.
+
+use super::normal::norm_pdf;
+use crate::error::{Error, Result};
+use crate::orderbook::GreeksInputs;
+use optionstratlib::greeks::{Greek, Greeks};
+use optionstratlib::prelude::Positive;
+use optionstratlib::{ExpirationDate, OptionStyle, OptionType, Options, Side};
+use rust_decimal::Decimal;
+
+/// Number of days per year used to convert year fractions to days.
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Builds a long European optionstratlib contract of one unit.
+///
+/// The expiration is expressed in days from `inputs.years_to_expiry`, so the
+/// contract only depends on the given inputs.
+///
+/// optionstratlib only takes a non-negative dividend yield. A negative yield
+/// (a carry cost, or Black-76 at negative rates where the yield is the rate)
+/// is folded into the underlying price instead: the contract is built with
+/// spot `S·e^(-qT)` and no yield, which has the same forward and so the same
+/// price. [`compute_greeks`] maps the Greeks back to the actual spot.
+///
+/// # Errors
+///
+/// Returns `Error::PricingError` if an input is out of range (non-positive
+/// strike, underlying price or volatility).
+pub(crate) fn build_option(
+    strike: u64,
+    option_style: OptionStyle,
+    inputs: &GreeksInputs,
+) -> Result<Options> {
+    let positive = |value: f64, name: &str| {
+        Positive::new(value).map_err(|e| Error::pricing(format!("invalid {name}: {e}")))
+    };
+    if strike == 0 || inputs.underlying_price == 0 {
+        return Err(Error::pricing(
+            "strike and underlying price must be positive",
+        ));
+    }
+    let risk_free_rate = Decimal::try_from(inputs.risk_free_rate)
+        .map_err(|e| Error::pricing(format!("invalid risk-free rate: {e}")))?;
+    let carry = carry_factor(inputs);
+
+    Ok(Options::new(
+        OptionType::European,
+        Side::Long,
+        String::new(),
+        positive(strike as f64, "strike")?,
+        ExpirationDate::Days(positive(inputs.years_to_expiry * DAYS_PER_YEAR, "expiry")?),
+        positive(inputs.volatility, "volatility")?,
+        Positive::ONE,
+        positive(inputs.underlying_price as f64 * carry, "underlying price")?,
+        risk_free_rate,
+        option_style,
+        positive(inputs.dividend_yield.max(0.0), "dividend yield")?,
+        None,
+    ))
+}
+
+/// Returns `e^(-qT)` for a negative dividend yield `q`, and 1 otherwise.
+///
+/// This is the factor applied to the underlying price by [`build_option`].
+fn carry_factor(inputs: &GreeksInputs) -> f64 {
+    if inputs.dividend_yield < 0.0 {
+        (-inputs.dividend_yield * inputs.years_to_expiry).exp()
+    } else {
+        1.0
+    }
+}
+
+/// Returns the Black-Scholes gamma of one contract at an underlying price,
+/// using the volatility, rates and time to expiry of `inputs`.
+///
+/// This closed form avoids building a contract, for scans over many
+/// underlying levels. Returns `None` if an input is out of range.
+#[must_use]
+pub(crate) fn black_scholes_gamma(
+    strike: f64,
+    underlying_price: f64,
+    inputs: &GreeksInputs,
+) -> Option<f64> {
+    let GreeksInputs {
+        volatility,
+        risk_free_rate,
+        dividend_yield,
+        years_to_expiry,
+        ..
+    } = *inputs;
+    if strike <= 0.0 || underlying_price <= 0.0 || volatility <= 0.0 || years_to_expiry <= 0.0 {
+        return None;
+    }
+    let deviation = volatility * years_to_expiry.sqrt();
+    let d1 = ((underlying_price / strike).ln()
+        + (risk_free_rate - dividend_yield + volatility * volatility / 2.0) * years_to_expiry)
+        / deviation;
+    Some((-dividend_yield * years_to_expiry).exp() * norm_pdf(d1) / (underlying_price * deviation))
+}
+
+/// Computes the Greeks of one long contract.
+///
+/// # Arguments
+///
+/// * `strike` - The strike price
+/// * `option_style` - Call or Put
+/// * `inputs` - Underlying price, volatility, rates and time to expiry
+///
+/// # Errors
+///
+/// Returns `Error::PricingError` if the inputs are out of range, or
+/// `Error::GreeksError` if optionstratlib fails to compute a Greek.
+pub fn compute_greeks(
+    strike: u64,
+    option_style: OptionStyle,
+    inputs: &GreeksInputs,
+) -> Result<Greek> {
+    if inputs.volatility <= 0.0 {
+        return Err(Error::pricing("volatility must be positive"));
+    }
+    let greeks = build_option(strike, option_style, inputs)?
+        .greeks()
+        .map_err(|e| Error::greeks(e.to_string()))?;
+    if inputs.dividend_yield < 0.0 {
+        to_spot_greeks(greeks, inputs)
+    } else {
+        Ok(greeks)
+    }
+}
+
+/// Maps Greeks computed on the carry-adjusted spot `S' = S·e^(-qT)` of a
+/// negative dividend yield back to the actual spot `S`.
+///
+/// Spot derivatives scale with `dS'/dS = e^(-qT)`, theta picks up the drift
+/// of `S'` in time, and rho to the dividend yield is already `-T·S'·Δ'`.
+/// Vega, rho and vomma are unchanged; veta, charm and color stay relative
+/// to the adjusted spot.
+fn to_spot_greeks(mut greeks: Greek, inputs: &GreeksInputs) -> Result<Greek> {
+    let decimal = |value: f64| {
+        Decimal::try_from(value).map_err(|e| Error::greeks(format!("invalid Greek: {e}")))
+    };
+    let carry = carry_factor(inputs);
+    let factor = decimal(carry)?;
+    let adjusted_spot = inputs.underlying_price as f64 * carry;
+    let drift = decimal(inputs.dividend_yield * adjusted_spot / DAYS_PER_YEAR)?;
+
+    greeks.theta += greeks.delta * drift;
+    greeks.delta *= factor;
+    greeks.gamma *= factor * factor;
+    greeks.vanna *= factor;
+    greeks.alpha = if greeks.theta.is_zero() {
+        Decimal::ZERO
+    } else {
+        greeks.gamma / greeks.theta
+    };
+    Ok(greeks)
+}
+
+/// Computes the Black-Scholes price of one contract, in strike units.
+///
+/// # Errors
+///
+/// Returns `Error::PricingError` if the inputs are out of range or the
+/// price cannot be computed.
+pub fn theoretical_price(
+    strike: u64,
+    option_style: OptionStyle,
+    inputs: &GreeksInputs,
+) -> Result<f64> {
+    let price = build_option(strike, option_style, inputs)?
+        .calculate_price_black_scholes()
+        .map_err(|e| Error::pricing(e.to_string()))?;
+    f64::try_from(price).map_err(|e| Error::pricing(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn inputs() -> GreeksInputs {
+        GreeksInputs {
+            underlying_price: 100,
+            volatility: 0.2,
+            risk_free_rate: 0.05,
+            dividend_yield: 0.0,
+            years_to_expiry: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_compute_greeks_call_put() {
+        let call = compute_greeks(100, OptionStyle::Call, &inputs()).unwrap();
+        let put = compute_greeks(100, OptionStyle::Put, &inputs()).unwrap();
+
+        assert!(call.delta > dec!(0.5) && call.delta < dec!(0.7));
+        assert!(put.delta < dec!(0) && put.delta > dec!(-0.5));
+        assert!((call.delta - put.delta - dec!(1)).abs() < dec!(0.01));
+        assert!(call.gamma > dec!(0));
+        assert!((call.gamma - put.gamma).abs() < dec!(0.0001));
+    }
+
+    #[test]
+    fn test_compute_greeks_rejects_invalid_inputs() {
+        let mut bad = inputs();
+        bad.volatility = 0.0;
+        assert!(compute_greeks(100, OptionStyle::Call, &bad).is_err());
+
+        assert!(compute_greeks(0, OptionStyle::Call, &inputs()).is_err());
+    }
+
+    #[test]
+    fn test_theoretical_price_parity() {
+        let call = theoretical_price(100, OptionStyle::Call, &inputs()).unwrap();
+        let put = theoretical_price(100, OptionStyle::Put, &inputs()).unwrap();
+        let forward_value = 100.0 - 100.0 * (-0.05f64 * 0.5).exp();
+        assert!((call - put - forward_value).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_negative_dividend_yield() {
+        use rust_decimal::prelude::ToPrimitive;
+
+        // Black-76 at a negative rate sets the yield to the rate.
+        let inputs = GreeksInputs {
+            underlying_price: 105,
+            risk_free_rate: -0.01,
+            dividend_yield: -0.01,
+            ..inputs()
+        };
+        let call = theoretical_price(100, OptionStyle::Call, &inputs).unwrap();
+        let put = theoretical_price(100, OptionStyle::Put, &inputs).unwrap();
+        let discount = (0.01f64 * 0.5).exp();
+        assert!((call - put - (105.0 - 100.0) * discount).abs() < 1e-6);
+
+        // Greeks agree with bumped prices and the closed-form gamma.
+        let greeks = compute_greeks(100, OptionStyle::Call, &inputs).unwrap();
+        let bumped = |spot: u64| {
+            theoretical_price(
+                100,
+                OptionStyle::Call,
+                &GreeksInputs {
+                    underlying_price: spot,
+                    ..inputs
+                },
+            )
+            .unwrap()
+        };
+        let delta = (bumped(106) - bumped(104)) / 2.0;
+        assert!((greeks.delta.to_f64().unwrap() - delta).abs() < 1e-3);
+        let gamma = black_scholes_gamma(100.0, 105.0, &inputs).unwrap();
+        assert!((greeks.gamma.to_f64().unwrap() - gamma).abs() < 1e-4);
+
+        let day = 1.0 / DAYS_PER_YEAR;
+        let decayed = theoretical_price(
+            100,
+            OptionStyle::Call,
+            &GreeksInputs {
+                years_to_expiry: 0.5 - day,
+                ..inputs
+            },
+        )
+        .unwrap();
+        assert!((greeks.theta.to_f64().unwrap() - (decayed - call)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_black_scholes_gamma_matches_engine() {
+        use rust_decimal::prelude::ToPrimitive;
+
+        let engine = compute_greeks(110, OptionStyle::Call, &inputs()).unwrap();
+        let gamma = black_scholes_gamma(110.0, 100.0, &inputs()).unwrap();
+        assert!((gamma - engine.gamma.to_f64().unwrap()).abs() < 1e-4);
+        assert!(black_scholes_gamma(110.0, 0.0, &inputs()).is_none());
+    }
+}
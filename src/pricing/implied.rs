@@ -0,0 +1,221 @@
+//! Implied volatility module.
+//!
+//! This module inverts option prices into implied volatilities with
+//! optionstratlib's Black-Scholes solver, using the Black-76 variant for
+//! options on futures.
+
+use super::greeks::build_option;
+use crate::error::{Error, Result};
+use crate::orderbook::GreeksInputs;
+use optionstratlib::OptionStyle;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Volatility used to build the contract before solving for the implied one.
+const SEED_VOLATILITY: f64 = 0.2;
+
+/// Upper bound of the implied volatility solver (500%).
+const MAX_VOLATILITY: f64 = 5.0;
+
+/// Option pricing model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PricingModel {
+    /// Black-Scholes-Merton on a spot underlying with a dividend yield.
+    #[default]
+    BlackScholes,
+    /// Black-76 on a futures price (no carry).
+    Black76,
+}
+
+impl PricingModel {
+    /// Returns the inputs adjusted for this model.
+    ///
+    /// Black-76 is Black-Scholes with the dividend yield equal to the
+    /// risk-free rate, so the forward equals the futures price.
+    #[must_use]
+    pub const fn adjust(self, inputs: GreeksInputs) -> GreeksInputs {
+        match self {
+            Self::BlackScholes => inputs,
+            Self::Black76 => GreeksInputs {
+                dividend_yield: inputs.risk_free_rate,
+                ..inputs
+            },
+        }
+    }
+}
+
+/// Implied volatilities of the best bid, best ask and mid price.
+///
+/// A side is `None` when it is not quoted or cannot be inverted (e.g., a
+/// bid below intrinsic value).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImpliedVolatilityQuote {
+    /// Implied volatility of the best bid.
+    pub bid: Option<f64>,
+    /// Implied volatility of the best ask.
+    pub ask: Option<f64>,
+    /// Implied volatility of the mid price.
+    pub mid: Option<f64>,
+}
+
+impl ImpliedVolatilityQuote {
+    /// Returns true if no side could be inverted.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.bid.is_none() && self.ask.is_none() && self.mid.is_none()
+    }
+}
+
+/// Computes the implied volatility of an option price.
+///
+/// The price must lie strictly between the discounted intrinsic value and
+/// the no-arbitrage upper bound (discounted underlying for calls, discounted
+/// strike for puts); otherwise the price carries no time value to invert.
+///
+/// # Arguments
+///
+/// * `price` - The option price, in strike units
+/// * `strike` - The strike price
+/// * `option_style` - Call or Put
+/// * `inputs` - Underlying price, rates and time to expiry (volatility is ignored)
+/// * `model` - Black-Scholes or Black-76
+///
+/// # Errors
+///
+/// Returns `Error::PricingError` if there is no time to expiry, the price is
+/// outside the no-arbitrage bounds, or the solver fails.
+pub fn implied_volatility(
+    price: f64,
+    strike: u64,
+    option_style: OptionStyle,
+    inputs: &GreeksInputs,
+    model: PricingModel,
+) -> Result<f64> {
+    let inputs = model.adjust(GreeksInputs {
+        volatility: SEED_VOLATILITY,
+        ..*inputs
+    });
+    if !price.is_finite() || price <= 0.0 {
+        return Err(Error::pricing(format!("invalid option price {price}")));
+    }
+    if inputs.years_to_expiry <= 0.0 {
+        return Err(Error::pricing("no time to expiry"));
+    }
+
+    let (lower, upper) = price_bounds(strike, option_style, &inputs);
+    if price <= lower {
+        return Err(Error::pricing(format!(
+            "price {price} has no time value over intrinsic {lower:.6}"
+        )));
+    }
+    if price >= upper {
+        return Err(Error::pricing(format!(
+            "price {price} is above the no-arbitrage bound {upper:.6}"
+        )));
+    }
+
+    let market_price = Decimal::try_from(price)
+        .map_err(|e| Error::pricing(format!("invalid option price {price}: {e}")))?;
+    let volatility = build_option(strike, option_style, &inputs)?
+        .calculate_implied_volatility(market_price)
+        .map_err(|e| Error::pricing(e.to_string()))?
+        .to_f64();
+    if volatility >= MAX_VOLATILITY * 0.999 {
+        return Err(Error::pricing(format!(
+            "implied volatility for price {price} exceeds {MAX_VOLATILITY}"
+        )));
+    }
+    Ok(volatility)
+}
+
+/// Returns the discounted intrinsic value and the upper price bound.
+fn price_bounds(strike: u64, option_style: OptionStyle, inputs: &GreeksInputs) -> (f64, f64) {
+    let t = inputs.years_to_expiry;
+    let forward_value = inputs.underlying_price as f64 * (-inputs.dividend_yield * t).exp();
+    let strike_value = strike as f64 * (-inputs.risk_free_rate * t).exp();
+    match option_style {
+        OptionStyle::Call => ((forward_value - strike_value).max(0.0), forward_value),
+        OptionStyle::Put => ((strike_value - forward_value).max(0.0), strike_value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> GreeksInputs {
+        GreeksInputs {
+            underlying_price: 100,
+            volatility: 0.0,
+            risk_free_rate: 0.05,
+            dividend_yield: 0.0,
+            years_to_expiry: 0.5,
+        }
+    }
+
+    fn price(option_style: OptionStyle, volatility: f64) -> f64 {
+        let inputs = GreeksInputs {
+            volatility,
+            ..inputs()
+        };
+        build_option(100, option_style, &inputs)
+            .unwrap()
+            .calculate_price_black_scholes()
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_implied_volatility_round_trip() {
+        for option_style in [OptionStyle::Call, OptionStyle::Put] {
+            let iv = implied_volatility(
+                price(option_style, 0.3),
+                100,
+                option_style,
+                &inputs(),
+                PricingModel::BlackScholes,
+            )
+            .unwrap();
+            assert!((iv - 0.3).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_implied_volatility_bounds() {
+        let model = PricingModel::BlackScholes;
+
+        // Deep ITM call quoted at intrinsic has no time value.
+        let err = implied_volatility(20.0, 80, OptionStyle::Call, &inputs(), model);
+        assert!(matches!(err, Err(Error::PricingError { .. })));
+        // Call worth more than the underlying.
+        assert!(implied_volatility(150.0, 100, OptionStyle::Call, &inputs(), model).is_err());
+        assert!(implied_volatility(0.0, 100, OptionStyle::Call, &inputs(), model).is_err());
+
+        let expired = GreeksInputs {
+            years_to_expiry: 0.0,
+            ..inputs()
+        };
+        assert!(implied_volatility(5.0, 100, OptionStyle::Call, &expired, model).is_err());
+    }
+
+    #[test]
+    fn test_black76_adjusts_carry() {
+        let adjusted = PricingModel::Black76.adjust(inputs());
+        assert_eq!(adjusted.dividend_yield, 0.05);
+        assert_eq!(PricingModel::BlackScholes.adjust(inputs()), inputs());
+
+        // Black-76 ATM call and put have the same price, so the same IV.
+        let call = implied_volatility(
+            5.0,
+            100,
+            OptionStyle::Call,
+            &inputs(),
+            PricingModel::Black76,
+        )
+        .unwrap();
+        let put = implied_volatility(5.0, 100, OptionStyle::Put, &inputs(), PricingModel::Black76)
+            .unwrap();
+        assert!((call - put).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,41 @@
+//! Pricing module.
+//!
+//! This module provides the market inputs and models used to value the
+//! contracts held in the order book hierarchy.
+//!
+//! ## Components
+//!
+//! - [`YieldCurve`]: Flat, piecewise or bootstrapped continuously compounded curve
+//! - [`RateCurves`]: Risk-free rate and dividend/borrow curves of an underlying
+//! - [`VolatilityProvider`]: Volatility lookup per contract ([`FlatVolatility`], [`StrikeVolatility`])
+//! - [`compute_greeks`]: Black-Scholes Greeks of a contract from its inputs
+//! - [`ContractEdge`]: Theoretical value and quote edges of a contract, per strike and per chain
+//! - [`implied_volatility`]: Black-Scholes/Black-76 implied volatility of a price
+//! - [`StrikeParity`]: Put-call parity, synthetic forward and implied carry per strike
+//! - [`ImpliedForward`]: Robust chain forward from the per-strike synthetic forwards
+//! - [`VolatilitySmile`]: Per-expiration smile interpolated in log-moneyness
+//! - [`SviFit`]: Raw SVI fit of one expiration with diagnostics
+//! - [`VolatilitySurface`]: SVI slices interpolated in total variance across expirations
+
+mod curve;
+mod edge;
+mod greeks;
+mod implied;
+mod normal;
+mod parity;
+mod smile;
+mod surface;
+mod svi;
+mod volatility;
+
+pub use curve::{CurvePoint, RateCurves, YieldCurve};
+pub use edge::{ChainEdgeReport, ContractEdge, Edge, StrikeEdge};
+pub(crate) use greeks::black_scholes_gamma;
+pub use greeks::{compute_greeks, theoretical_price};
+pub use implied::{ImpliedVolatilityQuote, PricingModel, implied_volatility};
+pub use parity::{ImpliedForward, ParityArbitrage, ParityReport, ParityViolation, StrikeParity};
+pub use smile::{SmilePoint, VolatilitySmile};
+pub(crate) use surface::solve_delta;
+pub use surface::{SurfaceSlice, VolatilitySurface};
+pub use svi::{SviFit, SviParameters};
+pub use volatility::{FlatVolatility, StrikeVolatility, VolatilityProvider};
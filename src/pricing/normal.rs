@@ -0,0 +1,52 @@
+//! Standard normal distribution helpers.
+
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+/// Standard normal cumulative distribution function.
+///
+/// Uses the Abramowitz-Stegun 7.1.26 approximation of `erf` (absolute error
+/// below 1.5e-7).
+#[must_use]
+pub(crate) fn norm_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x * FRAC_1_SQRT_2))
+}
+
+/// Standard normal probability density function.
+#[must_use]
+pub(crate) fn norm_pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * PI).sqrt()
+}
+
+/// Error function approximation.
+fn erf(x: f64) -> f64 {
+    const A1: f64 = 0.254_829_592;
+    const A2: f64 = -0.284_496_736;
+    const A3: f64 = 1.421_413_741;
+    const A4: f64 = -1.453_152_027;
+    const A5: f64 = 1.061_405_429;
+    const P: f64 = 0.327_591_1;
+
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + P * x);
+    let poly = ((((A5 * t + A4) * t + A3) * t + A2) * t + A1) * t;
+    sign * (1.0 - poly * (-x * x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_norm_cdf() {
+        assert!((norm_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((norm_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((norm_cdf(-1.0) + norm_cdf(1.0) - 1.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_norm_pdf() {
+        assert!((norm_pdf(0.0) - 0.398_942_280_4).abs() < 1e-9);
+        assert!((norm_pdf(1.5) - norm_pdf(-1.5)).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,325 @@
+//! Put-call parity module.
+//!
+//! This module reads European put-call parity off the call and put quotes of
+//! a strike:
+//!
+//! ```text
+//! C - P = DF * (F - K)
+//! ```
+//!
+//! Solving for `F` gives the synthetic forward of each strike and, against
+//! the underlying price, the implied carry. Synthetic forwards that cross the
+//! reference forward are conversion or reversal arbitrages.
+
+use crate::orderbook::{GreeksInputs, Quote};
+use optionstratlib::ExpirationDate;
+use serde::{Deserialize, Serialize};
+
+/// Direction of a put-call parity arbitrage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ParityArbitrage {
+    /// Sell the call at the bid, buy the put at the ask and buy the
+    /// underlying: the synthetic forward bid is above the reference forward.
+    Conversion,
+    /// Buy the call at the ask, sell the put at the bid and sell the
+    /// underlying: the synthetic forward ask is below the reference forward.
+    Reversal,
+}
+
+/// A put-call parity violation at one strike.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ParityViolation {
+    /// Direction of the arbitrage.
+    pub arbitrage: ParityArbitrage,
+    /// Present value of the arbitrage per unit, in price units.
+    pub edge: f64,
+    /// Executable quantity: the smaller of the two option sizes.
+    pub quantity: u64,
+    /// Present value of the arbitrage for the executable quantity.
+    pub profit: f64,
+}
+
+/// Put-call parity analytics of one strike.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StrikeParity {
+    /// The strike price.
+    pub strike: u64,
+    /// Discount factor to expiry.
+    pub discount_factor: f64,
+    /// Synthetic forward sold at the market: call bid minus put ask.
+    pub forward_bid: Option<f64>,
+    /// Synthetic forward bought at the market: call ask minus put bid.
+    pub forward_ask: Option<f64>,
+    /// Synthetic forward at the call and put mids.
+    pub forward: Option<f64>,
+    /// Risk-free rate implied by the mid forward, given the dividend yield.
+    pub implied_rate: Option<f64>,
+    /// Dividend or borrow yield implied by the mid forward, given the rate.
+    pub implied_borrow: Option<f64>,
+    /// Arbitrage against the reference forward, if any.
+    pub violation: Option<ParityViolation>,
+}
+
+impl StrikeParity {
+    /// Computes the parity analytics of a strike from its call and put
+    /// quotes.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - The strike price
+    /// * `call` - Best quote of the call
+    /// * `put` - Best quote of the put
+    /// * `inputs` - Underlying price, rates and time to expiry
+    #[must_use]
+    pub fn from_quotes(strike: u64, call: &Quote, put: &Quote, inputs: &GreeksInputs) -> Self {
+        let discount_factor = (-inputs.risk_free_rate * inputs.years_to_expiry).exp();
+        let synthetic = |call: Option<f64>, put: Option<f64>| {
+            call.zip(put)
+                .map(|(c, p)| strike as f64 + (c - p) / discount_factor)
+        };
+        let price = |p: Option<u128>| p.map(|p| p as f64);
+
+        let forward_bid = synthetic(price(call.bid_price()), price(put.ask_price()));
+        let forward_ask = synthetic(price(call.ask_price()), price(put.bid_price()));
+        let forward = synthetic(call.mid_price(), put.mid_price());
+        let carry = forward.and_then(|f| implied_carry(f, inputs));
+
+        let reference = reference_forward(inputs);
+        let conversion = forward_bid
+            .map(|f| (f - reference) * discount_factor)
+            .filter(|edge| *edge > 0.0)
+            .map(|edge| {
+                ParityViolation::new(
+                    ParityArbitrage::Conversion,
+                    edge,
+                    call.bid_size().min(put.ask_size()),
+                )
+            });
+        let reversal = forward_ask
+            .map(|f| (reference - f) * discount_factor)
+            .filter(|edge| *edge > 0.0)
+            .map(|edge| {
+                ParityViolation::new(
+                    ParityArbitrage::Reversal,
+                    edge,
+                    call.ask_size().min(put.bid_size()),
+                )
+            });
+
+        Self {
+            strike,
+            discount_factor,
+            forward_bid,
+            forward_ask,
+            forward,
+            implied_rate: carry.map(|c| inputs.dividend_yield + c),
+            implied_borrow: carry.map(|c| inputs.risk_free_rate - c),
+            violation: conversion.or(reversal),
+        }
+    }
+}
+
+impl ParityViolation {
+    /// Creates a violation from its per-unit edge and executable quantity.
+    #[must_use]
+    pub fn new(arbitrage: ParityArbitrage, edge: f64, quantity: u64) -> Self {
+        Self {
+            arbitrage,
+            edge,
+            quantity,
+            profit: edge * quantity as f64,
+        }
+    }
+}
+
+/// Robust estimate of the forward implied by a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImpliedForward {
+    /// Median of the per-strike mid forwards.
+    pub forward: f64,
+    /// Median absolute deviation of the per-strike mid forwards.
+    pub dispersion: f64,
+    /// Number of strikes used.
+    pub strike_count: usize,
+    /// Risk-free rate implied by the forward, given the dividend yield.
+    pub implied_rate: Option<f64>,
+    /// Dividend or borrow yield implied by the forward, given the rate.
+    pub implied_borrow: Option<f64>,
+}
+
+impl ImpliedForward {
+    /// Estimates the forward from per-strike parity analytics.
+    ///
+    /// The median discards strikes with stale or off-market quotes. Returns
+    /// `None` if no strike has a mid forward.
+    #[must_use]
+    pub fn estimate(strikes: &[StrikeParity], inputs: &GreeksInputs) -> Option<Self> {
+        let forwards: Vec<f64> = strikes.iter().filter_map(|s| s.forward).collect();
+        let forward = median(forwards.clone())?;
+        let dispersion = median(forwards.iter().map(|f| (f - forward).abs()).collect())?;
+        let carry = implied_carry(forward, inputs);
+        Some(Self {
+            forward,
+            dispersion,
+            strike_count: forwards.len(),
+            implied_rate: carry.map(|c| inputs.dividend_yield + c),
+            implied_borrow: carry.map(|c| inputs.risk_free_rate - c),
+        })
+    }
+}
+
+/// Put-call parity analytics of every strike of a chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParityReport {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Forward from the underlying price and the rate curves.
+    pub reference_forward: f64,
+    /// Forward implied by the option quotes, if any strike is quoted.
+    pub implied_forward: Option<ImpliedForward>,
+    /// Parity analytics per strike, sorted by strike.
+    pub strikes: Vec<StrikeParity>,
+}
+
+impl ParityReport {
+    /// Builds a report from per-strike parity analytics.
+    #[must_use]
+    pub fn new(
+        expiration: ExpirationDate,
+        mut strikes: Vec<StrikeParity>,
+        inputs: &GreeksInputs,
+    ) -> Self {
+        strikes.sort_by_key(|s| s.strike);
+        Self {
+            expiration,
+            reference_forward: reference_forward(inputs),
+            implied_forward: ImpliedForward::estimate(&strikes, inputs),
+            strikes,
+        }
+    }
+
+    /// Returns the strikes with a parity violation.
+    pub fn violations(&self) -> impl Iterator<Item = &StrikeParity> {
+        self.strikes.iter().filter(|s| s.violation.is_some())
+    }
+
+    /// Returns the total present value of every violation for its
+    /// executable quantity.
+    #[must_use]
+    pub fn total_arbitrage(&self) -> f64 {
+        self.strikes
+            .iter()
+            .filter_map(|s| s.violation)
+            .map(|v| v.profit)
+            .sum()
+    }
+}
+
+/// Returns the forward carried at the rate and dividend yield.
+fn reference_forward(inputs: &GreeksInputs) -> f64 {
+    let carry = inputs.risk_free_rate - inputs.dividend_yield;
+    inputs.underlying_price as f64 * (carry * inputs.years_to_expiry).exp()
+}
+
+/// Returns the annualized carry `r - q` implied by a forward.
+fn implied_carry(forward: f64, inputs: &GreeksInputs) -> Option<f64> {
+    (forward > 0.0 && inputs.underlying_price > 0 && inputs.years_to_expiry > 0.0)
+        .then(|| (forward / inputs.underlying_price as f64).ln() / inputs.years_to_expiry)
+}
+
+/// Returns the median of the values, or `None` if empty.
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use optionstratlib::prelude::pos_or_panic;
+
+    fn inputs() -> GreeksInputs {
+        GreeksInputs {
+            underlying_price: 100,
+            volatility: 0.0,
+            risk_free_rate: 0.05,
+            dividend_yield: 0.01,
+            years_to_expiry: 0.5,
+        }
+    }
+
+    fn quote(bid: u128, ask: u128, size: u64) -> Quote {
+        Quote::new(Some(bid), size, Some(ask), size, 0)
+    }
+
+    #[test]
+    fn test_strike_parity_forward_and_carry() {
+        let inputs = inputs();
+        // C - P = DF * (F - K) with F ~ 102 and K = 100.
+        let parity = StrikeParity::from_quotes(100, &quote(7, 9, 10), &quote(5, 7, 10), &inputs);
+
+        let df = (-0.025f64).exp();
+        assert!((parity.discount_factor - df).abs() < 1e-12);
+        let forward = parity.forward.unwrap();
+        assert!((forward - (100.0 + 2.0 / df)).abs() < 1e-9);
+        assert!(parity.forward_bid.unwrap() < forward);
+        assert!(parity.forward_ask.unwrap() > forward);
+
+        let carry = (forward / 100.0).ln() / 0.5;
+        assert!((parity.implied_rate.unwrap() - (0.01 + carry)).abs() < 1e-12);
+        assert!((parity.implied_borrow.unwrap() - (0.05 - carry)).abs() < 1e-12);
+        assert!(parity.violation.is_none());
+    }
+
+    #[test]
+    fn test_strike_parity_violations() {
+        let inputs = inputs();
+        // Reference forward ~ 102.02; the synthetic bid is ~ 110.
+        let parity = StrikeParity::from_quotes(100, &quote(15, 16, 4), &quote(4, 5, 3), &inputs);
+        let violation = parity.violation.unwrap();
+        assert_eq!(violation.arbitrage, ParityArbitrage::Conversion);
+        assert_eq!(violation.quantity, 3);
+        assert!((violation.profit - 3.0 * violation.edge).abs() < 1e-12);
+
+        let parity = StrikeParity::from_quotes(100, &quote(1, 2, 4), &quote(8, 9, 6), &inputs);
+        let violation = parity.violation.unwrap();
+        assert_eq!(violation.arbitrage, ParityArbitrage::Reversal);
+        assert_eq!(violation.quantity, 4);
+        assert!(violation.edge > 0.0);
+
+        let one_sided = Quote::new(Some(15), 4, None, 0, 0);
+        let parity = StrikeParity::from_quotes(100, &one_sided, &quote(4, 5, 3), &inputs);
+        assert!(parity.forward.is_none());
+        assert!(parity.violation.is_some());
+    }
+
+    #[test]
+    fn test_implied_forward_is_robust() {
+        let inputs = inputs();
+        let strikes: Vec<StrikeParity> = [(90, 13, 1), (100, 5, 3), (110, 1, 9), (120, 30, 1)]
+            .into_iter()
+            .map(|(k, c, p)| {
+                StrikeParity::from_quotes(k, &quote(c, c + 1, 1), &quote(p, p + 1, 1), &inputs)
+            })
+            .collect();
+        let report =
+            ParityReport::new(ExpirationDate::Days(pos_or_panic!(182.5)), strikes, &inputs);
+
+        let implied = report.implied_forward.unwrap();
+        assert_eq!(implied.strike_count, 4);
+        // The off-market 120 strike does not move the median.
+        assert!((implied.forward - 102.0).abs() < 0.5);
+        assert!(implied.implied_borrow.is_some());
+        assert_eq!(report.violations().count(), 1);
+        assert!(report.total_arbitrage() > 0.0);
+        assert!((report.reference_forward - 100.0 * 0.02f64.exp()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,238 @@
+//! Volatility smile module.
+//!
+//! This module provides the [`VolatilitySmile`] of a single expiration built
+//! from the implied volatilities of its listed strikes, with interpolation
+//! and extrapolation to arbitrary strikes.
+
+use super::implied::ImpliedVolatilityQuote;
+use super::volatility::VolatilityProvider;
+use optionstratlib::{ExpirationDate, OptionStyle};
+use serde::{Deserialize, Serialize};
+
+/// Implied volatilities of the call and put at one strike.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SmilePoint {
+    /// The strike price.
+    pub strike: u64,
+    /// Log-moneyness `ln(strike / forward)`.
+    pub log_moneyness: f64,
+    /// Implied volatilities of the call.
+    pub call: ImpliedVolatilityQuote,
+    /// Implied volatilities of the put.
+    pub put: ImpliedVolatilityQuote,
+}
+
+impl SmilePoint {
+    /// Returns the out-of-the-money side at this strike: calls at or above
+    /// the forward, puts below it.
+    #[must_use]
+    pub fn otm_style(&self) -> OptionStyle {
+        if self.log_moneyness >= 0.0 {
+            OptionStyle::Call
+        } else {
+            OptionStyle::Put
+        }
+    }
+
+    /// Returns the implied volatilities of the given side.
+    #[must_use]
+    pub const fn get(&self, option_style: OptionStyle) -> &ImpliedVolatilityQuote {
+        match option_style {
+            OptionStyle::Call => &self.call,
+            OptionStyle::Put => &self.put,
+        }
+    }
+
+    /// Returns the implied volatilities of the out-of-the-money side.
+    #[must_use]
+    pub fn otm(&self) -> &ImpliedVolatilityQuote {
+        self.get(self.otm_style())
+    }
+
+    /// Returns the mid volatility of this strike.
+    ///
+    /// Uses the out-of-the-money side, falling back to the in-the-money side
+    /// when the OTM option has no mid volatility.
+    #[must_use]
+    pub fn mid(&self) -> Option<f64> {
+        let itm = match self.otm_style() {
+            OptionStyle::Call => &self.put,
+            OptionStyle::Put => &self.call,
+        };
+        self.otm().mid.or(itm.mid)
+    }
+}
+
+/// Volatility smile of a single expiration.
+///
+/// The smile interpolates the out-of-the-money mid volatilities linearly in
+/// log-moneyness and extrapolates them flat beyond the wings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolatilitySmile {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Forward price used for log-moneyness, in strike units.
+    pub forward: f64,
+    /// Time to expiry in years.
+    pub years_to_expiry: f64,
+    /// Points sorted by strike.
+    pub points: Vec<SmilePoint>,
+}
+
+impl VolatilitySmile {
+    /// Creates a smile from points in any order.
+    #[must_use]
+    pub fn new(
+        expiration: ExpirationDate,
+        forward: f64,
+        years_to_expiry: f64,
+        mut points: Vec<SmilePoint>,
+    ) -> Self {
+        points.sort_by_key(|p| p.strike);
+        Self {
+            expiration,
+            forward,
+            years_to_expiry,
+            points,
+        }
+    }
+
+    /// Returns the number of strikes in the smile.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if the smile has no strikes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the point of a listed strike.
+    #[must_use]
+    pub fn point(&self, strike: u64) -> Option<&SmilePoint> {
+        self.points.iter().find(|p| p.strike == strike)
+    }
+
+    /// Returns the log-moneyness `ln(strike / forward)` of a strike.
+    #[must_use]
+    pub fn log_moneyness(&self, strike: f64) -> f64 {
+        (strike / self.forward).ln()
+    }
+
+    /// Returns the volatility at a log-moneyness, interpolating the mid
+    /// volatilities of the listed strikes.
+    ///
+    /// Returns `None` if no strike has a mid volatility.
+    #[must_use]
+    pub fn volatility_at_log_moneyness(&self, log_moneyness: f64) -> Option<f64> {
+        let nodes: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .filter_map(|p| p.mid().map(|vol| (p.log_moneyness, vol)))
+            .collect();
+        let (first, last) = (nodes.first()?, nodes.last()?);
+        if log_moneyness <= first.0 {
+            return Some(first.1);
+        }
+        if log_moneyness >= last.0 {
+            return Some(last.1);
+        }
+        nodes.windows(2).find(|w| log_moneyness <= w[1].0).map(|w| {
+            let weight = (log_moneyness - w[0].0) / (w[1].0 - w[0].0);
+            w[0].1 + weight * (w[1].1 - w[0].1)
+        })
+    }
+
+    /// Returns the volatility at any strike, listed or not.
+    #[must_use]
+    pub fn volatility(&self, strike: f64) -> Option<f64> {
+        self.volatility_at_log_moneyness(self.log_moneyness(strike))
+    }
+
+    /// Returns the at-the-forward volatility.
+    #[must_use]
+    pub fn atm_volatility(&self) -> Option<f64> {
+        self.volatility_at_log_moneyness(0.0)
+    }
+}
+
+impl VolatilityProvider for VolatilitySmile {
+    fn volatility(&self, expiration: &ExpirationDate, strike: u64, _: OptionStyle) -> Option<f64> {
+        if *expiration != self.expiration {
+            return None;
+        }
+        VolatilitySmile::volatility(self, strike as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use optionstratlib::prelude::pos_or_panic;
+
+    fn iv(mid: f64) -> ImpliedVolatilityQuote {
+        ImpliedVolatilityQuote {
+            bid: Some(mid - 0.01),
+            ask: Some(mid + 0.01),
+            mid: Some(mid),
+        }
+    }
+
+    fn point(strike: u64, forward: f64, call: f64, put: f64) -> SmilePoint {
+        SmilePoint {
+            strike,
+            log_moneyness: (strike as f64 / forward).ln(),
+            call: iv(call),
+            put: iv(put),
+        }
+    }
+
+    fn smile() -> VolatilitySmile {
+        VolatilitySmile::new(
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            100.0,
+            30.0 / 365.0,
+            vec![
+                point(110, 100.0, 0.18, 0.30),
+                point(90, 100.0, 0.40, 0.25),
+                point(100, 100.0, 0.20, 0.22),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_smile_otm_selection() {
+        let smile = smile();
+        assert_eq!(smile.points[0].strike, 90);
+        assert_eq!(smile.points[0].otm_style(), OptionStyle::Put);
+        assert_eq!(smile.points[0].mid(), Some(0.25));
+        assert_eq!(smile.points[1].otm_style(), OptionStyle::Call);
+        assert_eq!(smile.point(110).unwrap().mid(), Some(0.18));
+    }
+
+    #[test]
+    fn test_smile_interpolation_and_extrapolation() {
+        let smile = smile();
+        assert_eq!(smile.atm_volatility(), Some(0.20));
+        assert_eq!(smile.volatility(80.0), Some(0.25));
+        assert_eq!(smile.volatility(150.0), Some(0.18));
+
+        let vol = smile.volatility(95.0).unwrap();
+        assert!(vol > 0.20 && vol < 0.25);
+
+        let expiration = ExpirationDate::Days(pos_or_panic!(30.0));
+        let provided = VolatilityProvider::volatility(&smile, &expiration, 105, OptionStyle::Put);
+        assert!(provided.unwrap() > 0.18 && provided.unwrap() < 0.20);
+        let other = ExpirationDate::Days(pos_or_panic!(60.0));
+        assert!(VolatilityProvider::volatility(&smile, &other, 105, OptionStyle::Put).is_none());
+    }
+
+    #[test]
+    fn test_smile_falls_back_to_itm_mid() {
+        let mut p = point(110, 100.0, 0.18, 0.30);
+        p.call = ImpliedVolatilityQuote::default();
+        assert_eq!(p.mid(), Some(0.30));
+    }
+}
@@ -0,0 +1,402 @@
+//! Volatility surface module.
+//!
+//! This module provides the [`VolatilitySurface`] of an underlying: one SVI
+//! slice per expiration, interpolated across expirations in total variance
+//! at constant log-moneyness.
+
+use super::normal::norm_cdf;
+use super::smile::VolatilitySmile;
+use super::svi::SviFit;
+use super::volatility::VolatilityProvider;
+use crate::error::{Error, Result};
+use optionstratlib::{ExpirationDate, OptionStyle};
+use serde::{Deserialize, Serialize};
+
+/// Log-moneyness bounds of the delta-to-strike search.
+const DELTA_SEARCH_RANGE: f64 = 5.0;
+
+/// SVI slice of a single expiration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurfaceSlice {
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// Time to expiry in years when the slice was fitted.
+    pub years_to_expiry: f64,
+    /// Forward price, in strike units.
+    pub forward: f64,
+    /// SVI fit and its diagnostics.
+    pub fit: SviFit,
+}
+
+impl SurfaceSlice {
+    /// Fits a slice to the mid volatilities of a smile.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PricingError` if the smile has fewer than three mid
+    /// volatilities or the fit fails.
+    pub fn fit(smile: &VolatilitySmile) -> Result<Self> {
+        let points: Vec<(f64, f64)> = smile
+            .points
+            .iter()
+            .filter_map(|p| p.mid().map(|vol| (p.log_moneyness, vol)))
+            .collect();
+        Ok(Self {
+            expiration: smile.expiration,
+            years_to_expiry: smile.years_to_expiry,
+            forward: smile.forward,
+            fit: SviFit::fit(&points, smile.years_to_expiry)?,
+        })
+    }
+
+    /// Returns the total implied variance at a log-moneyness.
+    #[must_use]
+    pub fn total_variance(&self, log_moneyness: f64) -> f64 {
+        self.fit.parameters.total_variance(log_moneyness).max(0.0)
+    }
+
+    /// Returns the implied volatility at a strike.
+    #[must_use]
+    pub fn volatility(&self, strike: f64) -> f64 {
+        self.fit
+            .parameters
+            .volatility((strike / self.forward).ln(), self.years_to_expiry)
+    }
+}
+
+/// Volatility surface of an underlying.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolatilitySurface {
+    /// The underlying asset symbol.
+    pub underlying: String,
+    /// Timestamp of the build in milliseconds.
+    pub timestamp_ms: u64,
+    /// Slices sorted by time to expiry.
+    pub slices: Vec<SurfaceSlice>,
+    /// Expirations whose smile could not be fitted.
+    pub skipped: Vec<ExpirationDate>,
+}
+
+impl VolatilitySurface {
+    /// Fits a surface to the smiles of an underlying.
+    ///
+    /// Smiles that cannot be fitted are listed in `skipped`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NoDataAvailable` if no smile can be fitted.
+    pub fn from_smiles(underlying: impl Into<String>, smiles: &[VolatilitySmile]) -> Result<Self> {
+        let mut slices = Vec::with_capacity(smiles.len());
+        let mut skipped = Vec::new();
+        for smile in smiles {
+            match SurfaceSlice::fit(smile) {
+                Ok(slice) => slices.push(slice),
+                Err(_) => skipped.push(smile.expiration),
+            }
+        }
+        let underlying = underlying.into();
+        if slices.is_empty() {
+            return Err(Error::no_data(format!(
+                "no fittable volatility smile for {underlying}"
+            )));
+        }
+        slices.sort_by(|a, b| a.years_to_expiry.total_cmp(&b.years_to_expiry));
+        Ok(Self {
+            underlying,
+            timestamp_ms: orderbook_rs::current_time_millis(),
+            slices,
+            skipped,
+        })
+    }
+
+    /// Returns the slice of an expiration, if fitted.
+    #[must_use]
+    pub fn slice(&self, expiration: &ExpirationDate) -> Option<&SurfaceSlice> {
+        self.slices.iter().find(|s| s.expiration == *expiration)
+    }
+
+    /// Returns the forward at a time to expiry, interpolated log-linearly
+    /// between slices and held flat outside them.
+    #[must_use]
+    pub fn forward_at(&self, years: f64) -> f64 {
+        self.interpolate(years, |s| s.forward.ln(), |first, _| first, |last, _| last)
+            .exp()
+    }
+
+    /// Returns the total implied variance at a time to expiry and
+    /// log-moneyness.
+    ///
+    /// Between slices the total variance is interpolated linearly in time;
+    /// before the first slice it scales with time, and after the last slice
+    /// the volatility is held flat.
+    #[must_use]
+    pub fn total_variance_at(&self, years: f64, log_moneyness: f64) -> f64 {
+        self.interpolate(
+            years,
+            |s| s.total_variance(log_moneyness),
+            |w, t| w * years / t,
+            |w, t| w * years / t,
+        )
+    }
+
+    /// Returns the implied volatility at a time to expiry and log-moneyness.
+    #[must_use]
+    pub fn volatility_at(&self, years: f64, log_moneyness: f64) -> f64 {
+        if years <= 0.0 {
+            return 0.0;
+        }
+        (self.total_variance_at(years, log_moneyness) / years).sqrt()
+    }
+
+    /// Returns the implied volatility of a strike at an expiration, listed
+    /// or not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expiration cannot be converted to years.
+    pub fn volatility(&self, expiration: &ExpirationDate, strike: f64) -> Result<f64> {
+        if let Some(slice) = self.slice(expiration) {
+            return Ok(slice.volatility(strike));
+        }
+        let years = expiration.get_years()?.to_f64();
+        let log_moneyness = (strike / self.forward_at(years)).ln();
+        Ok(self.volatility_at(years, log_moneyness))
+    }
+
+    /// Returns the strike and implied volatility at a forward delta.
+    ///
+    /// # Arguments
+    ///
+    /// * `expiration` - The expiration date
+    /// * `delta` - Target delta, in `(0, 1)` for calls and `(-1, 0)` for puts
+    /// * `option_style` - Call or Put
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PricingError` if the delta is out of range or cannot
+    /// be reached, or an error if the expiration cannot be converted to years.
+    pub fn strike_for_delta(
+        &self,
+        expiration: &ExpirationDate,
+        delta: f64,
+        option_style: OptionStyle,
+    ) -> Result<(f64, f64)> {
+        let (years, forward, variance): (f64, f64, Box<dyn Fn(f64) -> f64>) =
+            match self.slice(expiration) {
+                Some(slice) => (
+                    slice.years_to_expiry,
+                    slice.forward,
+                    Box::new(|k| slice.total_variance(k)),
+                ),
+                None => {
+                    let years = expiration.get_years()?.to_f64();
+                    (
+                        years,
+                        self.forward_at(years),
+                        Box::new(move |k| self.total_variance_at(years, k)),
+                    )
+                }
+            };
+        let k = solve_delta(&*variance, delta, option_style)?;
+        let volatility = (variance(k) / years).sqrt();
+        Ok((forward * k.exp(), volatility))
+    }
+
+    /// Interpolates a per-slice value across time to expiry.
+    fn interpolate(
+        &self,
+        years: f64,
+        value: impl Fn(&SurfaceSlice) -> f64,
+        before: impl Fn(f64, f64) -> f64,
+        after: impl Fn(f64, f64) -> f64,
+    ) -> f64 {
+        let (Some(first), Some(last)) = (self.slices.first(), self.slices.last()) else {
+            return 0.0;
+        };
+        if years <= first.years_to_expiry {
+            return before(value(first), first.years_to_expiry);
+        }
+        if years >= last.years_to_expiry {
+            return after(value(last), last.years_to_expiry);
+        }
+        self.slices
+            .windows(2)
+            .find(|w| years <= w[1].years_to_expiry)
+            .map(|w| {
+                let weight =
+                    (years - w[0].years_to_expiry) / (w[1].years_to_expiry - w[0].years_to_expiry);
+                value(&w[0]) + weight * (value(&w[1]) - value(&w[0]))
+            })
+            .unwrap_or_else(|| value(last))
+    }
+}
+
+/// Finds the log-moneyness whose forward Black delta equals `delta`.
+///
+/// Forward delta is `N(d1)` for calls and `N(d1) - 1` for puts with
+/// `d1 = (-k + w / 2) / sqrt(w)`; both decrease with `k`.
+pub(crate) fn solve_delta(
+    variance: &dyn Fn(f64) -> f64,
+    delta: f64,
+    option_style: OptionStyle,
+) -> Result<f64> {
+    let valid = match option_style {
+        OptionStyle::Call => delta > 0.0 && delta < 1.0,
+        OptionStyle::Put => delta > -1.0 && delta < 0.0,
+    };
+    if !valid {
+        return Err(Error::pricing(format!(
+            "delta {delta} out of range for {option_style:?}"
+        )));
+    }
+    let delta_at = |k: f64| {
+        let w = variance(k).max(1e-12);
+        let n = norm_cdf((-k + w / 2.0) / w.sqrt());
+        match option_style {
+            OptionStyle::Call => n,
+            OptionStyle::Put => n - 1.0,
+        }
+    };
+
+    let (mut low, mut high) = (-DELTA_SEARCH_RANGE, DELTA_SEARCH_RANGE);
+    if delta_at(low) < delta || delta_at(high) > delta {
+        return Err(Error::pricing(format!("delta {delta} cannot be reached")));
+    }
+    for _ in 0..100 {
+        let mid = 0.5 * (low + high);
+        if delta_at(mid) > delta {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(0.5 * (low + high))
+}
+
+impl VolatilityProvider for VolatilitySurface {
+    fn volatility(&self, expiration: &ExpirationDate, strike: u64, _: OptionStyle) -> Option<f64> {
+        VolatilitySurface::volatility(self, expiration, strike as f64).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{ImpliedVolatilityQuote, SmilePoint, SviParameters};
+    use optionstratlib::prelude::pos_or_panic;
+
+    fn smile(days: f64, params: SviParameters) -> VolatilitySmile {
+        let years = days / 365.0;
+        let points = (-5..=5)
+            .map(|i| {
+                let k = f64::from(i) * 0.05;
+                let vol = params.volatility(k, years);
+                let iv = ImpliedVolatilityQuote {
+                    bid: None,
+                    ask: None,
+                    mid: Some(vol),
+                };
+                SmilePoint {
+                    strike: (100.0 * k.exp()).round() as u64,
+                    log_moneyness: k,
+                    call: iv,
+                    put: iv,
+                }
+            })
+            .collect();
+        VolatilitySmile::new(
+            ExpirationDate::Days(pos_or_panic!(days)),
+            100.0,
+            years,
+            points,
+        )
+    }
+
+    fn params(a: f64) -> SviParameters {
+        SviParameters {
+            a,
+            b: 0.08,
+            rho: -0.3,
+            m: 0.0,
+            sigma: 0.2,
+        }
+    }
+
+    fn surface() -> VolatilitySurface {
+        let mut thin = smile(45.0, params(0.01));
+        thin.points.truncate(2);
+        VolatilitySurface::from_smiles(
+            "SPX",
+            &[smile(91.25, params(0.02)), smile(30.0, params(0.005)), thin],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_surface_fits_and_skips() {
+        let surface = surface();
+        assert_eq!(surface.slices.len(), 2);
+        assert_eq!(surface.skipped.len(), 1);
+        assert!(surface.slices[0].years_to_expiry < surface.slices[1].years_to_expiry);
+        assert!(surface.slices.iter().all(|s| s.fit.rmse < 2e-3));
+
+        assert!(VolatilitySurface::from_smiles("SPX", &[]).is_err());
+    }
+
+    #[test]
+    fn test_surface_queries() {
+        let surface = surface();
+        let near = ExpirationDate::Days(pos_or_panic!(30.0));
+        let mid = ExpirationDate::Days(pos_or_panic!(60.0));
+
+        let listed = surface.volatility(&near, 100.0).unwrap();
+        assert!((listed - params(0.005).volatility(0.0, 30.0 / 365.0)).abs() < 2e-3);
+
+        // Total variance at 60 days lies between the two slices.
+        let w = surface.total_variance_at(60.0 / 365.0, 0.0);
+        let w_near = surface.slices[0].total_variance(0.0);
+        let w_far = surface.slices[1].total_variance(0.0);
+        assert!(w > w_near && w < w_far);
+        assert!(surface.volatility(&mid, 95.0).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_surface_strike_for_delta() {
+        let surface = surface();
+        let expiration = ExpirationDate::Days(pos_or_panic!(91.25));
+
+        let (call_strike, call_vol) = surface
+            .strike_for_delta(&expiration, 0.25, OptionStyle::Call)
+            .unwrap();
+        let (put_strike, put_vol) = surface
+            .strike_for_delta(&expiration, -0.25, OptionStyle::Put)
+            .unwrap();
+        assert!(call_strike > 100.0 && put_strike < 100.0);
+        // Negative rho: puts carry more volatility than calls.
+        assert!(put_vol > call_vol);
+
+        let (atm_strike, _) = surface
+            .strike_for_delta(&expiration, 0.5, OptionStyle::Call)
+            .unwrap();
+        assert!((atm_strike - 100.0).abs() < 5.0);
+        assert!(
+            surface
+                .strike_for_delta(&expiration, 1.5, OptionStyle::Call)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_surface_serialization_round_trip() {
+        let surface = surface();
+        let json = serde_json::to_string(&surface).unwrap();
+        let back: VolatilitySurface = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.underlying, surface.underlying);
+        assert_eq!(back.skipped, surface.skipped);
+        assert_eq!(back.slices.len(), surface.slices.len());
+        for (a, b) in back.slices.iter().zip(&surface.slices) {
+            assert_eq!(a.expiration, b.expiration);
+            assert!((a.total_variance(0.1) - b.total_variance(0.1)).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! SVI parameterization module.
+//!
+//! This module fits the raw SVI parameterization of total implied variance
+//! to the smile of one expiration:
+//!
+//! ```text
+//! w(k) = a + b * (rho * (k - m) + sqrt((k - m)^2 + sigma^2))
+//! ```
+//!
+//! where `k = ln(K / F)` and `w = vol^2 * T`. The fit uses the
+//! quasi-explicit method: for fixed `(m, sigma)` the remaining parameters
+//! solve a linear least-squares problem, and `(m, sigma)` are found by a
+//! refined grid search.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// Number of grid nodes per dimension in each search round.
+const GRID_NODES: usize = 15;
+
+/// Number of grid refinement rounds.
+const REFINEMENTS: usize = 5;
+
+/// Log-moneyness range checked for butterfly arbitrage.
+const ARBITRAGE_CHECK_RANGE: f64 = 1.5;
+
+/// Raw SVI parameters of one expiration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SviParameters {
+    /// Vertical level of total variance.
+    pub a: f64,
+    /// Slope of the wings.
+    pub b: f64,
+    /// Skew, in `[-1, 1]`.
+    pub rho: f64,
+    /// Horizontal translation.
+    pub m: f64,
+    /// Curvature at the vertex.
+    pub sigma: f64,
+}
+
+impl SviParameters {
+    /// Returns the total implied variance at a log-moneyness.
+    #[must_use]
+    pub fn total_variance(&self, log_moneyness: f64) -> f64 {
+        let x = log_moneyness - self.m;
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    /// Returns the implied volatility at a log-moneyness for a time to
+    /// expiry in years.
+    #[must_use]
+    pub fn volatility(&self, log_moneyness: f64, years_to_expiry: f64) -> f64 {
+        (self.total_variance(log_moneyness).max(0.0) / years_to_expiry).sqrt()
+    }
+
+    /// Returns Gatheral's butterfly density function `g(k)`; a negative
+    /// value signals butterfly arbitrage at `k`.
+    #[must_use]
+    pub fn density_factor(&self, log_moneyness: f64) -> f64 {
+        let x = log_moneyness - self.m;
+        let root = (x * x + self.sigma * self.sigma).sqrt();
+        let w = self.total_variance(log_moneyness);
+        let w1 = self.b * (self.rho + x / root);
+        let w2 = self.b * self.sigma * self.sigma / root.powi(3);
+        if w <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        (1.0 - log_moneyness * w1 / (2.0 * w)).powi(2) - w1 * w1 / 4.0 * (1.0 / w + 0.25) + w2 / 2.0
+    }
+
+    /// Returns true if the slice is free of butterfly arbitrage over the
+    /// checked log-moneyness range.
+    #[must_use]
+    pub fn is_butterfly_free(&self) -> bool {
+        let steps = (2.0 * ARBITRAGE_CHECK_RANGE * 100.0) as i32;
+        (0..=steps).all(|i| {
+            let k = -ARBITRAGE_CHECK_RANGE + f64::from(i) / 100.0;
+            self.density_factor(k) >= -1e-9
+        })
+    }
+}
+
+/// SVI fit of one expiration with its diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SviFit {
+    /// Fitted parameters.
+    pub parameters: SviParameters,
+    /// Root mean square error in volatility.
+    pub rmse: f64,
+    /// Largest absolute error in volatility.
+    pub max_error: f64,
+    /// Number of points used in the fit.
+    pub point_count: usize,
+    /// Whether the fitted slice is free of butterfly arbitrage.
+    pub butterfly_free: bool,
+}
+
+impl SviFit {
+    /// Fits SVI to `(log_moneyness, volatility)` points.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - Log-moneyness and implied volatility pairs
+    /// * `years_to_expiry` - Time to expiry in years
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PricingError` if there are fewer than three points,
+    /// the time to expiry is not positive, or no admissible fit is found.
+    pub fn fit(points: &[(f64, f64)], years_to_expiry: f64) -> Result<Self> {
+        if points.len() < 3 {
+            return Err(Error::pricing("SVI fit requires at least three points"));
+        }
+        if years_to_expiry <= 0.0 {
+            return Err(Error::pricing("SVI fit requires a positive time to expiry"));
+        }
+        let variances: Vec<(f64, f64)> = points
+            .iter()
+            .map(|&(k, vol)| (k, vol * vol * years_to_expiry))
+            .collect();
+
+        let k_min = variances.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+        let k_max = variances
+            .iter()
+            .map(|p| p.0)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let span = (k_max - k_min).max(0.05);
+
+        let mut m_range = (k_min - span, k_max + span);
+        let mut log_sigma_range = (0.001f64.ln(), (2.0 * span).ln());
+        let mut best: Option<(f64, SviParameters)> = None;
+
+        for _ in 0..REFINEMENTS {
+            for i in 0..GRID_NODES {
+                let m = grid_node(m_range, i);
+                for j in 0..GRID_NODES {
+                    let sigma = grid_node(log_sigma_range, j).exp();
+                    let Some(candidate) = fit_linear(&variances, m, sigma) else {
+                        continue;
+                    };
+                    if best.is_none_or(|(sse, _)| candidate.0 < sse) {
+                        best = Some(candidate);
+                    }
+                }
+            }
+            let (_, p) = best.ok_or_else(|| Error::pricing("no admissible SVI fit"))?;
+            let m_step = (m_range.1 - m_range.0) / (GRID_NODES - 1) as f64;
+            let s_step = (log_sigma_range.1 - log_sigma_range.0) / (GRID_NODES - 1) as f64;
+            m_range = (p.m - 2.0 * m_step, p.m + 2.0 * m_step);
+            let log_sigma = p.sigma.ln();
+            log_sigma_range = (log_sigma - 2.0 * s_step, log_sigma + 2.0 * s_step);
+        }
+
+        let (_, parameters) = best.ok_or_else(|| Error::pricing("no admissible SVI fit"))?;
+        let errors: Vec<f64> = points
+            .iter()
+            .map(|&(k, vol)| (parameters.volatility(k, years_to_expiry) - vol).abs())
+            .collect();
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+
+        Ok(Self {
+            parameters,
+            rmse,
+            max_error: errors.iter().copied().fold(0.0, f64::max),
+            point_count: points.len(),
+            butterfly_free: parameters.is_butterfly_free(),
+        })
+    }
+}
+
+/// Returns the `i`-th node of an evenly spaced grid over `range`.
+fn grid_node(range: (f64, f64), i: usize) -> f64 {
+    range.0 + (range.1 - range.0) * i as f64 / (GRID_NODES - 1) as f64
+}
+
+/// Solves the linear least-squares problem for fixed `(m, sigma)`.
+///
+/// With `y = (k - m) / sigma`, total variance is `a + d * y + c * sqrt(y^2 + 1)`
+/// where `c = b * sigma` and `d = rho * b * sigma`. The solution is projected
+/// onto `c >= 0`, `|d| <= c` and a non-negative minimum variance. Returns the
+/// sum of squared errors and the parameters.
+fn fit_linear(variances: &[(f64, f64)], m: f64, sigma: f64) -> Option<(f64, SviParameters)> {
+    let basis = |k: f64| {
+        let y = (k - m) / sigma;
+        [1.0, y, (y * y + 1.0).sqrt()]
+    };
+
+    let mut ata = [[0.0; 3]; 3];
+    let mut atb = [0.0; 3];
+    for &(k, w) in variances {
+        let row = basis(k);
+        for r in 0..3 {
+            atb[r] += row[r] * w;
+            for c in 0..3 {
+                ata[r][c] += row[r] * row[c];
+            }
+        }
+    }
+    let [_, d, c] = solve3(ata, atb)?;
+
+    let c = c.max(0.0);
+    let d = d.clamp(-c, c);
+    // Re-fit the level for the projected slope terms.
+    let a = variances
+        .iter()
+        .map(|&(k, w)| {
+            let row = basis(k);
+            w - d * row[1] - c * row[2]
+        })
+        .sum::<f64>()
+        / variances.len() as f64;
+    let a = a.max(-(c * c - d * d).sqrt());
+
+    let sse = variances
+        .iter()
+        .map(|&(k, w)| {
+            let row = basis(k);
+            (a + d * row[1] + c * row[2] - w).powi(2)
+        })
+        .sum();
+    let parameters = SviParameters {
+        a,
+        b: c / sigma,
+        rho: if c > 0.0 { d / c } else { 0.0 },
+        m,
+        sigma,
+    };
+    Some((sse, parameters))
+}
+
+/// Solves a 3x3 linear system by Gaussian elimination with partial pivoting.
+fn solve3(mut m: [[f64; 3]; 3], mut v: [f64; 3]) -> Option<[f64; 3]> {
+    for col in 0..3 {
+        let pivot = (col..3).max_by(|&i, &j| m[i][col].abs().total_cmp(&m[j][col].abs()))?;
+        if m[pivot][col].abs() < 1e-14 {
+            return None;
+        }
+        m.swap(col, pivot);
+        v.swap(col, pivot);
+        for row in col + 1..3 {
+            let factor = m[row][col] / m[col][col];
+            let (upper, lower) = m.split_at_mut(row);
+            for (x, p) in lower[0][col..].iter_mut().zip(&upper[col][col..]) {
+                *x -= factor * p;
+            }
+            v[row] -= factor * v[col];
+        }
+    }
+    let mut x = [0.0; 3];
+    for row in (0..3).rev() {
+        let tail: f64 = (row + 1..3).map(|k| m[row][k] * x[k]).sum();
+        x[row] = (v[row] - tail) / m[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: SviParameters = SviParameters {
+        a: 0.01,
+        b: 0.1,
+        rho: -0.4,
+        m: 0.02,
+        sigma: 0.15,
+    };
+
+    #[test]
+    fn test_svi_fit_recovers_parameters() {
+        let t = 0.5;
+        let points: Vec<(f64, f64)> = (-6..=6)
+            .map(|i| {
+                let k = f64::from(i) * 0.05;
+                (k, PARAMS.volatility(k, t))
+            })
+            .collect();
+
+        let fit = SviFit::fit(&points, t).unwrap();
+        assert_eq!(fit.point_count, 13);
+        assert!(fit.rmse < 1e-3, "rmse {}", fit.rmse);
+        assert!(fit.max_error < 2e-3);
+        assert!(fit.butterfly_free);
+        for &(k, vol) in &points {
+            assert!((fit.parameters.volatility(k, t) - vol).abs() < 2e-3);
+        }
+    }
+
+    #[test]
+    fn test_svi_fit_requires_points() {
+        assert!(SviFit::fit(&[(0.0, 0.2), (0.1, 0.2)], 0.5).is_err());
+        assert!(SviFit::fit(&[(0.0, 0.2), (0.1, 0.2), (0.2, 0.2)], 0.0).is_err());
+    }
+
+    #[test]
+    fn test_svi_arbitrage_check() {
+        assert!(PARAMS.is_butterfly_free());
+        let steep = SviParameters {
+            a: -0.05,
+            b: 2.0,
+            rho: 0.9,
+            m: 0.0,
+            sigma: 0.01,
+        };
+        assert!(!steep.is_butterfly_free());
+    }
+}
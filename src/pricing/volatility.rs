@@ -0,0 +1,115 @@
+//! Volatility provider module.
+//!
+//! This module provides the [`VolatilityProvider`] trait used by the Greeks
+//! engine to look up the volatility of each contract, with flat and
+//! per-strike implementations.
+
+use optionstratlib::{ExpirationDate, OptionStyle};
+use std::collections::HashMap;
+
+/// Source of annualized volatilities for option contracts.
+pub trait VolatilityProvider: Send + Sync {
+    /// Returns the volatility of a contract, or `None` if it is not known.
+    ///
+    /// # Arguments
+    ///
+    /// * `expiration` - The expiration date
+    /// * `strike` - The strike price
+    /// * `option_style` - Call or Put
+    fn volatility(
+        &self,
+        expiration: &ExpirationDate,
+        strike: u64,
+        option_style: OptionStyle,
+    ) -> Option<f64>;
+}
+
+/// The same volatility for every contract.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlatVolatility(pub f64);
+
+impl VolatilityProvider for FlatVolatility {
+    fn volatility(&self, _: &ExpirationDate, _: u64, _: OptionStyle) -> Option<f64> {
+        Some(self.0)
+    }
+}
+
+/// Volatilities set per expiration and strike, shared by the call and put.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrikeVolatility {
+    /// Volatilities indexed by expiration and strike.
+    volatilities: HashMap<(ExpirationDate, u64), f64>,
+    /// Volatility used for contracts without an explicit entry.
+    fallback: Option<f64>,
+}
+
+impl StrikeVolatility {
+    /// Creates an empty provider without a fallback.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the volatility used for contracts without an explicit entry.
+    #[must_use]
+    pub const fn with_fallback(mut self, volatility: f64) -> Self {
+        self.fallback = Some(volatility);
+        self
+    }
+
+    /// Sets the volatility of a strike.
+    pub fn insert(&mut self, expiration: ExpirationDate, strike: u64, volatility: f64) {
+        self.volatilities.insert((expiration, strike), volatility);
+    }
+
+    /// Returns the number of explicit entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.volatilities.len()
+    }
+
+    /// Returns true if there are no explicit entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.volatilities.is_empty()
+    }
+}
+
+impl VolatilityProvider for StrikeVolatility {
+    fn volatility(&self, expiration: &ExpirationDate, strike: u64, _: OptionStyle) -> Option<f64> {
+        self.volatilities
+            .get(&(*expiration, strike))
+            .copied()
+            .or(self.fallback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use optionstratlib::prelude::pos_or_panic;
+
+    #[test]
+    fn test_strike_volatility_lookup() {
+        let expiration = ExpirationDate::Days(pos_or_panic!(30.0));
+        let mut vols = StrikeVolatility::new();
+        vols.insert(expiration, 100, 0.25);
+
+        assert_eq!(vols.len(), 1);
+        assert_eq!(
+            vols.volatility(&expiration, 100, OptionStyle::Put),
+            Some(0.25)
+        );
+        assert_eq!(vols.volatility(&expiration, 110, OptionStyle::Call), None);
+
+        let vols = vols.with_fallback(0.3);
+        assert_eq!(
+            vols.volatility(&expiration, 110, OptionStyle::Call),
+            Some(0.3)
+        );
+        assert_eq!(
+            FlatVolatility(0.2).volatility(&expiration, 1, OptionStyle::Call),
+            Some(0.2)
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Quoting module.
+//!
+//! This module places and maintains the market maker's two-sided quotes in
+//! the order book hierarchy.
+//!
+//! ## Components
+//!
+//! - [`TheoProvider`]: Theoretical value per contract ([`TheoTable`], [`ModelTheo`])
+//! - [`QuoteParams`]: Width in ticks, volatility points or the optimal half spread, size, minimum edge and re-quote threshold
+//! - [`SkewedTheo`]: Theoretical values shifted by delta, vega and contract inventory (Avellaneda-Stoikov)
+//! - [`ChainQuoter`]: Maintains two-sided quotes in the selected contracts of a chain
+
+mod params;
+mod quoter;
+mod skew;
+mod theo;
+
+pub use params::{QuoteParams, QuoteTarget, QuoteWidth};
+pub use quoter::{ChainQuoter, QuoteCycleReport};
+pub use skew::{Inventory, SkewParams, SkewedTheo};
+pub use theo::{ModelTheo, Theo, TheoProvider, TheoTable};
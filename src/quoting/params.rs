@@ -0,0 +1,239 @@
+//! Quote parameters module.
+//!
+//! This module provides the [`QuoteParams`] that turn a theoretical value
+//! into the target bid and ask of a contract ([`QuoteTarget`]).
+
+use super::theo::Theo;
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// Distance of the bid and ask from the theoretical value, on each side.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum QuoteWidth {
+    /// A number of ticks.
+    Ticks(u32),
+    /// A number of volatility points, converted to price with the vega of
+    /// the theoretical value.
+    VolPoints(f64),
+    /// The half spread of the theoretical value, such as the
+    /// Avellaneda-Stoikov spread set by a
+    /// [`SkewedTheo`](super::SkewedTheo) with an arrival decay.
+    Optimal,
+}
+
+/// Parameters of a two-sided quote.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuoteParams {
+    /// Distance of each side from the theoretical value.
+    pub width: QuoteWidth,
+    /// Quantity quoted on each side.
+    pub size: u64,
+    /// Minimum distance of each side from the theoretical value, in price
+    /// units.
+    pub min_edge: f64,
+    /// Price increment of the contracts.
+    pub tick_size: u128,
+    /// Largest change of a target price, in price units, that leaves the
+    /// resting order in place.
+    pub requote_threshold: u128,
+}
+
+/// Target bid and ask of a contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteTarget {
+    /// Bid price, `None` if it would not be positive.
+    pub bid: Option<u128>,
+    /// Ask price.
+    pub ask: u128,
+    /// Quantity on each side.
+    pub size: u64,
+}
+
+impl QuoteParams {
+    /// Creates parameters with no minimum edge, re-quoting on any change.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Distance of each side from the theoretical value
+    /// * `size` - Quantity quoted on each side
+    /// * `tick_size` - Price increment of the contracts
+    #[must_use]
+    pub const fn new(width: QuoteWidth, size: u64, tick_size: u128) -> Self {
+        Self {
+            width,
+            size,
+            min_edge: 0.0,
+            tick_size,
+            requote_threshold: 0,
+        }
+    }
+
+    /// Sets the minimum distance of each side from the theoretical value.
+    #[must_use]
+    pub const fn with_min_edge(mut self, min_edge: f64) -> Self {
+        self.min_edge = min_edge;
+        self
+    }
+
+    /// Sets the largest target change that leaves resting orders in place.
+    #[must_use]
+    pub const fn with_requote_threshold(mut self, requote_threshold: u128) -> Self {
+        self.requote_threshold = requote_threshold;
+        self
+    }
+
+    /// Checks that the parameters can produce quotes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigurationError` if the size or tick size is
+    /// zero, the width is not positive, or the minimum edge is negative.
+    pub fn validate(&self) -> Result<()> {
+        if self.size == 0 || self.tick_size == 0 {
+            return Err(Error::configuration(
+                "quote size and tick size must be positive",
+            ));
+        }
+        let width_valid = match self.width {
+            QuoteWidth::Ticks(ticks) => ticks > 0,
+            QuoteWidth::VolPoints(points) => points.is_finite() && points > 0.0,
+            QuoteWidth::Optimal => true,
+        };
+        if !width_valid {
+            return Err(Error::configuration(format!(
+                "quote width {:?} must be positive",
+                self.width
+            )));
+        }
+        if !(self.min_edge.is_finite() && self.min_edge >= 0.0) {
+            return Err(Error::configuration(format!(
+                "minimum edge {} must be non-negative",
+                self.min_edge
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the target bid and ask around a theoretical value.
+    ///
+    /// The bid is rounded down and the ask up to the tick size, so each
+    /// side keeps at least the quoted width and the minimum edge.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::QuotingError` if the width is in volatility points
+    /// and the theoretical value has no vega, the width is optimal and the
+    /// theoretical value has no finite half spread, or the theoretical
+    /// price is not finite.
+    pub fn target(&self, theo: &Theo) -> Result<QuoteTarget> {
+        if !theo.price.is_finite() {
+            return Err(Error::quoting(format!(
+                "theoretical price {} is not finite",
+                theo.price
+            )));
+        }
+        let tick = self.tick_size.max(1) as f64;
+        let half_width = match self.width {
+            QuoteWidth::Ticks(ticks) => f64::from(ticks) * tick,
+            QuoteWidth::VolPoints(points) => {
+                let vega = theo.vega.ok_or_else(|| {
+                    Error::quoting("a width in volatility points requires a vega")
+                })?;
+                points * vega.abs()
+            }
+            QuoteWidth::Optimal => theo
+                .half_spread
+                .filter(|half_spread| half_spread.is_finite())
+                .ok_or_else(|| Error::quoting("an optimal width requires a half spread"))?,
+        };
+        let half_width = half_width.max(self.min_edge);
+
+        let bid = ((theo.price - half_width) / tick).floor();
+        let ask = ((theo.price + half_width) / tick).ceil().max(1.0);
+        Ok(QuoteTarget {
+            bid: (bid >= 1.0).then(|| bid as u128 * self.tick_size),
+            ask: ask as u128 * self.tick_size,
+            size: self.size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_in_ticks() {
+        let params = QuoteParams::new(QuoteWidth::Ticks(2), 10, 5);
+        let target = params.target(&Theo::new(101.0)).unwrap();
+        // 101 - 10 = 91 rounds down to 90; 101 + 10 = 111 rounds up to 115.
+        assert_eq!(target.bid, Some(90));
+        assert_eq!(target.ask, 115);
+        assert_eq!(target.size, 10);
+
+        // A minimum edge wider than the width takes over.
+        let target = params
+            .with_min_edge(20.0)
+            .target(&Theo::new(101.0))
+            .unwrap();
+        assert_eq!(target.bid, Some(80));
+        assert_eq!(target.ask, 125);
+
+        // Cheap options have no bid.
+        let target = params.target(&Theo::new(3.0)).unwrap();
+        assert_eq!(target.bid, None);
+        assert_eq!(target.ask, 15);
+    }
+
+    #[test]
+    fn test_target_in_vol_points() {
+        let params = QuoteParams::new(QuoteWidth::VolPoints(0.5), 1, 1);
+        assert!(params.target(&Theo::new(50.0)).is_err());
+        let target = params.target(&Theo::new(50.0).with_vega(4.0)).unwrap();
+        assert_eq!(target.bid, Some(48));
+        assert_eq!(target.ask, 52);
+    }
+
+    #[test]
+    fn test_target_optimal() {
+        let params = QuoteParams::new(QuoteWidth::Optimal, 1, 1);
+        assert!(params.validate().is_ok());
+        assert!(params.target(&Theo::new(50.0)).is_err());
+        let target = params
+            .target(&Theo::new(50.0).with_half_spread(2.5))
+            .unwrap();
+        assert_eq!(target.bid, Some(47));
+        assert_eq!(target.ask, 53);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            QuoteParams::new(QuoteWidth::Ticks(1), 1, 1)
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            QuoteParams::new(QuoteWidth::Ticks(0), 1, 1)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            QuoteParams::new(QuoteWidth::Ticks(1), 0, 1)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            QuoteParams::new(QuoteWidth::Ticks(1), 1, 0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            QuoteParams::new(QuoteWidth::VolPoints(f64::NAN), 1, 1)
+                .validate()
+                .is_err()
+        );
+        let negative = QuoteParams::new(QuoteWidth::Ticks(1), 1, 1).with_min_edge(-1.0);
+        assert!(negative.validate().is_err());
+    }
+}
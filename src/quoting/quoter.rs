@@ -0,0 +1,608 @@
+//! Chain quoter module.
+//!
+//! This module provides the [`ChainQuoter`], which maintains the market
+//! maker's resting bid and ask in the selected contracts of a chain.
+
+use super::params::{QuoteParams, QuoteTarget};
+use super::theo::TheoProvider;
+use crate::error::{Error, Result};
+use crate::orderbook::{
+    MassQuoteEntry, OptionChainOrderBook, OptionOrderBook, ParticipantOrder, ParticipantQuote,
+    QuoteLevel, SideAction, UnderlyingOrderBook,
+};
+use crate::risk::RiskEngine;
+use optionstratlib::OptionStyle;
+use orderbook_rs::Side;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Outcome of one quoting cycle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteCycleReport {
+    /// Orders placed.
+    pub placed: usize,
+    /// Orders cancelled.
+    pub cancelled: usize,
+    /// Resting orders left in place.
+    pub unchanged: usize,
+    /// Sides not placed because they would trade against the book.
+    pub crossing: usize,
+    /// Sides not placed because a pre-trade check failed.
+    pub rejected: usize,
+    /// Contracts pulled for lack of a theoretical value or target.
+    pub pulled: usize,
+}
+
+/// Maintains two-sided quotes in the selected contracts of a chain.
+///
+/// The quoter's orders are the participant quotes of its participant id in
+/// each contract book (see [`OptionOrderBook::replace_quote`]). Each cycle
+/// computes the target bid and ask of every selected contract from its
+/// theoretical value and the [`QuoteParams`], and only replaces a resting
+/// order when its target moves by more than the re-quote threshold or it
+/// is no longer fully in the book (filled or cancelled). Contracts without
+/// a theoretical value are pulled.
+///
+/// [`update`](Self::update) places orders directly in the books;
+/// [`update_checked`](Self::update_checked) places them through a
+/// [`RiskEngine`] for the participant's account.
+pub struct ChainQuoter {
+    /// The quoted chain.
+    chain: Arc<OptionChainOrderBook>,
+    /// Participant id the quotes are placed under.
+    participant: String,
+    /// Quote parameters.
+    params: QuoteParams,
+    /// Selected contracts.
+    selected: BTreeSet<(u64, OptionStyle)>,
+}
+
+impl ChainQuoter {
+    /// Creates a quoter for a chain with no selected contracts.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain` - The quoted chain
+    /// * `participant` - Participant id of the quotes, also the account
+    ///   of checked cycles
+    /// * `params` - Quote parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigurationError` if the parameters are invalid.
+    pub fn new(
+        chain: Arc<OptionChainOrderBook>,
+        participant: impl Into<String>,
+        params: QuoteParams,
+    ) -> Result<Self> {
+        params.validate()?;
+        Ok(Self {
+            chain,
+            participant: participant.into(),
+            params,
+            selected: BTreeSet::new(),
+        })
+    }
+
+    /// Returns the quoted chain.
+    #[must_use]
+    pub fn chain(&self) -> &OptionChainOrderBook {
+        &self.chain
+    }
+
+    /// Returns the participant id of the quotes.
+    #[must_use]
+    pub fn participant(&self) -> &str {
+        &self.participant
+    }
+
+    /// Returns the quote parameters.
+    #[must_use]
+    pub const fn params(&self) -> &QuoteParams {
+        &self.params
+    }
+
+    /// Replaces the quote parameters; resting orders are updated on the
+    /// next cycle.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigurationError` if the parameters are invalid.
+    pub fn set_params(&mut self, params: QuoteParams) -> Result<()> {
+        params.validate()?;
+        self.params = params;
+        Ok(())
+    }
+
+    /// Selects a contract for quoting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::StrikeNotFound` if the strike does not exist.
+    pub fn select(&mut self, strike: u64, option_style: OptionStyle) -> Result<()> {
+        self.chain.get_strike(strike)?;
+        self.selected.insert((strike, option_style));
+        Ok(())
+    }
+
+    /// Selects the call and put of every listed strike. Returns the number
+    /// of selected contracts.
+    pub fn select_all(&mut self) -> usize {
+        for strike in self.chain.strike_prices() {
+            for option_style in [OptionStyle::Call, OptionStyle::Put] {
+                self.selected.insert((strike, option_style));
+            }
+        }
+        self.selected.len()
+    }
+
+    /// Stops quoting a contract and cancels its resting orders. Returns
+    /// false if the contract was not selected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an order cannot be cancelled.
+    pub fn deselect(&mut self, strike: u64, option_style: OptionStyle) -> Result<bool> {
+        if !self.selected.remove(&(strike, option_style)) {
+            return Ok(false);
+        }
+        if let Ok(book) = self.chain.get_strike(strike) {
+            book.get(option_style)
+                .cancel_participant_quote(&self.participant)?;
+        }
+        Ok(true)
+    }
+
+    /// Returns the number of selected contracts.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Returns true if no contract is selected.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Returns the resting quote of a selected contract.
+    #[must_use]
+    pub fn quote(&self, strike: u64, option_style: OptionStyle) -> Option<ParticipantQuote> {
+        if !self.selected.contains(&(strike, option_style)) {
+            return None;
+        }
+        let book = self.chain.get_strike(strike).ok()?;
+        Some(
+            book.get(option_style)
+                .participant_quote(&self.participant)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Runs one quoting cycle over the selected contracts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an order cannot be placed or cancelled;
+    /// contracts processed before the error keep their new quotes.
+    pub fn update(&mut self, theos: &dyn TheoProvider) -> Result<QuoteCycleReport> {
+        self.cycle(theos, &mut OrderEntry::Direct)
+    }
+
+    /// Runs one quoting cycle, placing every order through the pre-trade
+    /// checks of a risk engine on behalf of the participant's account.
+    ///
+    /// Pending fills of the underlying are applied before and after the
+    /// cycle. Sides failing a check are counted as rejected and left empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if `book` is not the underlying of
+    /// the chain, or an error if an order cannot be placed or cancelled.
+    pub fn update_checked(
+        &mut self,
+        theos: &dyn TheoProvider,
+        engine: &mut RiskEngine,
+        book: &UnderlyingOrderBook,
+    ) -> Result<QuoteCycleReport> {
+        if book.underlying() != self.chain.underlying() {
+            return Err(Error::validation(format!(
+                "quoter of {} given the book of {}",
+                self.chain.underlying(),
+                book.underlying()
+            )));
+        }
+        engine.process_fills(book);
+        let report = self.cycle(theos, &mut OrderEntry::Checked { engine, book });
+        engine.process_fills(book);
+        report
+    }
+
+    /// Runs one quoting cycle with the given order entry.
+    fn cycle(
+        &mut self,
+        theos: &dyn TheoProvider,
+        entry: &mut OrderEntry<'_>,
+    ) -> Result<QuoteCycleReport> {
+        let mut report = QuoteCycleReport::default();
+        for &(strike, option_style) in &self.selected {
+            // A delisted strike took its orders with it.
+            let Ok(strike_book) = self.chain.get_strike(strike) else {
+                continue;
+            };
+            let book = strike_book.get(option_style);
+            let theo = theos.theo(strike, option_style);
+            let target = theo.and_then(|theo| self.params.target(&theo).ok());
+            let (Some(theo), Some(target)) = (theo, target) else {
+                report.cancelled += book.cancel_participant_quote(&self.participant)?;
+                report.pulled += 1;
+                continue;
+            };
+            let contract = Contract {
+                book,
+                participant: &self.participant,
+                entry: MassQuoteEntry {
+                    expiration: *self.chain.expiration(),
+                    strike,
+                    option_style,
+                    bid: None,
+                    ask: None,
+                },
+                theo: theo.price,
+            };
+            self.quote_contract(&contract, &target, entry, &mut report)?;
+        }
+        Ok(report)
+    }
+
+    /// Updates the quote of one contract towards its target.
+    fn quote_contract(
+        &self,
+        contract: &Contract<'_>,
+        target: &QuoteTarget,
+        entry: &mut OrderEntry<'_>,
+        report: &mut QuoteCycleReport,
+    ) -> Result<()> {
+        let book = contract.book;
+        let current = book
+            .participant_quote(contract.participant)
+            .unwrap_or_default();
+        // Retire both stale sides before placing either, so a new side is
+        // never checked against, or matched with, our own old order.
+        let mut bid = self.kept_level(book, current.bid, target.bid);
+        let ask = self.kept_level(book, current.ask, Some(target.ask));
+        let retired = book.replace_quote(contract.participant, bid, ask)?;
+        for action in [retired.0, retired.1] {
+            match action {
+                SideAction::Unchanged => report.unchanged += 1,
+                SideAction::Cancelled => report.cancelled += 1,
+                _ => {}
+            }
+        }
+
+        // Place the new sides one at a time, so a side that would cross or
+        // fails a check leaves the other side quoted.
+        if bid.is_none()
+            && let Some(price) = target.bid
+        {
+            let level = QuoteLevel::new(price, target.size);
+            if crosses(book, Side::Buy, price) {
+                report.crossing += 1;
+            } else if entry.place(contract, Some(level), ask)? {
+                bid = Some(level);
+                report.placed += 1;
+            } else {
+                report.rejected += 1;
+            }
+        }
+        if ask.is_none() {
+            let level = QuoteLevel::new(target.ask, target.size);
+            if crosses(book, Side::Sell, target.ask) {
+                report.crossing += 1;
+            } else if entry.place(contract, bid, Some(level))? {
+                report.placed += 1;
+            } else {
+                report.rejected += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the level of a resting side to keep: the order is still
+    /// fully in the book and within the re-quote threshold of its target.
+    fn kept_level(
+        &self,
+        book: &OptionOrderBook,
+        resting: Option<ParticipantOrder>,
+        price: Option<u128>,
+    ) -> Option<QuoteLevel> {
+        let order = resting?;
+        let live = book
+            .inner()
+            .get_order(order.order_id)
+            .is_some_and(|o| o.visible_quantity() == order.size);
+        (live && order.price.abs_diff(price?) <= self.params.requote_threshold)
+            .then(|| QuoteLevel::new(order.price, order.size))
+    }
+
+    /// Cancels every resting quote, keeping the selection. Returns the
+    /// number of orders cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an order cannot be cancelled.
+    pub fn pull_all(&mut self) -> Result<usize> {
+        let mut cancelled = 0;
+        for &(strike, option_style) in &self.selected {
+            if let Ok(book) = self.chain.get_strike(strike) {
+                cancelled += book
+                    .get(option_style)
+                    .cancel_participant_quote(&self.participant)?;
+            }
+        }
+        Ok(cancelled)
+    }
+}
+
+/// How the quoter places orders.
+enum OrderEntry<'a> {
+    /// Directly in the contract books.
+    Direct,
+    /// Through the pre-trade checks of a risk engine.
+    Checked {
+        /// The engine running the checks.
+        engine: &'a mut RiskEngine,
+        /// The underlying order book of the chain.
+        book: &'a UnderlyingOrderBook,
+    },
+}
+
+impl OrderEntry<'_> {
+    /// Replaces the participant's quote in a contract. Returns false if
+    /// a pre-trade check failed, leaving the previous quote in place.
+    fn place(
+        &mut self,
+        contract: &Contract<'_>,
+        bid: Option<QuoteLevel>,
+        ask: Option<QuoteLevel>,
+    ) -> Result<bool> {
+        match self {
+            Self::Direct => {
+                contract
+                    .book
+                    .replace_quote(contract.participant, bid, ask)?;
+                Ok(true)
+            }
+            Self::Checked { engine, book } => {
+                let entry = MassQuoteEntry {
+                    bid,
+                    ask,
+                    ..contract.entry
+                };
+                match engine.apply_mass_quote_entry(
+                    book,
+                    contract.participant,
+                    &entry,
+                    Some(contract.theo),
+                ) {
+                    Ok(_) => Ok(true),
+                    Err(
+                        Error::RiskLimitBreached { .. }
+                        | Error::InventoryLimitExceeded { .. }
+                        | Error::GreeksError { .. },
+                    ) => Ok(false),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+}
+
+/// A quoted contract.
+struct Contract<'a> {
+    /// The contract book.
+    book: &'a OptionOrderBook,
+    /// Participant id of the quote.
+    participant: &'a str,
+    /// The contract as a mass quote entry, without levels.
+    entry: MassQuoteEntry,
+    /// Theoretical value, in price units.
+    theo: f64,
+}
+
+/// Returns true if an order at `price` would trade against the book.
+fn crosses(book: &OptionOrderBook, side: Side, price: u128) -> bool {
+    match side {
+        Side::Buy => book.best_ask().is_some_and(|ask| price >= ask),
+        Side::Sell => book.best_bid().is_some_and(|bid| price <= bid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quoting::{QuoteWidth, Theo, TheoTable};
+    use optionstratlib::ExpirationDate;
+    use optionstratlib::prelude::pos_or_panic;
+    use orderbook_rs::OrderId;
+
+    fn chain() -> Arc<OptionChainOrderBook> {
+        let chain = OptionChainOrderBook::new("BTC", ExpirationDate::Days(pos_or_panic!(30.0)));
+        chain.get_or_create_strike(100);
+        chain.get_or_create_strike(110);
+        Arc::new(chain)
+    }
+
+    fn theos(call_100: f64) -> TheoTable {
+        let mut theos = TheoTable::new();
+        theos.insert(100, OptionStyle::Call, Theo::new(call_100));
+        theos.insert(100, OptionStyle::Put, Theo::new(40.0));
+        theos.insert(110, OptionStyle::Call, Theo::new(20.0));
+        theos
+    }
+
+    #[test]
+    fn test_quotes_selected_contracts() {
+        let chain = chain();
+        let params = QuoteParams::new(QuoteWidth::Ticks(2), 5, 1);
+        let mut quoter = ChainQuoter::new(Arc::clone(&chain), "MM", params).unwrap();
+        assert!(quoter.select(120, OptionStyle::Call).is_err());
+        quoter.select(100, OptionStyle::Call).unwrap();
+        quoter.select(110, OptionStyle::Call).unwrap();
+
+        let report = quoter.update(&theos(50.0)).unwrap();
+        assert_eq!(report.placed, 4);
+        let call = chain.get_strike(100).unwrap().call_quote();
+        assert_eq!(call.bid_price(), Some(48));
+        assert_eq!(call.ask_price(), Some(52));
+        assert_eq!(call.bid_size(), 5);
+        // The put is not selected.
+        assert!(
+            chain
+                .get_strike(100)
+                .unwrap()
+                .put_quote()
+                .bid_price()
+                .is_none()
+        );
+
+        assert_eq!(quoter.select_all(), 4);
+        // The 110 put has no theo and is pulled.
+        let report = quoter.update(&theos(50.0)).unwrap();
+        assert_eq!(report.unchanged, 4);
+        assert_eq!(report.placed, 2);
+        assert_eq!(report.pulled, 1);
+
+        assert!(quoter.deselect(100, OptionStyle::Call).unwrap());
+        assert!(!quoter.deselect(100, OptionStyle::Call).unwrap());
+        assert_eq!(chain.get_strike(100).unwrap().call().order_count(), 0);
+        assert_eq!(quoter.pull_all().unwrap(), 4);
+        assert_eq!(chain.total_order_count(), 0);
+    }
+
+    #[test]
+    fn test_requote_threshold_and_fills() {
+        let chain = chain();
+        let params = QuoteParams::new(QuoteWidth::Ticks(2), 5, 1).with_requote_threshold(1);
+        let mut quoter = ChainQuoter::new(Arc::clone(&chain), "MM", params).unwrap();
+        quoter.select(100, OptionStyle::Call).unwrap();
+        quoter.update(&theos(50.0)).unwrap();
+        let first = quoter.quote(100, OptionStyle::Call).unwrap();
+
+        // A one-tick move stays within the threshold.
+        let report = quoter.update(&theos(51.0)).unwrap();
+        assert_eq!(report.unchanged, 2);
+        assert_eq!(quoter.quote(100, OptionStyle::Call).unwrap(), first);
+
+        // A larger move replaces both sides.
+        let report = quoter.update(&theos(53.0)).unwrap();
+        assert_eq!((report.cancelled, report.placed), (2, 2));
+        let book = chain.get_strike(100).unwrap();
+        assert_eq!(book.call_quote().bid_price(), Some(51));
+        assert_eq!(book.call().order_count(), 2);
+
+        // A filled bid is replaced on the next cycle.
+        let bid = quoter.quote(100, OptionStyle::Call).unwrap().bid.unwrap();
+        book.call().cancel_order(bid.order_id).unwrap();
+        let report = quoter.update(&theos(53.0)).unwrap();
+        assert_eq!((report.placed, report.unchanged), (1, 1));
+        assert_ne!(quoter.quote(100, OptionStyle::Call).unwrap().bid, Some(bid));
+
+        // A partially filled ask is refreshed to the full size.
+        book.call()
+            .inner()
+            .submit_market_order(OrderId::new(), 2, Side::Buy)
+            .unwrap();
+        let report = quoter.update(&theos(53.0)).unwrap();
+        assert_eq!((report.cancelled, report.placed), (1, 1));
+        assert_eq!(book.call().ask_depth_at_price(55), 5);
+
+        // The quotes are the participant quote of the quoter in the book.
+        assert_eq!(
+            book.call().participant_quote("MM"),
+            quoter.quote(100, OptionStyle::Call)
+        );
+    }
+
+    #[test]
+    fn test_requote_large_theo_jump() {
+        let chain = chain();
+        let params = QuoteParams::new(QuoteWidth::Ticks(2), 5, 1);
+        let mut quoter = ChainQuoter::new(Arc::clone(&chain), "MM", params).unwrap();
+        quoter.select(100, OptionStyle::Call).unwrap();
+        quoter.update(&theos(50.0)).unwrap();
+
+        // The new bid is above the old ask: both sides move without
+        // trading against each other.
+        let report = quoter.update(&theos(60.0)).unwrap();
+        assert_eq!((report.cancelled, report.placed), (2, 2));
+        assert_eq!(report.crossing, 0);
+        let book = chain.get_strike(100).unwrap();
+        assert_eq!(book.call_quote().bid_price(), Some(58));
+        assert_eq!(book.call_quote().ask_price(), Some(62));
+        assert_eq!(book.call().activity().volume, 0);
+
+        let report = quoter.update(&theos(40.0)).unwrap();
+        assert_eq!((report.placed, report.crossing), (2, 0));
+        assert_eq!(book.call_quote().bid_price(), Some(38));
+        assert_eq!(book.call_quote().ask_price(), Some(42));
+        assert_eq!(book.call().order_count(), 2);
+    }
+
+    #[test]
+    fn test_update_checked() {
+        use crate::orderbook::UnderlyingOrderBook;
+        use crate::risk::{PositionKeeper, PreTradeLimits};
+
+        let book = UnderlyingOrderBook::new("BTC");
+        let expiration = book.get_or_create_expiration(ExpirationDate::Days(pos_or_panic!(30.0)));
+        expiration.get_or_create_strike(100);
+        let params = QuoteParams::new(QuoteWidth::Ticks(2), 5, 1);
+        let mut quoter = ChainQuoter::new(expiration.chain_arc(), "MM", params).unwrap();
+        quoter.select(100, OptionStyle::Call).unwrap();
+
+        let limits = PreTradeLimits::none().with_max_order_size(4);
+        let mut engine = RiskEngine::new(limits, PositionKeeper::default());
+        let report = quoter
+            .update_checked(&theos(50.0), &mut engine, &book)
+            .unwrap();
+        assert_eq!((report.placed, report.rejected), (0, 2));
+        assert_eq!(expiration.chain().total_order_count(), 0);
+
+        engine.set_limits(PreTradeLimits::none().with_max_order_size(5));
+        let report = quoter
+            .update_checked(&theos(50.0), &mut engine, &book)
+            .unwrap();
+        assert_eq!((report.placed, report.rejected), (2, 0));
+        assert_eq!(engine.open_order_count(&book, "MM"), 2);
+
+        let other = UnderlyingOrderBook::new("ETH");
+        assert!(
+            quoter
+                .update_checked(&theos(50.0), &mut engine, &other)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_does_not_cross_the_book() {
+        let chain = chain();
+        let params = QuoteParams::new(QuoteWidth::Ticks(2), 5, 1);
+        let mut quoter = ChainQuoter::new(Arc::clone(&chain), "MM", params).unwrap();
+        quoter.select(100, OptionStyle::Call).unwrap();
+        let book = chain.get_strike(100).unwrap();
+        book.call()
+            .add_limit_order(OrderId::new(), Side::Sell, 47, 1)
+            .unwrap();
+
+        let report = quoter.update(&theos(50.0)).unwrap();
+        assert_eq!(report.crossing, 1);
+        assert_eq!(report.placed, 1);
+        assert!(quoter.quote(100, OptionStyle::Call).unwrap().bid.is_none());
+
+        let invalid = QuoteParams::new(QuoteWidth::Ticks(0), 5, 1);
+        assert!(quoter.set_params(invalid).is_err());
+        assert!(ChainQuoter::new(chain, "MM", invalid).is_err());
+    }
+}
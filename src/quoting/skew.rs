@@ -0,0 +1,500 @@
+//! Inventory skew module.
+//!
+//! This module shifts theoretical values by the market maker's inventory,
+//! in the style of Avellaneda-Stoikov: each contract is quoted around a
+//! reservation price that makes trades reducing the inventory's risk more
+//! likely than trades adding to it.
+//!
+//! The risk of the inventory is modelled with two factors shared by every
+//! contract, the underlying price and implied volatility, plus a residual
+//! risk of each contract. For a contract with delta `d`, vega `v` and
+//! multiplier `m`, the reservation price is
+//!
+//! ```text
+//! r = theo - gamma * tau * (d * sigma_s^2 * D + v * sigma_v^2 * V + sigma_e^2 * m * q)
+//! ```
+//!
+//! where `D` and `V` are the aggregated delta and vega of the inventory
+//! ([`Inventory`]), `q` the position in the contract, `gamma` the risk
+//! aversion and `tau` the horizon ([`SkewParams`]).
+
+use super::theo::{Theo, TheoProvider};
+use crate::error::{Error, Result};
+use crate::orderbook::{
+    AggregatedGreeks, ExposureWeights, OptionChainOrderBook, PositionSet, StrikeOrderBook,
+    UnderlyingOrderBook,
+};
+use optionstratlib::OptionStyle;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// Risk parameters of the inventory skew.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SkewParams {
+    /// Risk aversion, per price unit of inventory value.
+    pub risk_aversion: f64,
+    /// Horizon over which the inventory is held, in years.
+    pub horizon_years: f64,
+    /// Annualized volatility of the underlying, as a fraction.
+    pub underlying_volatility: f64,
+    /// Annualized standard deviation of implied volatility, in volatility
+    /// points.
+    pub vol_of_vol: f64,
+    /// Annualized standard deviation of each contract's price not
+    /// explained by the underlying and volatility, in price units.
+    pub residual_volatility: f64,
+    /// Decay of the order arrival rate with the distance from the
+    /// reservation price, per price unit.
+    pub arrival_decay: f64,
+}
+
+impl SkewParams {
+    /// Creates parameters with no factor or residual risk, which skew
+    /// nothing until a volatility is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `risk_aversion` - Risk aversion per price unit of inventory value
+    /// * `horizon_years` - Holding horizon in years
+    #[must_use]
+    pub const fn new(risk_aversion: f64, horizon_years: f64) -> Self {
+        Self {
+            risk_aversion,
+            horizon_years,
+            underlying_volatility: 0.0,
+            vol_of_vol: 0.0,
+            residual_volatility: 0.0,
+            arrival_decay: 0.0,
+        }
+    }
+
+    /// Sets the annualized volatility of the underlying.
+    #[must_use]
+    pub const fn with_underlying_volatility(mut self, volatility: f64) -> Self {
+        self.underlying_volatility = volatility;
+        self
+    }
+
+    /// Sets the annualized standard deviation of implied volatility.
+    #[must_use]
+    pub const fn with_vol_of_vol(mut self, vol_of_vol: f64) -> Self {
+        self.vol_of_vol = vol_of_vol;
+        self
+    }
+
+    /// Sets the annualized residual standard deviation of contract prices.
+    #[must_use]
+    pub const fn with_residual_volatility(mut self, volatility: f64) -> Self {
+        self.residual_volatility = volatility;
+        self
+    }
+
+    /// Sets the decay of the order arrival rate used by
+    /// [`optimal_half_spread`](Self::optimal_half_spread).
+    #[must_use]
+    pub const fn with_arrival_decay(mut self, arrival_decay: f64) -> Self {
+        self.arrival_decay = arrival_decay;
+        self
+    }
+
+    /// Checks that the parameters are usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigurationError` if the risk aversion or horizon
+    /// is not positive, or a volatility or the arrival decay is negative.
+    pub fn validate(&self) -> Result<()> {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        let non_negative = |value: f64| value.is_finite() && value >= 0.0;
+        if !positive(self.risk_aversion) || !positive(self.horizon_years) {
+            return Err(Error::configuration(format!(
+                "risk aversion {} and horizon {} must be positive",
+                self.risk_aversion, self.horizon_years
+            )));
+        }
+        let others = [
+            self.underlying_volatility,
+            self.vol_of_vol,
+            self.residual_volatility,
+            self.arrival_decay,
+        ];
+        if !others.into_iter().all(non_negative) {
+            return Err(Error::configuration(
+                "skew volatilities and arrival decay must be non-negative",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the Avellaneda-Stoikov half spread for a contract whose
+    /// price has the given annualized variance:
+    /// `(gamma * variance * tau + 2 / gamma * ln(1 + gamma / k)) / 2`.
+    ///
+    /// Returns `None` if no arrival decay is set.
+    #[must_use]
+    pub fn optimal_half_spread(&self, variance: f64) -> Option<f64> {
+        if self.arrival_decay <= 0.0 {
+            return None;
+        }
+        let gamma = self.risk_aversion;
+        let inventory_term = gamma * variance * self.horizon_years;
+        let arrival_term = 2.0 / gamma * (gamma / self.arrival_decay).ln_1p();
+        Some((inventory_term + arrival_term) / 2.0)
+    }
+}
+
+/// Positions of the market maker with their aggregated delta and vega.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    /// Signed position per contract symbol.
+    pub positions: PositionSet,
+    /// Aggregated delta in underlying units, including multipliers.
+    pub delta: f64,
+    /// Aggregated vega per volatility point, including multipliers.
+    pub vega: f64,
+    /// Underlying price the delta was measured at.
+    pub underlying_price: f64,
+}
+
+impl Inventory {
+    /// Creates an inventory from positions and their aggregated Greeks.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if the underlying price is not
+    /// positive.
+    pub fn from_greeks(
+        positions: PositionSet,
+        greeks: &AggregatedGreeks,
+        underlying_price: f64,
+    ) -> Result<Self> {
+        if !(underlying_price.is_finite() && underlying_price > 0.0) {
+            return Err(Error::market_data(format!(
+                "underlying price {underlying_price} must be positive"
+            )));
+        }
+        Ok(Self {
+            delta: greeks.cash_delta / underlying_price,
+            vega: greeks.vega_per_point,
+            underlying_price,
+            positions,
+        })
+    }
+
+    /// Creates the inventory of the positions in one chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if the chain has no underlying
+    /// price.
+    pub fn from_chain(chain: &OptionChainOrderBook, positions: PositionSet) -> Result<Self> {
+        let price = chain
+            .underlying_price()
+            .ok_or_else(|| Error::market_data("no underlying price for the chain"))?;
+        let greeks = chain.aggregate_greeks(&ExposureWeights::Positions(&positions));
+        Self::from_greeks(positions, &greeks, price as f64)
+    }
+
+    /// Creates the inventory of the positions across every expiration of an
+    /// underlying, so that each chain is skewed by the risk of the whole
+    /// book.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MarketDataError` if the underlying has no spot
+    /// price.
+    pub fn from_underlying(book: &UnderlyingOrderBook, positions: PositionSet) -> Result<Self> {
+        let price = book.spot_price().ok_or_else(|| {
+            Error::market_data(format!("no spot price for {}", book.underlying()))
+        })?;
+        let greeks = book.aggregate_greeks(&ExposureWeights::Positions(&positions));
+        Self::from_greeks(positions, &greeks, price as f64)
+    }
+}
+
+/// Theoretical values of a chain shifted to the inventory's reservation
+/// prices.
+///
+/// Each contract's delta and vega are read from its stored Greeks; without
+/// Greeks the delta is taken as zero and the vega of the inner theoretical
+/// value is used. Contracts not listed in the chain are passed through.
+pub struct SkewedTheo<'a> {
+    /// The unskewed theoretical values.
+    inner: &'a dyn TheoProvider,
+    /// The quoted chain.
+    chain: &'a OptionChainOrderBook,
+    /// Risk parameters.
+    params: SkewParams,
+    /// The inventory to skew for.
+    inventory: &'a Inventory,
+}
+
+impl<'a> SkewedTheo<'a> {
+    /// Creates a skewed provider.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ConfigurationError` if the parameters are invalid.
+    pub fn new(
+        inner: &'a dyn TheoProvider,
+        chain: &'a OptionChainOrderBook,
+        params: SkewParams,
+        inventory: &'a Inventory,
+    ) -> Result<Self> {
+        params.validate()?;
+        Ok(Self {
+            inner,
+            chain,
+            params,
+            inventory,
+        })
+    }
+
+    /// Returns the amount subtracted from a contract's theoretical value:
+    /// positive when the inventory is long the contract's risk.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - The strike price
+    /// * `option_style` - Call or Put
+    /// * `theo` - The unskewed theoretical value, used for its vega
+    #[must_use]
+    pub fn reservation_shift(&self, strike: u64, option_style: OptionStyle, theo: &Theo) -> f64 {
+        let Ok(strike_book) = self.chain.get_strike(strike) else {
+            return 0.0;
+        };
+        let (delta, vega, _) = self.exposure(&strike_book, option_style, theo);
+        let book = strike_book.get(option_style);
+        let multiplier = strike_book.terms().multiplier.to_f64().unwrap_or(1.0);
+        let position = self.inventory.positions.get(book.symbol()) as f64;
+
+        let params = &self.params;
+        let underlying_variance =
+            (params.underlying_volatility * self.inventory.underlying_price).powi(2);
+        let covariance = delta * underlying_variance * self.inventory.delta
+            + vega * params.vol_of_vol.powi(2) * self.inventory.vega
+            + params.residual_volatility.powi(2) * multiplier * position;
+        params.risk_aversion * params.horizon_years * covariance
+    }
+
+    /// Returns the Avellaneda-Stoikov half spread of a contract, or `None`
+    /// if it has no theoretical value or no arrival decay is set.
+    #[must_use]
+    pub fn half_spread(&self, strike: u64, option_style: OptionStyle) -> Option<f64> {
+        let theo = self.inner.theo(strike, option_style)?;
+        self.contract_half_spread(strike, option_style, &theo)
+    }
+
+    /// Returns the Avellaneda-Stoikov half spread of a contract given its
+    /// unskewed theoretical value.
+    fn contract_half_spread(
+        &self,
+        strike: u64,
+        option_style: OptionStyle,
+        theo: &Theo,
+    ) -> Option<f64> {
+        let strike_book = self.chain.get_strike(strike).ok()?;
+        let (_, _, variance) = self.exposure(&strike_book, option_style, theo);
+        self.params.optimal_half_spread(variance)
+    }
+
+    /// Returns the delta, vega and annualized price variance of a contract.
+    fn exposure(
+        &self,
+        strike_book: &StrikeOrderBook,
+        option_style: OptionStyle,
+        theo: &Theo,
+    ) -> (f64, f64, f64) {
+        let greeks = strike_book.greeks_snapshot(option_style).map(|s| s.greeks);
+        let delta = greeks
+            .as_ref()
+            .and_then(|g| g.delta.to_f64())
+            .unwrap_or_default();
+        let vega = greeks
+            .as_ref()
+            .and_then(|g| g.vega.to_f64())
+            .or(theo.vega)
+            .unwrap_or_default();
+        let params = &self.params;
+        let variance = (delta * params.underlying_volatility * self.inventory.underlying_price)
+            .powi(2)
+            + (vega * params.vol_of_vol).powi(2)
+            + params.residual_volatility.powi(2);
+        (delta, vega, variance)
+    }
+}
+
+/// Skewed theoretical values carry the Avellaneda-Stoikov half spread when
+/// an arrival decay is set, for quoting with [`QuoteWidth::Optimal`].
+///
+/// [`QuoteWidth::Optimal`]: super::QuoteWidth::Optimal
+impl TheoProvider for SkewedTheo<'_> {
+    fn theo(&self, strike: u64, option_style: OptionStyle) -> Option<Theo> {
+        let theo = self.inner.theo(strike, option_style)?;
+        let shift = self.reservation_shift(strike, option_style, &theo);
+        Some(Theo {
+            price: theo.price - shift,
+            vega: theo.vega,
+            half_spread: self
+                .contract_half_spread(strike, option_style, &theo)
+                .or(theo.half_spread),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::ReferencePriceKind;
+    use crate::pricing::FlatVolatility;
+    use crate::quoting::{ChainQuoter, QuoteParams, QuoteWidth, TheoTable};
+    use optionstratlib::ExpirationDate;
+    use optionstratlib::prelude::pos_or_panic;
+    use orderbook_rs::{OrderId, Side};
+    use std::sync::Arc;
+
+    fn chain() -> Arc<OptionChainOrderBook> {
+        let chain = OptionChainOrderBook::new("SPX", ExpirationDate::Days(pos_or_panic!(30.0)));
+        chain.get_or_create_strike(100);
+        chain.get_or_create_strike(110);
+        chain.reference().update(ReferencePriceKind::Index, 100);
+        Arc::new(chain)
+    }
+
+    #[test]
+    fn test_params_validation_and_half_spread() {
+        assert!(SkewParams::new(0.0, 1.0).validate().is_err());
+        assert!(
+            SkewParams::new(0.1, 1.0)
+                .with_vol_of_vol(-1.0)
+                .validate()
+                .is_err()
+        );
+        let params = SkewParams::new(0.1, 0.5);
+        assert!(params.optimal_half_spread(4.0).is_none());
+        let params = params.with_arrival_decay(1.5);
+        let expected = (0.1 * 4.0 * 0.5 + 2.0 / 0.1 * (1.0_f64 + 0.1 / 1.5).ln()) / 2.0;
+        assert!((params.optimal_half_spread(4.0).unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_optimal_width_from_skewed_half_spread() {
+        let chain = chain();
+        let mut theos = TheoTable::new();
+        theos.insert(100, OptionStyle::Call, Theo::new(50.0));
+        let inventory = Inventory::from_chain(&chain, PositionSet::new()).unwrap();
+        let quote = QuoteParams::new(QuoteWidth::Optimal, 1, 1);
+
+        let params = SkewParams::new(0.5, 0.1).with_residual_volatility(2.0);
+        let skewed = SkewedTheo::new(&theos, &chain, params, &inventory).unwrap();
+        let theo = skewed.theo(100, OptionStyle::Call).unwrap();
+        assert!(theo.half_spread.is_none());
+        assert!(quote.target(&theo).is_err());
+
+        let params = params.with_arrival_decay(1.0);
+        let skewed = SkewedTheo::new(&theos, &chain, params, &inventory).unwrap();
+        let theo = skewed.theo(100, OptionStyle::Call).unwrap();
+        let expected = params.optimal_half_spread(4.0).unwrap();
+        assert!((theo.half_spread.unwrap() - expected).abs() < 1e-12);
+        assert_eq!(skewed.half_spread(100, OptionStyle::Call), theo.half_spread);
+        // 50 -/+ 0.91 rounds out to 49 and 51.
+        let target = quote.target(&theo).unwrap();
+        assert_eq!((target.bid, target.ask), (Some(49), 51));
+    }
+
+    #[test]
+    fn test_residual_skew_of_own_position() {
+        let chain = chain();
+        let mut theos = TheoTable::new();
+        theos.insert(100, OptionStyle::Call, Theo::new(50.0));
+        theos.insert(110, OptionStyle::Call, Theo::new(20.0));
+        let params = SkewParams::new(0.5, 0.1).with_residual_volatility(2.0);
+
+        let symbol = chain.get_strike(100).unwrap().call().symbol().to_string();
+        let mut positions = PositionSet::new();
+        positions.set(symbol.clone(), 10);
+        let inventory = Inventory::from_chain(&chain, positions).unwrap();
+        let skewed = SkewedTheo::new(&theos, &chain, params, &inventory).unwrap();
+
+        // Long 10: 0.5 * 0.1 * 4 * 10 = 2 below theo; other contracts flat.
+        let theo = skewed.theo(100, OptionStyle::Call).unwrap();
+        assert!((theo.price - 48.0).abs() < 1e-9);
+        assert!((skewed.theo(110, OptionStyle::Call).unwrap().price - 20.0).abs() < 1e-9);
+        assert!(skewed.theo(100, OptionStyle::Put).is_none());
+
+        let mut positions = PositionSet::new();
+        positions.set(symbol, -10);
+        let inventory = Inventory::from_chain(&chain, positions).unwrap();
+        let skewed = SkewedTheo::new(&theos, &chain, params, &inventory).unwrap();
+        assert!((skewed.theo(100, OptionStyle::Call).unwrap().price - 52.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_delta_inventory_skews_calls_and_puts_apart() {
+        let chain = chain();
+        chain.refresh_greeks(&FlatVolatility(0.2)).unwrap();
+        let mut theos = TheoTable::new();
+        theos.insert(100, OptionStyle::Call, Theo::new(3.0));
+        theos.insert(100, OptionStyle::Put, Theo::new(3.0));
+
+        // Long calls at 110: a long delta inventory.
+        let mut positions = PositionSet::new();
+        let symbol = chain.get_strike(110).unwrap().call().symbol().to_string();
+        positions.set(symbol, 20);
+        let inventory = Inventory::from_chain(&chain, positions).unwrap();
+        assert!(inventory.delta > 0.0);
+        assert!(inventory.vega > 0.0);
+
+        let params = SkewParams::new(0.01, 0.1).with_underlying_volatility(0.2);
+        let skewed = SkewedTheo::new(&theos, &chain, params, &inventory).unwrap();
+        // Calls add delta and are skewed down; puts reduce it and go up.
+        assert!(skewed.theo(100, OptionStyle::Call).unwrap().price < 3.0);
+        assert!(skewed.theo(100, OptionStyle::Put).unwrap().price > 3.0);
+        assert!(
+            Inventory::from_chain(
+                &OptionChainOrderBook::new("X", ExpirationDate::Days(pos_or_panic!(30.0))),
+                PositionSet::new()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_simulated_fills_walk_quotes_down() {
+        let chain = chain();
+        let mut theos = TheoTable::new();
+        theos.insert(100, OptionStyle::Call, Theo::new(50.0));
+        let params = SkewParams::new(0.5, 0.1).with_residual_volatility(2.0);
+        let book = chain.get_strike(100).unwrap();
+        let symbol = book.call().symbol().to_string();
+
+        let mut quoter = ChainQuoter::new(
+            Arc::clone(&chain),
+            "MM",
+            QuoteParams::new(QuoteWidth::Ticks(1), 1, 1),
+        )
+        .unwrap();
+        quoter.select(100, OptionStyle::Call).unwrap();
+        let mut positions = PositionSet::new();
+        let mut reservations = Vec::new();
+        for _ in 0..4 {
+            let inventory = Inventory::from_chain(&chain, positions.clone()).unwrap();
+            let skewed = SkewedTheo::new(&theos, &chain, params, &inventory).unwrap();
+            reservations.push(skewed.theo(100, OptionStyle::Call).unwrap().price);
+            quoter.update(&skewed).unwrap();
+            // A seller hits the bid: the market maker gets longer.
+            book.call()
+                .inner()
+                .submit_market_order(OrderId::new(), 1, Side::Sell)
+                .unwrap();
+            positions.add(symbol.clone(), 1);
+        }
+        // Each unit of inventory lowers the reservation price by 0.2.
+        for (reservation, expected) in reservations.iter().zip([50.0, 49.8, 49.6, 49.4]) {
+            assert!((reservation - expected).abs() < 1e-9);
+        }
+        let inventory = Inventory::from_chain(&chain, positions).unwrap();
+        let skewed = SkewedTheo::new(&theos, &chain, params, &inventory).unwrap();
+        assert!((skewed.theo(100, OptionStyle::Call).unwrap().price - 49.2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,189 @@
+//! Theoretical value module.
+//!
+//! This module provides the [`TheoProvider`] trait the quoter reads the
+//! theoretical value of each contract from, with a table implementation and
+//! a model implementation priced off the chain's market data.
+
+use crate::orderbook::OptionChainOrderBook;
+use crate::pricing::{VolatilityProvider, compute_greeks};
+use optionstratlib::OptionStyle;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Theoretical value of a contract.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Theo {
+    /// Theoretical price, in price units.
+    pub price: f64,
+    /// Change of price for one volatility point, if known.
+    pub vega: Option<f64>,
+    /// Half spread to quote around the price, in price units, if the
+    /// provider sets one (see [`QuoteWidth::Optimal`](super::QuoteWidth::Optimal)).
+    #[serde(default)]
+    pub half_spread: Option<f64>,
+}
+
+impl Theo {
+    /// Creates a theoretical value without vega.
+    #[must_use]
+    pub const fn new(price: f64) -> Self {
+        Self {
+            price,
+            vega: None,
+            half_spread: None,
+        }
+    }
+
+    /// Sets the vega per volatility point.
+    #[must_use]
+    pub const fn with_vega(mut self, vega: f64) -> Self {
+        self.vega = Some(vega);
+        self
+    }
+
+    /// Sets the half spread to quote around the price.
+    #[must_use]
+    pub const fn with_half_spread(mut self, half_spread: f64) -> Self {
+        self.half_spread = Some(half_spread);
+        self
+    }
+}
+
+/// Source of theoretical values for the contracts of a chain.
+pub trait TheoProvider: Send + Sync {
+    /// Returns the theoretical value of a contract, or `None` if it is not
+    /// known.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - The strike price
+    /// * `option_style` - Call or Put
+    fn theo(&self, strike: u64, option_style: OptionStyle) -> Option<Theo>;
+}
+
+/// Theoretical values set per contract.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TheoTable {
+    /// Theoretical values indexed by strike and option style.
+    theos: HashMap<(u64, OptionStyle), Theo>,
+}
+
+impl TheoTable {
+    /// Creates an empty table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the theoretical value of a contract.
+    pub fn insert(&mut self, strike: u64, option_style: OptionStyle, theo: Theo) {
+        self.theos.insert((strike, option_style), theo);
+    }
+
+    /// Removes the theoretical value of a contract.
+    pub fn remove(&mut self, strike: u64, option_style: OptionStyle) -> Option<Theo> {
+        self.theos.remove(&(strike, option_style))
+    }
+
+    /// Returns the number of contracts with a theoretical value.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.theos.len()
+    }
+
+    /// Returns true if the table is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.theos.is_empty()
+    }
+}
+
+impl TheoProvider for TheoTable {
+    fn theo(&self, strike: u64, option_style: OptionStyle) -> Option<Theo> {
+        self.theos.get(&(strike, option_style)).copied()
+    }
+}
+
+/// Theoretical values priced off a chain's market data and a volatility
+/// provider, with Black-Scholes vega.
+pub struct ModelTheo<'a> {
+    /// The chain supplying the underlying price, rates and pricing model.
+    chain: &'a OptionChainOrderBook,
+    /// Theoretical volatilities.
+    volatilities: &'a dyn VolatilityProvider,
+}
+
+impl<'a> ModelTheo<'a> {
+    /// Creates a model provider for a chain.
+    #[must_use]
+    pub fn new(chain: &'a OptionChainOrderBook, volatilities: &'a dyn VolatilityProvider) -> Self {
+        Self {
+            chain,
+            volatilities,
+        }
+    }
+}
+
+impl TheoProvider for ModelTheo<'_> {
+    fn theo(&self, strike: u64, option_style: OptionStyle) -> Option<Theo> {
+        let price = self
+            .chain
+            .theoretical_value(strike, option_style, self.volatilities)
+            .ok()?;
+        let vega = self
+            .volatilities
+            .volatility(self.chain.expiration(), strike, option_style)
+            .and_then(|vol| self.chain.greeks_inputs(vol).ok())
+            .and_then(|inputs| compute_greeks(strike, option_style, &inputs).ok())
+            .and_then(|greeks| greeks.vega.to_f64());
+        Some(Theo {
+            price,
+            vega,
+            half_spread: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::ReferencePriceKind;
+    use crate::pricing::FlatVolatility;
+    use optionstratlib::ExpirationDate;
+    use optionstratlib::prelude::pos_or_panic;
+
+    #[test]
+    fn test_theo_table() {
+        let mut table = TheoTable::new();
+        assert!(table.is_empty());
+        table.insert(100, OptionStyle::Call, Theo::new(5.0).with_vega(0.2));
+        assert_eq!(table.len(), 1);
+        assert_eq!(
+            table.theo(100, OptionStyle::Call),
+            Some(Theo {
+                price: 5.0,
+                vega: Some(0.2),
+                half_spread: None,
+            })
+        );
+        assert!(table.theo(100, OptionStyle::Put).is_none());
+        assert!(table.remove(100, OptionStyle::Call).is_some());
+    }
+
+    #[test]
+    fn test_model_theo() {
+        let chain = OptionChainOrderBook::new("SPX", ExpirationDate::Days(pos_or_panic!(30.0)));
+        chain.get_or_create_strike(100);
+        let vols = FlatVolatility(0.2);
+        let model = ModelTheo::new(&chain, &vols);
+        // No underlying price yet.
+        assert!(model.theo(100, OptionStyle::Call).is_none());
+
+        chain.reference().update(ReferencePriceKind::Index, 100);
+        let theo = model.theo(100, OptionStyle::Call).unwrap();
+        assert!(theo.price > 0.0);
+        assert!(theo.vega.unwrap() > 0.0);
+        assert!(model.theo(200, OptionStyle::Call).is_none());
+    }
+}
@@ -0,0 +1,715 @@
+//! Position keeper module.
+//!
+//! This module provides the [`PositionKeeper`], which turns the executions
+//! of the contract books into per-account positions, rolls them up the
+//! hierarchy and enforces [`InventoryLimits`].
+
+use super::position::{ContractFill, ContractKey, Position, PositionRollup};
+use crate::error::{Error, Result};
+use crate::orderbook::{
+    OptionChainOrderBook, OptionOrderBook, PositionSet, UnderlyingOrderBook,
+    UnderlyingOrderBookManager,
+};
+use optionstratlib::OptionStyle;
+use orderbook_rs::{OrderId, Side};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Level of the hierarchy a position or limit applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LimitScope {
+    /// One contract.
+    Contract,
+    /// The call and put of a strike.
+    Strike,
+    /// Every contract of an expiration.
+    Expiration,
+    /// Every contract of an underlying.
+    Underlying,
+}
+
+impl LimitScope {
+    /// All scopes, from the narrowest.
+    pub const ALL: [Self; 4] = [
+        Self::Contract,
+        Self::Strike,
+        Self::Expiration,
+        Self::Underlying,
+    ];
+
+    /// Returns true if a contract falls within the scope of another.
+    #[must_use]
+    pub fn contains(&self, scope_key: &ContractKey, key: &ContractKey) -> bool {
+        let same_underlying = scope_key.underlying == key.underlying;
+        let same_expiration = same_underlying && scope_key.expiration == key.expiration;
+        let same_strike = same_expiration && scope_key.strike == key.strike;
+        match self {
+            Self::Contract => same_strike && scope_key.option_style == key.option_style,
+            Self::Strike => same_strike,
+            Self::Expiration => same_expiration,
+            Self::Underlying => same_underlying,
+        }
+    }
+}
+
+impl fmt::Display for LimitScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Contract => "contract",
+            Self::Strike => "strike",
+            Self::Expiration => "expiration",
+            Self::Underlying => "underlying",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Maximum inventory of an account.
+///
+/// The contract limit applies to the absolute net position in a contract;
+/// the strike, expiration and underlying limits to the gross position
+/// (sum of absolute positions) of their contracts. `None` is unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InventoryLimits {
+    /// Maximum absolute position per contract.
+    pub max_contract: Option<u64>,
+    /// Maximum gross position per strike.
+    pub max_strike: Option<u64>,
+    /// Maximum gross position per expiration.
+    pub max_expiration: Option<u64>,
+    /// Maximum gross position per underlying.
+    pub max_underlying: Option<u64>,
+}
+
+impl InventoryLimits {
+    /// Creates limits with no maximum.
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_contract: None,
+            max_strike: None,
+            max_expiration: None,
+            max_underlying: None,
+        }
+    }
+
+    /// Sets the maximum absolute position per contract.
+    #[must_use]
+    pub const fn with_max_contract(mut self, limit: u64) -> Self {
+        self.max_contract = Some(limit);
+        self
+    }
+
+    /// Sets the maximum gross position per strike.
+    #[must_use]
+    pub const fn with_max_strike(mut self, limit: u64) -> Self {
+        self.max_strike = Some(limit);
+        self
+    }
+
+    /// Sets the maximum gross position per expiration.
+    #[must_use]
+    pub const fn with_max_expiration(mut self, limit: u64) -> Self {
+        self.max_expiration = Some(limit);
+        self
+    }
+
+    /// Sets the maximum gross position per underlying.
+    #[must_use]
+    pub const fn with_max_underlying(mut self, limit: u64) -> Self {
+        self.max_underlying = Some(limit);
+        self
+    }
+
+    /// Returns the limit of a scope.
+    #[must_use]
+    pub const fn get(&self, scope: LimitScope) -> Option<u64> {
+        match scope {
+            LimitScope::Contract => self.max_contract,
+            LimitScope::Strike => self.max_strike,
+            LimitScope::Expiration => self.max_expiration,
+            LimitScope::Underlying => self.max_underlying,
+        }
+    }
+}
+
+/// An inventory above its limit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LimitBreach {
+    /// The account above its limit.
+    pub account: String,
+    /// The contract whose fill or order caused the breach.
+    pub key: ContractKey,
+    /// The breached scope.
+    pub scope: LimitScope,
+    /// The configured limit.
+    pub limit: u64,
+    /// The inventory measured against the limit.
+    pub current: u64,
+}
+
+impl LimitBreach {
+    /// Returns the breach as an `Error::InventoryLimitExceeded`.
+    #[must_use]
+    pub fn to_error(&self) -> Error {
+        Error::inventory_limit_exceeded(
+            format!(
+                "{} {} position of {}",
+                self.key.underlying, self.scope, self.account
+            ),
+            Decimal::from(self.limit),
+            Decimal::from(self.current),
+        )
+    }
+}
+
+/// An order of an account that may still fill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenOrder {
+    /// The contract of the order.
+    pub key: ContractKey,
+    /// Buy or Sell.
+    pub side: Side,
+    /// Quantity left to fill.
+    pub quantity: u64,
+}
+
+impl OpenOrder {
+    /// Creates an open order.
+    #[must_use]
+    pub const fn new(key: ContractKey, side: Side, quantity: u64) -> Self {
+        Self {
+            key,
+            side,
+            quantity,
+        }
+    }
+}
+
+/// Outcome of consuming the executions of a set of books.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FillReport {
+    /// Executions consumed.
+    pub executions: usize,
+    /// Executions dropped from a book's log before they were read.
+    pub missed: u64,
+    /// Fills applied to an account (an execution between two registered
+    /// orders counts twice).
+    pub fills: usize,
+    /// Limits breached after the fills.
+    pub breaches: Vec<LimitBreach>,
+}
+
+impl FillReport {
+    /// Adds another report to this one.
+    pub fn merge(&mut self, other: Self) {
+        self.executions += other.executions;
+        self.missed += other.missed;
+        self.fills += other.fills;
+        self.breaches.extend(other.breaches);
+    }
+}
+
+/// Keeps per-account positions from the fills of the contract books.
+///
+/// Orders are attributed to an account with
+/// [`register_order`](Self::register_order); executions of unregistered
+/// orders are consumed but belong to no tracked account. Fills cannot be
+/// undone, so limits are reported as breaches after a fill and enforced
+/// before one with [`check_order`](Self::check_order).
+///
+/// An order is unregistered once its fills are consumed and it is no
+/// longer in its book. Cancels leave no execution, so the caller must
+/// [`unregister_order`](Self::unregister_order) the orders it cancels;
+/// the [`RiskEngine`](super::RiskEngine) does so for its orders.
+///
+/// The keeper reads each book's executions from its own cursor and leaves
+/// them in place for other consumers.
+#[derive(Debug, Clone, Default)]
+pub struct PositionKeeper {
+    /// Position per account and contract.
+    positions: BTreeMap<String, BTreeMap<ContractKey, Position>>,
+    /// Account of each registered order.
+    orders: HashMap<OrderId, String>,
+    /// Inventory limits of every account.
+    limits: InventoryLimits,
+    /// Sequence of the next execution to read, per contract book id.
+    cursors: HashMap<OrderId, u64>,
+}
+
+impl PositionKeeper {
+    /// Creates a keeper with the given limits.
+    #[must_use]
+    pub fn new(limits: InventoryLimits) -> Self {
+        Self {
+            limits,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the inventory limits.
+    #[must_use]
+    pub const fn limits(&self) -> &InventoryLimits {
+        &self.limits
+    }
+
+    /// Replaces the inventory limits.
+    pub fn set_limits(&mut self, limits: InventoryLimits) {
+        self.limits = limits;
+    }
+
+    /// Attributes an order to an account.
+    pub fn register_order(&mut self, order_id: OrderId, account: impl Into<String>) {
+        self.orders.insert(order_id, account.into());
+    }
+
+    /// Stops attributing an order, e.g. once it is filled or cancelled.
+    /// Returns its account.
+    pub fn unregister_order(&mut self, order_id: OrderId) -> Option<String> {
+        self.orders.remove(&order_id)
+    }
+
+    /// Returns the account of an order.
+    #[must_use]
+    pub fn order_account(&self, order_id: OrderId) -> Option<&str> {
+        self.orders.get(&order_id).map(String::as_str)
+    }
+
+    /// Applies a fill to its account's position.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the quantity is zero, and
+    /// `Error::InventoryLimitExceeded` if the position after the fill is
+    /// above a limit. The fill is applied in the latter case.
+    pub fn apply_fill(&mut self, fill: &ContractFill) -> Result<()> {
+        match self.apply(fill)?.first() {
+            Some(breach) => Err(breach.to_error()),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that an order can be filled in full without taking the
+    /// account above a limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InventoryLimitExceeded` for the narrowest breached
+    /// limit.
+    pub fn check_order(
+        &self,
+        account: &str,
+        key: &ContractKey,
+        side: Side,
+        quantity: u64,
+    ) -> Result<()> {
+        self.check_order_with_open_orders(account, key, side, quantity, &[])
+    }
+
+    /// Checks an order against the limits in the worst case where the
+    /// account's open orders fill as well.
+    ///
+    /// The worst case of a contract is the larger absolute position of all
+    /// its open buys filling or all its open sells filling. The order is
+    /// rejected if its worst case is above a limit and above the worst case
+    /// without it, so orders reducing the inventory always pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InventoryLimitExceeded` for the narrowest breached
+    /// limit.
+    pub fn check_order_with_open_orders(
+        &self,
+        account: &str,
+        key: &ContractKey,
+        side: Side,
+        quantity: u64,
+        open_orders: &[OpenOrder],
+    ) -> Result<()> {
+        let order = OpenOrder::new(key.clone(), side, quantity);
+        let without = self.worst_positions(account, open_orders.iter());
+        let with = self.worst_positions(account, open_orders.iter().chain([&order]));
+        for scope in LimitScope::ALL {
+            let Some(limit) = self.limits.get(scope) else {
+                continue;
+            };
+            let measure = |worst: &BTreeMap<ContractKey, u64>| {
+                worst
+                    .iter()
+                    .filter(|(contract, _)| scope.contains(key, contract))
+                    .fold(0u64, |total, (_, size)| total.saturating_add(*size))
+            };
+            let value = measure(&with);
+            if value > limit && value > measure(&without) {
+                return Err(LimitBreach {
+                    account: account.to_string(),
+                    key: key.clone(),
+                    scope,
+                    limit,
+                    current: value,
+                }
+                .to_error());
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes the executions of every contract of a chain.
+    pub fn process_chain(&mut self, chain: &OptionChainOrderBook) -> FillReport {
+        let mut report = FillReport::default();
+        for entry in chain.strikes().iter() {
+            for option_style in [OptionStyle::Call, OptionStyle::Put] {
+                let key = ContractKey::new(
+                    chain.underlying(),
+                    *chain.expiration(),
+                    *entry.key(),
+                    option_style,
+                );
+                report.merge(self.process_contract(entry.value().get(option_style), &key));
+            }
+        }
+        report
+    }
+
+    /// Consumes the executions of one contract book, listed as `key`.
+    pub fn process_contract(&mut self, book: &OptionOrderBook, key: &ContractKey) -> FillReport {
+        let mut report = FillReport::default();
+        let next = book.next_execution_sequence();
+        let cursor = self.cursors.entry(book.id()).or_default();
+        let from = *cursor;
+        *cursor = next;
+        let executions = book.executions_since(from);
+        if let Some(first) = executions.first() {
+            report.missed += first.sequence - from;
+        }
+        let mut traded = Vec::new();
+        for execution in executions.into_iter().filter(|e| e.sequence < next) {
+            report.executions += 1;
+            for order_id in [execution.taker_order_id, execution.maker_order_id] {
+                let (Some(account), Some(side)) =
+                    (self.orders.get(&order_id), execution.side_of(order_id))
+                else {
+                    continue;
+                };
+                let fill = ContractFill {
+                    account: account.clone(),
+                    key: key.clone(),
+                    symbol: book.symbol().to_string(),
+                    side,
+                    price: execution.price,
+                    quantity: execution.quantity,
+                    timestamp_ms: execution.timestamp_ms,
+                };
+                traded.push(order_id);
+                if let Ok(breaches) = self.apply(&fill) {
+                    report.fills += 1;
+                    report.breaches.extend(breaches);
+                }
+            }
+        }
+        // Orders that left the book have no fills to come, unless the book
+        // traded again while this batch was read.
+        if book.next_execution_sequence() == next {
+            for order_id in traded {
+                if book.inner().get_order(order_id).is_none() {
+                    self.orders.remove(&order_id);
+                }
+            }
+        }
+        report
+    }
+
+    /// Consumes the executions of every expiration of an underlying.
+    pub fn process_underlying(&mut self, book: &UnderlyingOrderBook) -> FillReport {
+        let mut report = FillReport::default();
+        for entry in book.expirations().iter() {
+            report.merge(self.process_chain(entry.value().chain()));
+        }
+        report
+    }
+
+    /// Consumes the executions of every underlying of a manager.
+    pub fn process_all(&mut self, manager: &UnderlyingOrderBookManager) -> FillReport {
+        let mut report = FillReport::default();
+        for entry in manager.iter() {
+            report.merge(self.process_underlying(entry.value()));
+        }
+        report
+    }
+
+    /// Returns the position of an account in a contract.
+    #[must_use]
+    pub fn position(&self, account: &str, key: &ContractKey) -> Option<&Position> {
+        self.positions.get(account)?.get(key)
+    }
+
+    /// Returns the positions of an account, ordered by contract.
+    pub fn positions(&self, account: &str) -> impl Iterator<Item = (&ContractKey, &Position)> {
+        self.positions.get(account).into_iter().flatten()
+    }
+
+    /// Returns the accounts with a position.
+    pub fn accounts(&self) -> impl Iterator<Item = &str> {
+        self.positions.keys().map(String::as_str)
+    }
+
+    /// Returns the positions of an account in the scope of a contract,
+    /// e.g. its strike or expiration.
+    #[must_use]
+    pub fn rollup(&self, account: &str, key: &ContractKey, scope: LimitScope) -> PositionRollup {
+        let mut rollup = PositionRollup::default();
+        for (contract, position) in self.positions(account) {
+            if scope.contains(key, contract) {
+                rollup.add(position);
+            }
+        }
+        rollup
+    }
+
+    /// Returns the open positions of an account keyed by contract symbol,
+    /// for aggregating their Greeks.
+    #[must_use]
+    pub fn position_set(&self, account: &str) -> PositionSet {
+        let mut set = PositionSet::new();
+        for (_, position) in self.positions(account) {
+            if !position.is_flat() {
+                set.set(position.symbol.clone(), position.quantity);
+            }
+        }
+        set
+    }
+
+    /// Returns the worst-case absolute position of an account per contract
+    /// if its orders fill.
+    fn worst_positions<'a>(
+        &self,
+        account: &str,
+        orders: impl Iterator<Item = &'a OpenOrder>,
+    ) -> BTreeMap<ContractKey, u64> {
+        let mut bounds: BTreeMap<ContractKey, (i64, i64)> = self
+            .positions(account)
+            .map(|(key, position)| (key.clone(), (position.quantity, position.quantity)))
+            .collect();
+        for order in orders {
+            let (long, short) = bounds.entry(order.key.clone()).or_default();
+            let quantity = i64::try_from(order.quantity).unwrap_or(i64::MAX);
+            match order.side {
+                Side::Buy => *long = long.saturating_add(quantity),
+                Side::Sell => *short = short.saturating_sub(quantity),
+            }
+        }
+        bounds
+            .into_iter()
+            .map(|(key, (long, short))| (key, long.unsigned_abs().max(short.unsigned_abs())))
+            .collect()
+    }
+
+    /// Applies a fill and returns the limits breached afterwards.
+    fn apply(&mut self, fill: &ContractFill) -> Result<Vec<LimitBreach>> {
+        self.positions
+            .entry(fill.account.clone())
+            .or_default()
+            .entry(fill.key.clone())
+            .or_insert_with(|| Position::new(fill.symbol.clone()))
+            .apply(fill.side, fill.price, fill.quantity, fill.timestamp_ms)?;
+
+        let mut breaches = Vec::new();
+        for scope in LimitScope::ALL {
+            let Some(limit) = self.limits.get(scope) else {
+                continue;
+            };
+            let rollup = self.rollup(&fill.account, &fill.key, scope);
+            let current = if scope == LimitScope::Contract {
+                rollup.net.unsigned_abs()
+            } else {
+                rollup.gross
+            };
+            if current > limit {
+                breaches.push(LimitBreach {
+                    account: fill.account.clone(),
+                    key: fill.key.clone(),
+                    scope,
+                    limit,
+                    current,
+                });
+            }
+        }
+        Ok(breaches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use optionstratlib::ExpirationDate;
+    use optionstratlib::prelude::pos_or_panic;
+
+    fn expiration() -> ExpirationDate {
+        ExpirationDate::Days(pos_or_panic!(30.0))
+    }
+
+    fn fill(
+        account: &str,
+        strike: u64,
+        option_style: OptionStyle,
+        side: Side,
+        quantity: u64,
+    ) -> ContractFill {
+        ContractFill {
+            account: account.to_string(),
+            key: ContractKey::new("BTC", expiration(), strike, option_style),
+            symbol: format!("BTC-{strike}-{option_style:?}"),
+            side,
+            price: 100,
+            quantity,
+            timestamp_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_rollups_and_limits() {
+        let limits = InventoryLimits::unlimited()
+            .with_max_contract(10)
+            .with_max_strike(15);
+        let mut keeper = PositionKeeper::new(limits);
+        keeper
+            .apply_fill(&fill("A", 100, OptionStyle::Call, Side::Buy, 8))
+            .unwrap();
+        keeper
+            .apply_fill(&fill("A", 100, OptionStyle::Put, Side::Sell, 6))
+            .unwrap();
+        keeper
+            .apply_fill(&fill("B", 100, OptionStyle::Put, Side::Sell, 6))
+            .unwrap();
+
+        let key = ContractKey::new("BTC", expiration(), 100, OptionStyle::Call);
+        let strike = keeper.rollup("A", &key, LimitScope::Strike);
+        assert_eq!((strike.net, strike.gross), (2, 14));
+        assert_eq!(keeper.rollup("A", &key, LimitScope::Contract).net, 8);
+        assert_eq!(keeper.accounts().count(), 2);
+        assert_eq!(keeper.position_set("A").len(), 2);
+
+        // Pre-trade: buying 3 more calls breaks the contract limit first.
+        let err = keeper.check_order("A", &key, Side::Buy, 3).unwrap_err();
+        assert!(matches!(err, Error::InventoryLimitExceeded { .. }));
+        assert!(err.to_string().contains("contract"));
+        // Selling reduces the inventory.
+        keeper.check_order("A", &key, Side::Sell, 8).unwrap();
+        // Buying 2 more calls breaks only the strike limit.
+        let err = keeper.check_order("A", &key, Side::Buy, 2).unwrap_err();
+        assert!(err.to_string().contains("strike"));
+
+        // Open orders count in the worst case; orders reducing it pass.
+        let put = ContractKey::new("BTC", expiration(), 100, OptionStyle::Put);
+        let open = [OpenOrder::new(key.clone(), Side::Buy, 1)];
+        keeper
+            .check_order_with_open_orders("A", &key, Side::Buy, 1, &[])
+            .unwrap();
+        let err = keeper
+            .check_order_with_open_orders("A", &put, Side::Sell, 1, &open)
+            .unwrap_err();
+        assert!(err.to_string().contains("strike"));
+        keeper
+            .check_order_with_open_orders("A", &put, Side::Buy, 1, &open)
+            .unwrap();
+
+        // Post-trade: the fill is applied and the breach reported.
+        assert!(
+            keeper
+                .apply_fill(&fill("A", 100, OptionStyle::Call, Side::Buy, 2))
+                .is_err()
+        );
+        assert_eq!(keeper.position("A", &key).unwrap().quantity, 10);
+    }
+
+    #[test]
+    fn test_process_executions_from_books() {
+        let manager = UnderlyingOrderBookManager::new();
+        let underlying = manager.get_or_create("BTC");
+        let strike = underlying
+            .get_or_create_expiration(expiration())
+            .get_or_create_strike(100);
+        let call = strike.call();
+
+        let mut keeper = PositionKeeper::new(InventoryLimits::unlimited().with_max_underlying(4));
+        let maker = OrderId::new();
+        let taker = OrderId::new();
+        keeper.register_order(maker, "MM");
+        keeper.register_order(taker, "CUST");
+        call.add_limit_order(maker, Side::Sell, 50, 5).unwrap();
+        call.add_limit_order(taker, Side::Buy, 50, 3).unwrap();
+        // An unregistered order trades with the maker too.
+        call.add_limit_order(OrderId::new(), Side::Buy, 50, 2)
+            .unwrap();
+        assert_eq!(call.next_execution_sequence(), 2);
+        // Another keeper can read the same executions.
+        let mut audit = PositionKeeper::default();
+        audit.register_order(taker, "CUST");
+
+        let report = keeper.process_all(&manager);
+        assert_eq!(report.executions, 2);
+        assert_eq!(report.fills, 3);
+        assert_eq!(report.breaches.len(), 1);
+        assert_eq!(report.breaches[0].account, "MM");
+        assert_eq!(report.breaches[0].scope, LimitScope::Underlying);
+        assert_eq!(keeper.process_all(&manager), FillReport::default());
+        assert_eq!(audit.process_all(&manager).fills, 1);
+        assert_eq!(audit.process_all(&manager).executions, 0);
+
+        let key = ContractKey::new("BTC", expiration(), 100, OptionStyle::Call);
+        let mm = keeper.position("MM", &key).unwrap();
+        assert_eq!(mm.quantity, -5);
+        assert!((mm.average_cost - 50.0).abs() < 1e-12);
+        assert_eq!(mm.symbol, call.symbol());
+        assert_eq!(keeper.position("CUST", &key).unwrap().quantity, 3);
+        // Filled orders are unregistered; resting ones stay.
+        assert!(keeper.order_account(maker).is_none());
+        assert!(keeper.order_account(taker).is_none());
+        let resting = OrderId::new();
+        keeper.register_order(resting, "MM");
+        call.add_limit_order(resting, Side::Sell, 60, 5).unwrap();
+        call.add_limit_order(OrderId::new(), Side::Buy, 60, 2)
+            .unwrap();
+        assert_eq!(keeper.process_all(&manager).fills, 1);
+        assert_eq!(keeper.order_account(resting), Some("MM"));
+        assert_eq!(keeper.unregister_order(resting).as_deref(), Some("MM"));
+    }
+
+    #[test]
+    fn test_cursors_follow_the_book_not_the_symbol() {
+        let underlying = UnderlyingOrderBook::new("BTC");
+        let chain = underlying.get_or_create_expiration(expiration());
+        let strike = chain.get_or_create_strike(100);
+        let mut keeper = PositionKeeper::default();
+        let maker = OrderId::new();
+        keeper.register_order(maker, "MM");
+        strike
+            .call()
+            .add_limit_order(maker, Side::Sell, 50, 5)
+            .unwrap();
+        strike
+            .call()
+            .add_limit_order(OrderId::new(), Side::Buy, 50, 1)
+            .unwrap();
+        assert_eq!(keeper.process_underlying(&underlying).fills, 1);
+
+        // A book listed again under the same symbol is read from its start.
+        let relisted = OptionChainOrderBook::new("BTC", expiration());
+        let book = relisted.get_or_create_strike(100);
+        assert_eq!(book.call().symbol(), strike.call().symbol());
+        let maker = OrderId::new();
+        keeper.register_order(maker, "MM");
+        book.call()
+            .add_limit_order(maker, Side::Sell, 50, 5)
+            .unwrap();
+        book.call()
+            .add_limit_order(OrderId::new(), Side::Buy, 50, 2)
+            .unwrap();
+        let report = keeper.process_chain(&relisted);
+        assert_eq!((report.executions, report.missed, report.fills), (1, 0, 1));
+        let key = ContractKey::new("BTC", expiration(), 100, OptionStyle::Call);
+        assert_eq!(keeper.position("MM", &key).unwrap().quantity, -3);
+    }
+}
@@ -0,0 +1,21 @@
+//! Risk module.
+//!
+//! This module tracks the positions built by fills in the order book
+//! hierarchy, enforces inventory limits on them and checks orders before
+//! they reach the books.
+//!
+//! ## Components
+//!
+//! - [`Position`]: Signed quantity, average cost and realized P&L of an account in a contract
+//! - [`PositionRollup`]: Net and gross positions at strike, expiration or underlying level
+//! - [`InventoryLimits`]: Maximum position per contract, strike, expiration and underlying
+//! - [`PositionKeeper`]: Per-account positions driven by the executions of the contract books
+//! - [`RiskEngine`]: Order entry with pre-trade size, notional, price, open order and Greek checks
+
+mod keeper;
+mod position;
+mod pretrade;
+
+pub use keeper::{FillReport, InventoryLimits, LimitBreach, LimitScope, OpenOrder, PositionKeeper};
+pub use position::{ContractFill, ContractKey, Position, PositionRollup};
+pub use pretrade::{OrderAck, OrderRequest, PreTradeLimits, RiskEngine};
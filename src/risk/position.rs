@@ -0,0 +1,250 @@
+//! Position module.
+//!
+//! This module provides the [`Position`] of an account in one contract,
+//! with its average cost and realized P&L, and the [`PositionRollup`] of
+//! positions at strike, expiration or underlying level.
+
+use crate::error::{Error, Result};
+use optionstratlib::{ExpirationDate, OptionStyle};
+use orderbook_rs::Side;
+use serde::{Deserialize, Serialize};
+
+/// Identifies a contract in the order book hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ContractKey {
+    /// The underlying asset symbol.
+    pub underlying: String,
+    /// The expiration date.
+    pub expiration: ExpirationDate,
+    /// The strike price.
+    pub strike: u64,
+    /// Call or Put.
+    pub option_style: OptionStyle,
+}
+
+impl ContractKey {
+    /// Creates a contract key.
+    #[must_use]
+    pub fn new(
+        underlying: impl Into<String>,
+        expiration: ExpirationDate,
+        strike: u64,
+        option_style: OptionStyle,
+    ) -> Self {
+        Self {
+            underlying: underlying.into(),
+            expiration,
+            strike,
+            option_style,
+        }
+    }
+}
+
+/// A fill of an account in one contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractFill {
+    /// The account that traded.
+    pub account: String,
+    /// The traded contract.
+    pub key: ContractKey,
+    /// The contract symbol.
+    pub symbol: String,
+    /// Side traded by the account.
+    pub side: Side,
+    /// Fill price in smallest units.
+    pub price: u128,
+    /// Filled quantity.
+    pub quantity: u64,
+    /// Timestamp of the fill in milliseconds.
+    pub timestamp_ms: u64,
+}
+
+/// Position of an account in one contract.
+///
+/// Prices and P&L are in price units per contract unit, before the
+/// contract multiplier.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    /// The contract symbol.
+    pub symbol: String,
+    /// Signed quantity: positive long, negative short.
+    pub quantity: i64,
+    /// Average price of the open quantity, zero when flat.
+    pub average_cost: f64,
+    /// P&L realized by closing trades.
+    pub realized_pnl: f64,
+    /// Total quantity bought.
+    pub bought: u64,
+    /// Total quantity sold.
+    pub sold: u64,
+    /// Timestamp of the last fill in milliseconds.
+    pub last_fill_ms: u64,
+}
+
+impl Position {
+    /// Creates a flat position.
+    #[must_use]
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            quantity: 0,
+            average_cost: 0.0,
+            realized_pnl: 0.0,
+            bought: 0,
+            sold: 0,
+            last_fill_ms: 0,
+        }
+    }
+
+    /// Applies a fill.
+    ///
+    /// Fills adding to the position move the average cost; fills reducing
+    /// it realize P&L against the average cost. A fill through zero closes
+    /// the position and opens the remainder at the fill price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the quantity is zero.
+    pub fn apply(
+        &mut self,
+        side: Side,
+        price: u128,
+        quantity: u64,
+        timestamp_ms: u64,
+    ) -> Result<()> {
+        if quantity == 0 {
+            return Err(Error::validation(format!(
+                "fill in {} has zero quantity",
+                self.symbol
+            )));
+        }
+        let signed = match side {
+            Side::Buy => i64::try_from(quantity).unwrap_or(i64::MAX),
+            Side::Sell => -i64::try_from(quantity).unwrap_or(i64::MAX),
+        };
+        let price = price as f64;
+        let previous = self.quantity;
+        let next = previous.saturating_add(signed);
+
+        if previous == 0 || previous.signum() == signed.signum() {
+            let open = previous.unsigned_abs() as f64;
+            let added = signed.unsigned_abs() as f64;
+            self.average_cost = (self.average_cost * open + price * added) / (open + added);
+        } else {
+            let closed = previous.unsigned_abs().min(signed.unsigned_abs()) as f64;
+            self.realized_pnl += (price - self.average_cost) * closed * previous.signum() as f64;
+            if next == 0 {
+                self.average_cost = 0.0;
+            } else if next.signum() != previous.signum() {
+                self.average_cost = price;
+            }
+        }
+
+        self.quantity = next;
+        match side {
+            Side::Buy => self.bought = self.bought.saturating_add(quantity),
+            Side::Sell => self.sold = self.sold.saturating_add(quantity),
+        }
+        self.last_fill_ms = timestamp_ms;
+        Ok(())
+    }
+
+    /// Returns true if there is no open quantity.
+    #[must_use]
+    pub const fn is_flat(&self) -> bool {
+        self.quantity == 0
+    }
+
+    /// Returns the P&L of the open quantity at a mark price.
+    #[must_use]
+    pub fn unrealized_pnl(&self, mark: f64) -> f64 {
+        (mark - self.average_cost) * self.quantity as f64
+    }
+}
+
+/// Positions aggregated over a set of contracts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PositionRollup {
+    /// Sum of signed quantities.
+    pub net: i64,
+    /// Sum of absolute quantities.
+    pub gross: u64,
+    /// Sum of long quantities.
+    pub long: u64,
+    /// Sum of short quantities, as a positive number.
+    pub short: u64,
+    /// Realized P&L of the contracts.
+    pub realized_pnl: f64,
+    /// Number of contracts with an open quantity.
+    pub open_contracts: usize,
+}
+
+impl PositionRollup {
+    /// Adds a position.
+    pub fn add(&mut self, position: &Position) {
+        let size = position.quantity.unsigned_abs();
+        self.net = self.net.saturating_add(position.quantity);
+        self.gross = self.gross.saturating_add(size);
+        if position.quantity > 0 {
+            self.long = self.long.saturating_add(size);
+        } else {
+            self.short = self.short.saturating_add(size);
+        }
+        self.realized_pnl += position.realized_pnl;
+        if !position.is_flat() {
+            self.open_contracts += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_cost_and_realized_pnl() {
+        let mut position = Position::new("TEST");
+        position.apply(Side::Buy, 10, 2, 1).unwrap();
+        position.apply(Side::Buy, 13, 1, 2).unwrap();
+        assert_eq!(position.quantity, 3);
+        assert!((position.average_cost - 11.0).abs() < 1e-12);
+        assert!((position.unrealized_pnl(12.0) - 3.0).abs() < 1e-12);
+
+        // Reducing keeps the average cost.
+        position.apply(Side::Sell, 15, 1, 3).unwrap();
+        assert_eq!(position.quantity, 2);
+        assert!((position.average_cost - 11.0).abs() < 1e-12);
+        assert!((position.realized_pnl - 4.0).abs() < 1e-12);
+
+        // Selling through zero opens a short at the fill price.
+        position.apply(Side::Sell, 9, 5, 4).unwrap();
+        assert_eq!(position.quantity, -3);
+        assert!((position.average_cost - 9.0).abs() < 1e-12);
+        assert!((position.realized_pnl - 0.0).abs() < 1e-12);
+        assert_eq!((position.bought, position.sold), (3, 6));
+        assert_eq!(position.last_fill_ms, 4);
+
+        position.apply(Side::Buy, 7, 3, 5).unwrap();
+        assert!(position.is_flat());
+        assert_eq!(position.average_cost, 0.0);
+        assert!((position.realized_pnl - 6.0).abs() < 1e-12);
+        assert!(position.apply(Side::Buy, 7, 0, 6).is_err());
+    }
+
+    #[test]
+    fn test_rollup() {
+        let mut long = Position::new("A");
+        long.apply(Side::Buy, 10, 4, 0).unwrap();
+        let mut short = Position::new("B");
+        short.apply(Side::Sell, 10, 3, 0).unwrap();
+
+        let mut rollup = PositionRollup::default();
+        rollup.add(&long);
+        rollup.add(&short);
+        rollup.add(&Position::new("C"));
+        assert_eq!(rollup.net, 1);
+        assert_eq!(rollup.gross, 7);
+        assert_eq!((rollup.long, rollup.short), (4, 3));
+        assert_eq!(rollup.open_contracts, 2);
+    }
+}
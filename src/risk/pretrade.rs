@@ -0,0 +1,970 @@
+//! Pre-trade risk module.
+//!
+//! This module provides the [`RiskEngine`], a checked order entry point
+//! that runs configurable [`PreTradeLimits`] before an order
+//! reaches its [`OptionOrderBook`](crate::orderbook::OptionOrderBook).
+//!
+//! Checks run in order: order size, notional, price deviation, open order
+//! count, inventory limits of the [`PositionKeeper`], then the account's
+//! Greeks after a hypothetical full fill. Inventory and Greeks are checked
+//! in the worst case where the account's resting orders fill too. The
+//! first failed check rejects the order: with
+//! `Error::InventoryLimitExceeded` for inventory limits,
+//! `Error::GreeksError` when the contract has no Greeks to check, and
+//! `Error::RiskLimitBreached`, named after its [`PreTradeLimits`] field,
+//! for every other check.
+
+use super::keeper::{FillReport, OpenOrder, PositionKeeper};
+use super::position::ContractKey;
+use crate::error::{Error, Result};
+use crate::orderbook::{
+    AggregatedGreeks, ExposureWeights, MassQuote, MassQuoteAck, MassQuoteEntry, MassQuoteReport,
+    PositionSet, SideAction, StrikeOrderBook, UnderlyingOrderBook,
+};
+use orderbook_rs::{OrderId, Side};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// A limit order submitted by an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderRequest {
+    /// The submitting account.
+    pub account: String,
+    /// The contract to trade.
+    pub key: ContractKey,
+    /// Buy or Sell.
+    pub side: Side,
+    /// Limit price in smallest units.
+    pub price: u128,
+    /// Order quantity.
+    pub quantity: u64,
+}
+
+impl OrderRequest {
+    /// Creates an order request.
+    #[must_use]
+    pub fn new(
+        account: impl Into<String>,
+        key: ContractKey,
+        side: Side,
+        price: u128,
+        quantity: u64,
+    ) -> Self {
+        Self {
+            account: account.into(),
+            key,
+            side,
+            price,
+            quantity,
+        }
+    }
+}
+
+/// Pre-trade limits. `None` disables a check.
+///
+/// Greek limits apply to the absolute value of the account's aggregated
+/// cash Greeks (see [`AggregatedGreeks`]) including the order as if fully
+/// filled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PreTradeLimits {
+    /// Maximum quantity of one order.
+    pub max_order_size: Option<u64>,
+    /// Maximum price times quantity times multiplier of one order, in price
+    /// units.
+    pub max_notional: Option<f64>,
+    /// Maximum relative distance of the limit price from the theoretical
+    /// value, or the mid price when no theoretical value is given.
+    pub max_price_deviation: Option<f64>,
+    /// Maximum resting orders per account.
+    pub max_open_orders: Option<usize>,
+    /// Maximum absolute cash delta per account.
+    pub max_cash_delta: Option<f64>,
+    /// Maximum absolute dollar gamma per account.
+    pub max_dollar_gamma: Option<f64>,
+    /// Maximum absolute vega per volatility point per account.
+    pub max_vega: Option<f64>,
+}
+
+impl PreTradeLimits {
+    /// Creates limits with every check disabled.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            max_order_size: None,
+            max_notional: None,
+            max_price_deviation: None,
+            max_open_orders: None,
+            max_cash_delta: None,
+            max_dollar_gamma: None,
+            max_vega: None,
+        }
+    }
+
+    /// Sets the maximum quantity of one order.
+    #[must_use]
+    pub const fn with_max_order_size(mut self, limit: u64) -> Self {
+        self.max_order_size = Some(limit);
+        self
+    }
+
+    /// Sets the maximum notional of one order.
+    #[must_use]
+    pub const fn with_max_notional(mut self, limit: f64) -> Self {
+        self.max_notional = Some(limit);
+        self
+    }
+
+    /// Sets the maximum relative price deviation from theo or mid.
+    #[must_use]
+    pub const fn with_max_price_deviation(mut self, limit: f64) -> Self {
+        self.max_price_deviation = Some(limit);
+        self
+    }
+
+    /// Sets the maximum resting orders per account.
+    #[must_use]
+    pub const fn with_max_open_orders(mut self, limit: usize) -> Self {
+        self.max_open_orders = Some(limit);
+        self
+    }
+
+    /// Sets the maximum absolute cash delta per account.
+    #[must_use]
+    pub const fn with_max_cash_delta(mut self, limit: f64) -> Self {
+        self.max_cash_delta = Some(limit);
+        self
+    }
+
+    /// Sets the maximum absolute dollar gamma per account.
+    #[must_use]
+    pub const fn with_max_dollar_gamma(mut self, limit: f64) -> Self {
+        self.max_dollar_gamma = Some(limit);
+        self
+    }
+
+    /// Sets the maximum absolute vega per volatility point per account.
+    #[must_use]
+    pub const fn with_max_vega(mut self, limit: f64) -> Self {
+        self.max_vega = Some(limit);
+        self
+    }
+
+    /// Returns true if any Greek limit is set.
+    #[must_use]
+    pub const fn has_greek_limits(&self) -> bool {
+        self.max_cash_delta.is_some() || self.max_dollar_gamma.is_some() || self.max_vega.is_some()
+    }
+}
+
+/// An order accepted by the [`RiskEngine`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderAck {
+    /// Identifier of the order in the contract book.
+    pub order_id: OrderId,
+    /// Fills applied after placing the order, including its own.
+    pub fills: FillReport,
+}
+
+/// Order entry with pre-trade risk checks.
+///
+/// The engine sits beside the hierarchy rather than inside it: orders are
+/// checked when entered with [`submit`](Self::submit), mass quotes with
+/// [`apply_mass_quote`](Self::apply_mass_quote) and a
+/// [`ChainQuoter`](crate::quoting::ChainQuoter) cycle with
+/// `update_checked`. The books' own entry points, such as
+/// [`OptionOrderBook::add_limit_order`](crate::orderbook::OptionOrderBook::add_limit_order),
+/// place orders directly without checks.
+///
+/// Accepted orders are registered with the engine's [`PositionKeeper`],
+/// and the fills they trigger on entry are applied before returning, so
+/// the next checks run against the new positions. Inventory and Greek
+/// checks assume the account's resting orders fill as well.
+///
+/// Executions are read only from contracts where the engine has orders
+/// and from the contract being traded; fills of orders registered with
+/// the keeper directly are applied by
+/// [`PositionKeeper::process_underlying`].
+#[derive(Debug, Clone, Default)]
+pub struct RiskEngine {
+    /// Pre-trade limits.
+    limits: PreTradeLimits,
+    /// Positions and inventory limits.
+    keeper: PositionKeeper,
+    /// Contract and account of each order placed through the engine.
+    orders: HashMap<OrderId, (String, ContractKey)>,
+    /// Open orders placed through the engine, per account and contract.
+    open: HashMap<String, BTreeMap<ContractKey, Vec<OrderId>>>,
+}
+
+impl RiskEngine {
+    /// Creates an engine with the given limits and position keeper.
+    #[must_use]
+    pub fn new(limits: PreTradeLimits, keeper: PositionKeeper) -> Self {
+        Self {
+            limits,
+            keeper,
+            orders: HashMap::new(),
+            open: HashMap::new(),
+        }
+    }
+
+    /// Returns the pre-trade limits.
+    #[must_use]
+    pub const fn limits(&self) -> &PreTradeLimits {
+        &self.limits
+    }
+
+    /// Replaces the pre-trade limits.
+    pub fn set_limits(&mut self, limits: PreTradeLimits) {
+        self.limits = limits;
+    }
+
+    /// Returns the position keeper.
+    #[must_use]
+    pub const fn keeper(&self) -> &PositionKeeper {
+        &self.keeper
+    }
+
+    /// Returns the position keeper mutably, e.g. to apply external fills.
+    pub fn keeper_mut(&mut self) -> &mut PositionKeeper {
+        &mut self.keeper
+    }
+
+    /// Returns the number of orders of an account still resting in `book`.
+    #[must_use]
+    pub fn open_order_count(&self, book: &UnderlyingOrderBook, account: &str) -> usize {
+        self.open_orders(book, account, &[]).len()
+    }
+
+    /// Returns the orders of an account still resting in `book`, with their
+    /// remaining quantity.
+    #[must_use]
+    pub fn open_orders(
+        &self,
+        book: &UnderlyingOrderBook,
+        account: &str,
+        excluded: &[OrderId],
+    ) -> Vec<OpenOrder> {
+        let mut open = Vec::new();
+        for (key, order_ids) in self.open.get(account).into_iter().flatten() {
+            let Ok(contract) = contract_book(book, key) else {
+                continue;
+            };
+            for order_id in order_ids.iter().filter(|id| !excluded.contains(id)) {
+                if let Some(order) = contract.get(key.option_style).inner().get_order(*order_id) {
+                    open.push(OpenOrder::new(
+                        key.clone(),
+                        order.side(),
+                        order.visible_quantity() + order.hidden_quantity(),
+                    ));
+                }
+            }
+        }
+        open
+    }
+
+    /// Runs the pre-trade checks of an order.
+    ///
+    /// # Arguments
+    ///
+    /// * `book` - The underlying order book of the contract
+    /// * `request` - The order
+    /// * `theo` - Theoretical value of the contract for the price deviation
+    ///   check; the contract's mid price is used when `None`, and the check
+    ///   is skipped when neither is known
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` if the order has a zero quantity or
+    /// price or targets another underlying, `Error::ExpirationNotFound` or
+    /// `Error::StrikeNotFound` if the contract is not listed,
+    /// `Error::InventoryLimitExceeded` from the position keeper,
+    /// `Error::GreeksError` if a Greek limit is set and the contract has no
+    /// Greeks, and `Error::RiskLimitBreached` for the first failed
+    /// pre-trade check.
+    pub fn check(
+        &self,
+        book: &UnderlyingOrderBook,
+        request: &OrderRequest,
+        theo: Option<f64>,
+    ) -> Result<()> {
+        let open = self.open_orders(book, &request.account, &[]);
+        self.check_against(book, request, theo, &open)
+    }
+
+    /// Applies the pending fills of the account's contracts, checks an
+    /// order and places it in its contract book, then applies the fills it
+    /// caused.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failed check (see
+    /// [`check`](Self::check)), or an order book error if placing fails.
+    pub fn submit(
+        &mut self,
+        book: &UnderlyingOrderBook,
+        request: &OrderRequest,
+        theo: Option<f64>,
+    ) -> Result<OrderAck> {
+        let mut fills = self.process_contracts(book, Some(&request.key));
+        self.check(book, request, theo)?;
+        let contract = contract_book(book, &request.key)?;
+        let order_id = OrderId::new();
+        contract.get(request.key.option_style).add_limit_order(
+            order_id,
+            request.side,
+            request.price,
+            request.quantity,
+        )?;
+        self.track(order_id, &request.account, &request.key);
+        fills.merge(self.process_contracts(book, Some(&request.key)));
+        Ok(OrderAck { order_id, fills })
+    }
+
+    /// Checks and applies a participant's mass quote. Fills of the
+    /// engine's orders are applied before the checks and after the quote.
+    ///
+    /// Both sides of each entry are checked as orders of the participant,
+    /// in place of the participant's current quote in that contract. An
+    /// entry failing a check is rejected and leaves its contract's previous
+    /// quote in place; the other entries are applied. Each entry is checked
+    /// against the positions and orders left by the entries before it, as
+    /// the quote is not applied atomically.
+    pub fn apply_mass_quote(
+        &mut self,
+        book: &UnderlyingOrderBook,
+        quote: &MassQuote,
+    ) -> (MassQuoteReport, FillReport) {
+        let mut fills = self.process_fills(book);
+        let acks = quote
+            .entries
+            .iter()
+            .map(|entry| {
+                let result = self.apply_mass_quote_entry(book, &quote.participant, entry, None);
+                MassQuoteAck::new(entry, result)
+            })
+            .collect();
+        fills.merge(self.process_fills(book));
+        (MassQuoteReport::new(quote.participant.clone(), acks), fills)
+    }
+
+    /// Cancels an order placed through the engine. Returns true if it was
+    /// resting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract book rejects the cancel.
+    pub fn cancel(&mut self, book: &UnderlyingOrderBook, order_id: OrderId) -> Result<bool> {
+        let Some((_, key)) = self.orders.get(&order_id) else {
+            return Ok(false);
+        };
+        let contract = contract_book(book, key)?;
+        let cancelled = contract.get(key.option_style).cancel_order(order_id)?;
+        self.untrack(order_id);
+        Ok(cancelled)
+    }
+
+    /// Applies the fills of the engine's orders in an underlying to the
+    /// positions and forgets orders no longer resting.
+    ///
+    /// Only the contracts where the engine has open orders are read.
+    pub fn process_fills(&mut self, book: &UnderlyingOrderBook) -> FillReport {
+        self.process_contracts(book, None)
+    }
+
+    /// Applies the fills of the contracts with open orders of the engine,
+    /// and of `traded` if given, then forgets orders no longer resting.
+    fn process_contracts(
+        &mut self,
+        book: &UnderlyingOrderBook,
+        traded: Option<&ContractKey>,
+    ) -> FillReport {
+        let mut keys: BTreeSet<ContractKey> = self
+            .open
+            .values()
+            .flat_map(BTreeMap::keys)
+            .filter(|key| key.underlying == book.underlying())
+            .cloned()
+            .collect();
+        keys.extend(
+            traded
+                .filter(|key| key.underlying == book.underlying())
+                .cloned(),
+        );
+
+        let mut report = FillReport::default();
+        let mut done = Vec::new();
+        for key in keys {
+            let Ok(contract) = contract_book(book, &key) else {
+                continue;
+            };
+            let contract = contract.get(key.option_style);
+            report.merge(self.keeper.process_contract(contract, &key));
+            for order_ids in self.open.values() {
+                let Some(order_ids) = order_ids.get(&key) else {
+                    continue;
+                };
+                done.extend(
+                    order_ids
+                        .iter()
+                        .filter(|id| contract.inner().get_order(**id).is_none()),
+                );
+            }
+        }
+        for order_id in done {
+            self.untrack(order_id);
+        }
+        report
+    }
+
+    /// Attributes an order placed in a contract book to an account.
+    ///
+    /// Fills are read after placing, so an order is tracked once it is in
+    /// the book and its first fills still reach the account.
+    fn track(&mut self, order_id: OrderId, account: &str, key: &ContractKey) {
+        self.keeper.register_order(order_id, account);
+        self.orders
+            .insert(order_id, (account.to_string(), key.clone()));
+        self.open
+            .entry(account.to_string())
+            .or_default()
+            .entry(key.clone())
+            .or_default()
+            .push(order_id);
+    }
+
+    /// Forgets an order placed through the engine.
+    fn untrack(&mut self, order_id: OrderId) {
+        self.keeper.unregister_order(order_id);
+        let Some((account, key)) = self.orders.remove(&order_id) else {
+            return;
+        };
+        let Some(contracts) = self.open.get_mut(&account) else {
+            return;
+        };
+        if let Some(order_ids) = contracts.get_mut(&key) {
+            order_ids.retain(|id| *id != order_id);
+            if order_ids.is_empty() {
+                contracts.remove(&key);
+            }
+        }
+        if contracts.is_empty() {
+            self.open.remove(&account);
+        }
+    }
+
+    /// Checks and applies one mass quote entry, with the theoretical value
+    /// of the contract for the price deviation check if known.
+    pub(crate) fn apply_mass_quote_entry(
+        &mut self,
+        book: &UnderlyingOrderBook,
+        participant: &str,
+        entry: &MassQuoteEntry,
+        theo: Option<f64>,
+    ) -> Result<(SideAction, SideAction)> {
+        let expiration = book.get_expiration(&entry.expiration)?;
+        let contract = expiration.get_strike(entry.strike)?;
+        let contract = contract.get(entry.option_style);
+        let key = ContractKey::new(
+            book.underlying(),
+            entry.expiration,
+            entry.strike,
+            entry.option_style,
+        );
+
+        // The entry replaces the participant's quote in the contract.
+        let previous = contract.participant_quote(participant).unwrap_or_default();
+        let replaced: Vec<OrderId> = [previous.bid, previous.ask]
+            .into_iter()
+            .flatten()
+            .map(|order| order.order_id)
+            .collect();
+        let mut open = self.open_orders(book, participant, &replaced);
+        for (side, level) in [(Side::Buy, entry.bid), (Side::Sell, entry.ask)] {
+            let Some(level) = level.filter(|level| level.size > 0) else {
+                continue;
+            };
+            let request =
+                OrderRequest::new(participant, key.clone(), side, level.price, level.size);
+            self.check_against(book, &request, theo, &open)?;
+            open.push(OpenOrder::new(key.clone(), side, level.size));
+        }
+
+        let actions = expiration
+            .chain()
+            .apply_mass_quote_entry(participant, entry)?;
+        let current = contract.participant_quote(participant).unwrap_or_default();
+        for order in [current.bid, current.ask].into_iter().flatten() {
+            if !self.orders.contains_key(&order.order_id) {
+                self.track(order.order_id, participant, &key);
+            }
+        }
+        Ok(actions)
+    }
+
+    /// Runs the pre-trade checks of an order given the account's other
+    /// open orders.
+    fn check_against(
+        &self,
+        book: &UnderlyingOrderBook,
+        request: &OrderRequest,
+        theo: Option<f64>,
+        open: &[OpenOrder],
+    ) -> Result<()> {
+        if request.quantity == 0 || request.price == 0 {
+            return Err(Error::validation(
+                "order quantity and price must be positive",
+            ));
+        }
+        if request.key.underlying != book.underlying() {
+            return Err(Error::validation(format!(
+                "order for {} sent to {}",
+                request.key.underlying,
+                book.underlying()
+            )));
+        }
+        let strike = contract_book(book, &request.key)?;
+        let contract = strike.get(request.key.option_style);
+        let limits = &self.limits;
+
+        if let Some(limit) = limits.max_order_size
+            && request.quantity > limit
+        {
+            return Err(breach(
+                "max_order_size",
+                request.quantity as f64,
+                limit as f64,
+            ));
+        }
+        if let Some(limit) = limits.max_notional {
+            let multiplier = strike.terms().multiplier.to_f64().unwrap_or(1.0);
+            let notional = request.price as f64 * request.quantity as f64 * multiplier;
+            if notional > limit {
+                return Err(breach("max_notional", notional, limit));
+            }
+        }
+        if let Some(limit) = limits.max_price_deviation
+            && let Some(reference) = theo.or_else(|| contract.mid_price())
+            && reference > 0.0
+        {
+            let deviation = (request.price as f64 - reference).abs() / reference;
+            if deviation > limit {
+                return Err(breach("max_price_deviation", deviation, limit));
+            }
+        }
+        if let Some(limit) = limits.max_open_orders
+            && open.len() >= limit
+        {
+            return Err(breach(
+                "max_open_orders",
+                (open.len() + 1) as f64,
+                limit as f64,
+            ));
+        }
+        self.keeper.check_order_with_open_orders(
+            &request.account,
+            &request.key,
+            request.side,
+            request.quantity,
+            open,
+        )?;
+        if limits.has_greek_limits() {
+            self.check_greeks(book, request, open)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the account's Greeks in the worst case where the order and
+    /// either all open buys or all open sells fill.
+    ///
+    /// As for inventory, the order is rejected only if it takes a Greek
+    /// above its limit and further from zero than without it.
+    fn check_greeks(
+        &self,
+        book: &UnderlyingOrderBook,
+        request: &OrderRequest,
+        open: &[OpenOrder],
+    ) -> Result<()> {
+        let strike = contract_book(book, &request.key)?;
+        let symbol = strike.get(request.key.option_style).symbol();
+        let mut order = PositionSet::new();
+        order.set(symbol, signed(request.side, request.quantity));
+        let order_greeks = AggregatedGreeks::from_strike(
+            &strike,
+            &ExposureWeights::Positions(&order),
+            book.spot_price(),
+        );
+        if order_greeks.missing_count > 0 {
+            return Err(Error::greeks(format!(
+                "no Greeks for {} {} {:?}",
+                request.key.underlying, request.key.strike, request.key.option_style
+            )));
+        }
+
+        // Scenarios: open buys fill, or open sells fill.
+        let scenarios = [Side::Buy, Side::Sell].map(|side| {
+            let mut positions = self.keeper.position_set(&request.account);
+            for open_order in open.iter().filter(|o| o.side == side) {
+                if let Ok(symbol) = contract_symbol(book, &open_order.key) {
+                    positions.add(symbol, signed(side, open_order.quantity));
+                }
+            }
+            book.aggregate_greeks(&ExposureWeights::Positions(&positions))
+        });
+        let worst = |pick: GreekMeasure, with_order: bool| {
+            scenarios
+                .iter()
+                .map(|greeks| {
+                    let extra = if with_order { pick(&order_greeks) } else { 0.0 };
+                    (pick(greeks) + extra).abs()
+                })
+                .fold(0.0, f64::max)
+        };
+
+        let checks: [(&str, GreekMeasure, Option<f64>); 3] = [
+            (
+                "max_cash_delta",
+                |g| g.cash_delta,
+                self.limits.max_cash_delta,
+            ),
+            (
+                "max_dollar_gamma",
+                |g| g.dollar_gamma,
+                self.limits.max_dollar_gamma,
+            ),
+            ("max_vega", |g| g.vega_per_point, self.limits.max_vega),
+        ];
+        for (name, pick, limit) in checks {
+            let Some(limit) = limit else {
+                continue;
+            };
+            let value = worst(pick, true);
+            if value > limit && value > worst(pick, false) {
+                return Err(breach(name, value, limit));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads one Greek of an aggregate.
+type GreekMeasure = fn(&AggregatedGreeks) -> f64;
+
+/// Returns the signed quantity of a side.
+fn signed(side: Side, quantity: u64) -> i64 {
+    let quantity = i64::try_from(quantity).unwrap_or(i64::MAX);
+    match side {
+        Side::Buy => quantity,
+        Side::Sell => -quantity,
+    }
+}
+
+/// Returns the strike book listing a contract.
+fn contract_book(book: &UnderlyingOrderBook, key: &ContractKey) -> Result<Arc<StrikeOrderBook>> {
+    book.get_expiration(&key.expiration)?.get_strike(key.strike)
+}
+
+/// Returns the symbol of a listed contract.
+fn contract_symbol(book: &UnderlyingOrderBook, key: &ContractKey) -> Result<String> {
+    let strike = contract_book(book, key)?;
+    Ok(strike.get(key.option_style).symbol().to_string())
+}
+
+/// Creates the error of a failed check of the named limit.
+fn breach(limit_type: &str, value: f64, limit: f64) -> Error {
+    Error::risk_limit_breached(
+        limit_type,
+        Decimal::from_f64(limit).unwrap_or_default(),
+        Decimal::from_f64(value).unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::{QuoteLevel, ReferencePriceKind};
+    use crate::pricing::FlatVolatility;
+    use crate::risk::InventoryLimits;
+    use optionstratlib::prelude::pos_or_panic;
+    use optionstratlib::{ExpirationDate, OptionStyle};
+
+    fn expiration() -> ExpirationDate {
+        ExpirationDate::Days(pos_or_panic!(30.0))
+    }
+
+    fn book() -> UnderlyingOrderBook {
+        let book = UnderlyingOrderBook::new("SPX");
+        book.get_or_create_expiration(expiration())
+            .get_or_create_strike(100);
+        book
+    }
+
+    fn strike(book: &UnderlyingOrderBook) -> Arc<StrikeOrderBook> {
+        book.get_expiration(&expiration())
+            .unwrap()
+            .get_strike(100)
+            .unwrap()
+    }
+
+    fn key() -> ContractKey {
+        ContractKey::new("SPX", expiration(), 100, OptionStyle::Call)
+    }
+
+    fn order(side: Side, price: u128, quantity: u64) -> OrderRequest {
+        OrderRequest::new("A", key(), side, price, quantity)
+    }
+
+    fn limit_type(err: &Error) -> &str {
+        match err {
+            Error::RiskLimitBreached { limit_type, .. } => limit_type,
+            other => panic!("unexpected error {other}"),
+        }
+    }
+
+    #[test]
+    fn test_order_validation() {
+        let book = book();
+        let engine = RiskEngine::default();
+        assert!(engine.check(&book, &order(Side::Buy, 30, 0), None).is_err());
+        assert!(engine.check(&book, &order(Side::Buy, 0, 1), None).is_err());
+
+        let mut other = order(Side::Buy, 30, 1);
+        other.key.strike = 110;
+        assert!(matches!(
+            engine.check(&book, &other, None),
+            Err(Error::StrikeNotFound { .. })
+        ));
+        other.key.underlying = "NDX".to_string();
+        assert!(matches!(
+            engine.check(&book, &other, None),
+            Err(Error::ValidationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_order_size_limit() {
+        let book = book();
+        let limits = PreTradeLimits::none().with_max_order_size(10);
+        let engine = RiskEngine::new(limits, PositionKeeper::default());
+
+        engine.check(&book, &order(Side::Buy, 5, 10), None).unwrap();
+        let err = engine
+            .check(&book, &order(Side::Buy, 5, 11), None)
+            .unwrap_err();
+        assert_eq!(limit_type(&err), "max_order_size");
+        assert!(matches!(
+            err,
+            Error::RiskLimitBreached { limit, current, .. }
+                if limit == Decimal::from(10) && current == Decimal::from(11)
+        ));
+    }
+
+    #[test]
+    fn test_notional_limit() {
+        let book = book();
+        let limits = PreTradeLimits::none().with_max_notional(500.0);
+        let engine = RiskEngine::new(limits, PositionKeeper::default());
+
+        engine
+            .check(&book, &order(Side::Buy, 50, 10), None)
+            .unwrap();
+        let err = engine
+            .check(&book, &order(Side::Buy, 60, 10), None)
+            .unwrap_err();
+        assert_eq!(limit_type(&err), "max_notional");
+    }
+
+    #[test]
+    fn test_price_deviation_limit() {
+        let book = book();
+        let limits = PreTradeLimits::none().with_max_price_deviation(0.2);
+        let engine = RiskEngine::new(limits, PositionKeeper::default());
+
+        // No theo and no mid: the check is skipped.
+        engine.check(&book, &order(Side::Buy, 30, 1), None).unwrap();
+        let err = engine
+            .check(&book, &order(Side::Buy, 30, 1), Some(20.0))
+            .unwrap_err();
+        assert_eq!(limit_type(&err), "max_price_deviation");
+
+        // Without a theo, the mid of 22 is the reference.
+        let call = strike(&book);
+        call.call()
+            .add_limit_order(OrderId::new(), Side::Buy, 20, 1)
+            .unwrap();
+        call.call()
+            .add_limit_order(OrderId::new(), Side::Sell, 24, 1)
+            .unwrap();
+        engine.check(&book, &order(Side::Buy, 21, 1), None).unwrap();
+        let err = engine
+            .check(&book, &order(Side::Buy, 30, 1), None)
+            .unwrap_err();
+        assert_eq!(limit_type(&err), "max_price_deviation");
+    }
+
+    #[test]
+    fn test_open_order_limit() {
+        let book = book();
+        let limits = PreTradeLimits::none().with_max_open_orders(2);
+        let mut engine = RiskEngine::new(limits, PositionKeeper::default());
+
+        let first = engine
+            .submit(&book, &order(Side::Buy, 20, 1), None)
+            .unwrap()
+            .order_id;
+        engine
+            .submit(&book, &order(Side::Sell, 24, 1), None)
+            .unwrap();
+        assert_eq!(engine.open_order_count(&book, "A"), 2);
+        let err = engine
+            .submit(&book, &order(Side::Buy, 21, 1), None)
+            .unwrap_err();
+        assert_eq!(limit_type(&err), "max_open_orders");
+
+        assert!(engine.cancel(&book, first).unwrap());
+        assert!(!engine.cancel(&book, first).unwrap());
+        engine
+            .submit(&book, &order(Side::Buy, 21, 1), None)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_submit_applies_own_fills() {
+        let book = book();
+        let mut engine = RiskEngine::default();
+        strike(&book)
+            .call()
+            .add_limit_order(OrderId::new(), Side::Sell, 5, 10)
+            .unwrap();
+
+        // The account's buy lifts the resting seller.
+        let ack = engine.submit(&book, &order(Side::Buy, 5, 4), None).unwrap();
+        assert_eq!(ack.fills.fills, 1);
+        assert_eq!(engine.keeper().position("A", &key()).unwrap().quantity, 4);
+        assert_eq!(engine.open_order_count(&book, "A"), 0);
+        assert!(engine.keeper().order_account(ack.order_id).is_none());
+    }
+
+    #[test]
+    fn test_fills_read_only_from_engine_contracts() {
+        let book = book();
+        let mut engine = RiskEngine::default();
+        let contracts = strike(&book);
+        let put = contracts.put();
+        put.add_limit_order(OrderId::new(), Side::Sell, 5, 1)
+            .unwrap();
+        put.add_limit_order(OrderId::new(), Side::Buy, 5, 1)
+            .unwrap();
+
+        // The put traded, but the engine has no orders there.
+        let ack = engine.submit(&book, &order(Side::Buy, 5, 1), None).unwrap();
+        assert_eq!(ack.fills.executions, 0);
+        assert_eq!(engine.process_fills(&book).executions, 0);
+
+        contracts
+            .call()
+            .add_limit_order(OrderId::new(), Side::Sell, 5, 1)
+            .unwrap();
+        let report = engine.process_fills(&book);
+        assert_eq!((report.executions, report.fills), (1, 1));
+        assert_eq!(engine.open_order_count(&book, "A"), 0);
+    }
+
+    #[test]
+    fn test_inventory_limit_includes_open_orders() {
+        let book = book();
+        let keeper = PositionKeeper::new(InventoryLimits::unlimited().with_max_contract(5));
+        let mut engine = RiskEngine::new(PreTradeLimits::none(), keeper);
+
+        engine.submit(&book, &order(Side::Buy, 5, 3), None).unwrap();
+        // Nothing is filled yet, but the resting buy counts.
+        assert!(matches!(
+            engine.check(&book, &order(Side::Buy, 5, 3), None),
+            Err(Error::InventoryLimitExceeded { .. })
+        ));
+        engine.check(&book, &order(Side::Buy, 5, 2), None).unwrap();
+        engine.check(&book, &order(Side::Sell, 6, 3), None).unwrap();
+
+        // Once filled, the position takes its place.
+        strike(&book)
+            .call()
+            .add_limit_order(OrderId::new(), Side::Sell, 5, 3)
+            .unwrap();
+        engine.process_fills(&book);
+        assert_eq!(engine.open_order_count(&book, "A"), 0);
+        assert!(matches!(
+            engine.check(&book, &order(Side::Buy, 5, 3), None),
+            Err(Error::InventoryLimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_greek_limits_include_open_orders() {
+        let book = book();
+        book.update_reference_price(ReferencePriceKind::Index, 100);
+        let limits = PreTradeLimits::none().with_max_cash_delta(300.0);
+        let mut engine = RiskEngine::new(limits, PositionKeeper::default());
+
+        let err = engine
+            .check(&book, &order(Side::Buy, 5, 1), None)
+            .unwrap_err();
+        assert!(matches!(err, Error::GreeksError { .. }));
+
+        // Long 4 resting ATM calls: about 4 * 0.5 * 100 = 200 of cash delta.
+        book.refresh_greeks(&FlatVolatility(0.2)).unwrap();
+        engine.submit(&book, &order(Side::Buy, 5, 4), None).unwrap();
+        engine.check(&book, &order(Side::Buy, 5, 1), None).unwrap();
+        let err = engine
+            .check(&book, &order(Side::Buy, 5, 2), None)
+            .unwrap_err();
+        assert_eq!(limit_type(&err), "max_cash_delta");
+        // Selling reduces the delta.
+        engine.check(&book, &order(Side::Sell, 6, 4), None).unwrap();
+    }
+
+    #[test]
+    fn test_checked_mass_quote() {
+        let book = book();
+        let keeper = PositionKeeper::new(InventoryLimits::unlimited().with_max_contract(5));
+        let limits = PreTradeLimits::none().with_max_order_size(10);
+        let mut engine = RiskEngine::new(limits, keeper);
+        let quote = |bid: u64, ask: u64| {
+            MassQuote::new("MM").with_entry(
+                expiration(),
+                100,
+                OptionStyle::Call,
+                Some(QuoteLevel::new(10, bid)),
+                Some(QuoteLevel::new(12, ask)),
+            )
+        };
+
+        let (report, _) = engine.apply_mass_quote(&book, &quote(5, 5));
+        assert_eq!(report.accepted_count(), 1);
+        assert_eq!(engine.open_order_count(&book, "MM"), 2);
+        // Replacing the quote does not count the orders it replaces.
+        let (report, _) = engine.apply_mass_quote(&book, &quote(4, 5));
+        assert_eq!(report.accepted_count(), 1);
+
+        let (report, _) = engine.apply_mass_quote(&book, &quote(6, 5));
+        assert_eq!(report.rejected_count(), 1);
+        let (report, _) = engine.apply_mass_quote(&book, &quote(4, 11));
+        assert_eq!(report.rejected_count(), 1);
+        let call = strike(&book);
+        assert_eq!(call.call().bid_depth_at_price(10), 4);
+        assert_eq!(call.call().ask_depth_at_price(12), 5);
+
+        // Fills of the quote reach the participant before the next checks.
+        call.call()
+            .add_limit_order(OrderId::new(), Side::Sell, 10, 3)
+            .unwrap();
+        let (report, fills) = engine.apply_mass_quote(&book, &quote(3, 5));
+        assert_eq!(fills.fills, 1);
+        assert_eq!(report.rejected_count(), 1);
+        assert_eq!(engine.keeper().position("MM", &key()).unwrap().quantity, 3);
+        let (report, _) = engine.apply_mass_quote(&book, &quote(2, 5));
+        assert_eq!(report.accepted_count(), 1);
+    }
+}